@@ -0,0 +1,371 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    heif::{parse_boxes, read_box_header},
+    locale::tr,
+    makernote::{read_ifd, IfdEntry, MakerNote, MakerNoteContext},
+    tiff::detect_endian,
+    ImageMetadata,
+};
+
+/// "Apple iOS\0" + 2 字节版本 + 字节序标记
+const HEADER_LEN: usize = 14;
+
+/// Live Photo 视频中记录配对 ID 的 QuickTime 元数据键
+const CONTENT_IDENTIFIER_KEY: &[u8] = b"com.apple.quicktime.content.identifier";
+
+/// 读取 moov 盒子的上限，正常的 moov 只有几百 KB
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Live Photo 配对信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct LivePhoto {
+    /// MakerNote 中的 ContentIdentifier，与配套 MOV 中的 content.identifier 相同
+    pub content_identifier: String,
+    /// 同一目录中 content.identifier 相同的视频，未经 [`LivePhotoIndex::pair`] 配对或未找到时为 None
+    pub video: Option<PathBuf>,
+}
+
+/// 解码 Apple iOS MakerNote
+///
+/// 头部之后是一个 IFD，字节序由头部中的 II/MM 标记决定，偏移相对于 MakerNote 开头
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `_ctx`: 未使用，Apple MakerNote 是自包含的
+///
+/// # 返回值
+/// - 解码出的标签；缺少头部或 IFD 损坏时返回 None
+pub fn decode_apple_maker_note(data: &[u8], _ctx: &MakerNoteContext) -> Option<MakerNote> {
+    if !data.starts_with(b"Apple iOS\0") {
+        return None;
+    }
+    let endian = detect_endian(data.get(12..)?)?;
+    let entries = read_ifd(data, HEADER_LEN, &endian, 0)?;
+
+    let mut note = MakerNote::new("Apple", "Apple iOS");
+    for e in &entries {
+        decode_entry(&mut note, e);
+    }
+    Some(note)
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry) {
+    let id = e.tag;
+    let yes_no = |v: i64| if v == 0 { tr("No", "否", "いいえ") } else { tr("Yes", "是", "はい") };
+    let ratio = |(n, d): (i32, i32)| if d == 0 { 0.0 } else { n as f64 / d as f64 };
+    match id {
+        0x000b => note.push(id, "BurstUUID", tr("Burst UUID", "连拍 UUID", "連写 UUID"), e.text()),
+        0x0011 => note.push(id, "ContentIdentifier", tr("Content Identifier", "实况照片配对 ID", "Live Photo ペアリング ID"), e.text()),
+        0x0015 => note.push(id, "ImageUniqueID", tr("Image Unique ID", "图像唯一 ID", "画像ユニーク ID"), e.text()),
+        0x0020 => note.push(id, "ImageCaptureRequestID", tr("Image Capture Request ID", "拍摄请求 ID", "撮影リクエスト ID"), e.text()),
+        0x002b => note.push(id, "PhotoIdentifier", tr("Photo Identifier", "照片 ID", "写真 ID"), e.text()),
+        0x0008 => {
+            // 单位为 g，x 轴朝向机身右侧，y 轴朝向机身上方，z 轴朝向镜头后方
+            let v: Vec<f64> = e.srationals().into_iter().map(ratio).collect();
+            if let [x, y, z] = v[..] {
+                note.push(id, "AccelerationVector", tr("Acceleration Vector", "加速度矢量", "加速度ベクトル"), format!("{:.4} {:.4} {:.4} g", x, y, z));
+            }
+        }
+        0x000c => {
+            let v: Vec<f64> = e.srationals().into_iter().map(ratio).collect();
+            if let [near, far] = v[..] {
+                note.push(id, "FocusDistanceRange", tr("Focus Distance Range", "对焦距离范围", "撮影距離範囲"), format!("{:.2} - {:.2} m", near, far));
+            }
+        }
+        0x001d | 0x0021 | 0x0030 => {
+            if let Some(&v) = e.srationals().first() {
+                let (name, label) = match id {
+                    0x001d => ("LuminanceNoiseAmplitude", tr("Luminance Noise Amplitude", "亮度噪声幅度", "輝度ノイズ振幅")),
+                    0x0021 => ("HDRHeadroom", tr("HDR Headroom", "HDR 余量", "HDR ヘッドルーム")),
+                    _ => ("HDRGain", tr("HDR Gain", "HDR 增益", "HDR ゲイン")),
+                };
+                note.push(id, name, label, format!("{:.4}", ratio(v)));
+            }
+        }
+        _ => {
+            let Some(v) = e.first_int() else { return };
+            match id {
+                0x0001 => note.push(id, "MakerNoteVersion", tr("MakerNote Version", "MakerNote 版本", "MakerNote バージョン"), v.to_string()),
+                0x0004 => note.push(id, "AEStable", tr("AE Stable", "自动曝光稳定", "AE 安定"), yes_no(v)),
+                0x0005 => note.push(id, "AETarget", tr("AE Target", "自动曝光目标", "AE ターゲット"), v.to_string()),
+                0x0006 => note.push(id, "AEAverage", tr("AE Average", "自动曝光平均值", "AE 平均"), v.to_string()),
+                0x0007 => note.push(id, "AFStable", tr("AF Stable", "自动对焦稳定", "AF 安定"), yes_no(v)),
+                0x000a => {
+                    let value = match v {
+                        2 => tr("HDR Image (legacy)", "HDR 图像（旧版）", "HDR 画像（旧形式）"),
+                        3 => tr("HDR Image", "HDR 图像", "HDR 画像"),
+                        4 => tr("Original Image", "原始图像", "オリジナル画像"),
+                        _ => tr("Unknown", "未知", "不明"),
+                    };
+                    note.push(id, "HDRImageType", tr("HDR Image Type", "HDR 图像类型", "HDR 画像タイプ"), value);
+                }
+                0x000f => note.push(id, "OISMode", tr("OIS Mode", "光学防抖模式", "光学式手ブレ補正モード"), v.to_string()),
+                0x0014 => {
+                    let value = match v {
+                        1 => "ProRAW",
+                        2 => tr("Portrait", "人像", "ポートレート"),
+                        10 => tr("Photo", "照片", "写真"),
+                        11 => tr("Manual Focus", "手动对焦", "マニュアルフォーカス"),
+                        12 => tr("Scene", "场景", "シーン"),
+                        _ => tr("Unknown", "未知", "不明"),
+                    };
+                    note.push(id, "ImageCaptureType", tr("Camera Type", "拍摄类型", "撮影タイプ"), value);
+                }
+                0x0017 => note.push(id, "LivePhotoVideoIndex", tr("Live Photo Video Index", "实况照片视频索引", "Live Photo 動画インデックス"), v.to_string()),
+                0x002d => note.push(id, "ColorTemperature", tr("Color Temperature", "色温", "色温度"), format!("{} K", v)),
+                0x002e => {
+                    let value = match v {
+                        0 => tr("Back Wide Angle", "后置广角", "背面広角"),
+                        1 => tr("Back Normal", "后置标准", "背面標準"),
+                        6 => tr("Front", "前置", "前面"),
+                        _ => tr("Unknown", "未知", "不明"),
+                    };
+                    note.push(id, "CameraType", tr("Camera", "摄像头", "カメラ"), value);
+                }
+                0x002f => note.push(id, "FocusPosition", tr("Focus Position", "对焦位置", "フォーカス位置"), v.to_string()),
+                0x004a => note.push(id, "FrontFacingCamera", tr("Front-facing Camera", "前置摄像头", "前面カメラ"), yes_no(v)),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// 按目录缓存的 Live Photo 视频索引
+///
+/// 读取元数据时不访问图片以外的文件，配对是单独的一步：每个目录第一次用到时扫描其中的 .mov 文件，
+/// 建立 content.identifier → 视频路径的映射，同一目录中的其余图片直接查表
+#[derive(Debug, Default)]
+pub struct LivePhotoIndex {
+    /// 目录 → (content.identifier → 按文件名排序的视频路径)
+    dirs: HashMap<PathBuf, HashMap<String, Vec<PathBuf>>>,
+}
+
+impl LivePhotoIndex {
+    /// 创建空索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为图片的 Live Photo 信息填入配套视频，图片没有 ContentIdentifier 时不做任何事
+    ///
+    /// # 参数
+    /// - `meta`: [`crate::read_metadata`] 返回的元数据
+    pub fn pair(&mut self, meta: &mut ImageMetadata) {
+        if let Some(live) = &mut meta.live_photo {
+            live.video = self.find(&meta.path, &live.content_identifier);
+        }
+    }
+
+    /// 查找与图片配对的 Live Photo 视频
+    ///
+    /// 同一目录中有多个 content.identifier 相同的视频时，与图片同名的优先，其次按文件名排序取第一个
+    ///
+    /// # 参数
+    /// - `image`: 图片路径
+    /// - `content_identifier`: 图片 MakerNote 中的 ContentIdentifier
+    pub fn find(&mut self, image: &Path, content_identifier: &str) -> Option<PathBuf> {
+        let dir = match image.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let videos = self
+            .dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| index_directory(dir))
+            .get(content_identifier)?;
+        let stem = image.file_stem();
+        videos
+            .iter()
+            .find(|v| v.file_stem() == stem)
+            .or_else(|| videos.first())
+            .cloned()
+    }
+}
+
+/// 读取目录中所有 .mov 文件的 content.identifier
+///
+/// # 返回值
+/// - content.identifier → 视频路径（按文件名排序）；目录不可读时为空
+fn index_directory(dir: &Path) -> HashMap<String, Vec<PathBuf>> {
+    let mut videos: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("mov")))
+            .collect(),
+        Err(_) => return HashMap::new(),
+    };
+    // 排序以保证结果稳定
+    videos.sort();
+    let mut index: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for video in videos {
+        if let Some(id) = read_video_content_identifier(&video) {
+            index.entry(id).or_default().push(video);
+        }
+    }
+    index
+}
+
+/// 读取 QuickTime 视频 moov/meta 中的 com.apple.quicktime.content.identifier
+///
+/// # 返回值
+/// - 配对 ID；文件不是 QuickTime 格式或没有该元数据时返回 None
+pub fn read_video_content_identifier(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    content_identifier(&mut file, len)
+}
+
+/// 从 QuickTime 数据中读取 content.identifier，参见 [`read_video_content_identifier`]
+///
+/// # 参数
+/// - `reader`: 视频数据
+/// - `len`: 数据总长度
+fn content_identifier<R: Read + Seek>(reader: &mut R, len: u64) -> Option<String> {
+    let moov = read_moov(reader, len)?;
+    let boxes = parse_boxes(&moov, 0).ok()?;
+    let meta = boxes.iter().find(|b| &b.box_type == b"meta")?;
+    // QuickTime 的 meta 是普通盒子，ISO BMFF 的 meta 则多出 4 字节版本和标志；
+    // 后者全为 0，会被当成延伸到结尾的盒子，所以按内容区分而不是看能否解析
+    let body = match meta.body.get(..4) {
+        Some([0, 0, 0, 0]) => &meta.body[4..],
+        _ => meta.body,
+    };
+    let children = parse_boxes(body, 0).ok()?;
+    let keys = children.iter().find(|b| &b.box_type == b"keys")?.body;
+    let ilst = children.iter().find(|b| &b.box_type == b"ilst")?.body;
+
+    // keys：版本和标志、条目数，之后每个条目为长度 + 命名空间 + 键名，编号从 1 开始
+    let count = u32::from_be_bytes(keys.get(4..8)?.try_into().ok()?);
+    let mut pos = 8;
+    let mut index = None;
+    for i in 1..=count {
+        let size = u32::from_be_bytes(keys.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        if size < 8 {
+            return None;
+        }
+        if keys.get(pos + 8..pos + size)? == CONTENT_IDENTIFIER_KEY {
+            index = Some(i);
+            break;
+        }
+        pos += size;
+    }
+    let index = index?;
+
+    // ilst：每个子盒子的类型是键的编号，里面的 data 盒子为类型 + 语言 + 值
+    let item = parse_boxes(ilst, 0).ok()?.into_iter().find(|b| u32::from_be_bytes(b.box_type) == index)?;
+    let data = parse_boxes(item.body, 0).ok()?.into_iter().find(|b| &b.box_type == b"data")?;
+    let value = String::from_utf8_lossy(data.body.get(8..)?).trim_end_matches('\0').to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// 逐个跳过顶层盒子，只把 moov 读入内存（mdat 可能有几百 MB）
+fn read_moov<R: Read + Seek>(reader: &mut R, len: u64) -> Option<Vec<u8>> {
+    let mut pos = 0u64;
+    while pos < len {
+        reader.seek(SeekFrom::Start(pos)).ok()?;
+        let mut header = Vec::with_capacity(16);
+        reader.by_ref().take(16).read_to_end(&mut header).ok()?;
+        let header = read_box_header(&header, len - pos, pos as usize).ok()?;
+        if &header.box_type == b"moov" {
+            let body_len = header.size - header.header_len as u64;
+            if body_len > MAX_MOOV_SIZE {
+                return None;
+            }
+            reader.seek(SeekFrom::Start(pos + header.header_len as u64)).ok()?;
+            let mut body = vec![0u8; body_len as usize];
+            reader.read_exact(&mut body).ok()?;
+            return Some(body);
+        }
+        pos += header.size;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heif::test_util::make_box;
+    use crate::makernote::test_util::{build_ifd, u32s, value};
+    use little_exif::endian::Endian;
+    use std::io::Cursor;
+
+    /// "Apple iOS\0" + 版本 + 字节序标记 + IFD，偏移相对于 MakerNote 开头
+    fn maker_note(endian: &Endian, entries: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = b"Apple iOS\0\0\x01".to_vec();
+        data.extend(match endian {
+            Endian::Big => b"MM",
+            Endian::Little => b"II",
+        });
+        data.extend(build_ifd(entries, HEADER_LEN, endian));
+        data
+    }
+
+    #[test]
+    fn maker_note_header() {
+        // 外层上下文的字节序与 MakerNote 无关
+        let ctx = MakerNoteContext { make: Some("Apple"), model: Some("iPhone 15 Pro"), tiff_offset: Some(1000), endian: Endian::Little };
+        for endian in [Endian::Big, Endian::Little] {
+            let data = maker_note(
+                &endian,
+                &[
+                    (0x0001, 9, u32s(&[14], &endian)),
+                    (0x0008, 10, u32s(&[-1i32 as u32, 2, 1, 4, 0, 1], &endian)),
+                    (0x000a, 9, u32s(&[3], &endian)),
+                    (0x0011, 2, b"7F5C9E1A-24B3\0".to_vec()),
+                    (0x002e, 9, u32s(&[6], &endian)),
+                ],
+            );
+            let note = decode_apple_maker_note(&data, &ctx).unwrap();
+            assert_eq!((note.vendor, note.format.as_str()), ("Apple", "Apple iOS"));
+            assert_eq!(value(&note, "MakerNoteVersion"), "14");
+            assert_eq!(value(&note, "AccelerationVector"), "-0.5000 0.2500 0.0000 g");
+            assert_eq!(value(&note, "HDRImageType"), "HDR Image");
+            assert_eq!(value(&note, "ContentIdentifier"), "7F5C9E1A-24B3");
+            assert_eq!(value(&note, "CameraType"), "Front");
+        }
+
+        assert!(decode_apple_maker_note(b"Apple iPhone\0\0MM\0\0", &ctx).is_none());
+        // 字节序标记缺失
+        assert!(decode_apple_maker_note(b"Apple iOS\0\0\x01XX\0\0\0\0\0\0", &ctx).is_none());
+    }
+
+    /// QuickTime 的 moov/meta：keys 中第 2 个键是 content.identifier，ilst 中编号 2 的项目是它的值
+    fn moov(meta_prefix: &[u8], identifier: &[u8]) -> Vec<u8> {
+        let key = |name: &[u8]| [&(8 + name.len() as u32).to_be_bytes()[..], b"mdta", name].concat();
+        let keys = [&[0, 0, 0, 0, 0, 0, 0, 2][..], &key(b"com.apple.quicktime.location.ISO6709"), &key(CONTENT_IDENTIFIER_KEY)].concat();
+        let data = make_box(b"data", &[&[0, 0, 0, 1, 0, 0, 0, 0][..], identifier].concat());
+        let ilst = make_box(b"ilst", &[make_box(&1u32.to_be_bytes(), b""), make_box(&2u32.to_be_bytes(), &data)].concat());
+        let meta = make_box(b"meta", &[meta_prefix, &make_box(b"keys", &keys), &ilst].concat());
+        make_box(b"moov", &[make_box(b"mvhd", &[0; 8]), meta].concat())
+    }
+
+    fn identifier(file: &[u8]) -> Option<String> {
+        content_identifier(&mut Cursor::new(file), file.len() as u64)
+    }
+
+    #[test]
+    fn video_content_identifier() {
+        // 64 位长度的 mdat 在 moov 之前，被跳过而不读入
+        let mdat = [&[0, 0, 0, 1][..], b"mdat", &24u64.to_be_bytes(), &[0xaa; 8]].concat();
+        let ftyp = make_box(b"ftyp", b"qt  \0\0\0\0qt  ");
+        let file = [&ftyp[..], &mdat, &moov(b"", b"7F5C9E1A-24B3")].concat();
+        assert_eq!(identifier(&file).as_deref(), Some("7F5C9E1A-24B3"));
+
+        // ISO BMFF 形式的 meta 多出 4 字节版本和标志
+        let file = [&ftyp[..], &moov(&[0; 4], b"ABC\0")].concat();
+        assert_eq!(identifier(&file).as_deref(), Some("ABC"));
+
+        // 值为空、没有 moov、盒子长度越界
+        assert_eq!(identifier(&[&ftyp[..], &moov(b"", b"")].concat()), None);
+        assert_eq!(identifier(&[&ftyp[..], &mdat].concat()), None);
+        let huge = [&ftyp[..], &[0, 0, 0, 1], b"mdat", &[0xff; 8], &moov(b"", b"X")].concat();
+        assert_eq!(identifier(&huge), None);
+    }
+}
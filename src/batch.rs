@@ -0,0 +1,337 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Condvar, Mutex, PoisonError,
+    },
+};
+
+use crate::{read_metadata, Error, ImageMetadata, SUPPORTED_FORMATS};
+
+/// 批量处理选项
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// 是否递归进入子目录
+    pub recursive: bool,
+    /// 只处理这些扩展名（小写），为空时使用 SUPPORTED_FORMATS
+    pub extensions: Vec<String>,
+}
+
+impl BatchOptions {
+    /// 判断文件扩展名是否在处理范围内
+    fn accepts(&self, path: &Path) -> bool {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        if self.extensions.is_empty() {
+            SUPPORTED_FORMATS.contains(&ext.as_str())
+        } else {
+            self.extensions.contains(&ext)
+        }
+    }
+}
+
+/// 展开输入路径后得到的文件列表
+#[derive(Debug, Default)]
+pub struct FileList {
+    /// 待处理的图片文件（按路径排序）
+    pub files: Vec<PathBuf>,
+    /// 因扩展名不在范围内而跳过的文件
+    pub skipped: Vec<PathBuf>,
+    /// 无法展开的输入（路径不存在、目录不可读、通配符错误等）
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+/// 单个文件的处理结果
+#[derive(Debug)]
+pub struct BatchItem<T = ImageMetadata> {
+    pub path: PathBuf,
+    pub result: Result<T, Error>,
+}
+
+/// 判断输入是否包含通配符
+fn is_glob_pattern(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+/// 将命令行给出的文件、目录和通配符展开为图片文件列表
+///
+/// # 参数
+/// - `inputs`: 文件、目录或通配符路径
+/// - `opts`: 批量处理选项
+///
+/// # 返回值
+/// - 展开后的文件列表；直接给出的文件总会被处理，目录中的文件按扩展名过滤
+pub fn collect_image_files(inputs: &[PathBuf], opts: &BatchOptions) -> FileList {
+    let mut list = FileList::default();
+    for input in inputs {
+        let pattern = input.to_string_lossy();
+        if !input.exists() && is_glob_pattern(&pattern) {
+            match glob::glob(&pattern) {
+                Ok(paths) => {
+                    for entry in paths {
+                        match entry {
+                            Ok(p) if p.is_dir() => walk_dir(&p, opts, &mut list),
+                            Ok(p) => add_filtered(p, opts, &mut list),
+                            Err(e) => list.errors.push((e.path().to_path_buf(), Error::Io(e.into()))),
+                        }
+                    }
+                }
+                Err(e) => list.errors.push((
+                    input.clone(),
+                    Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string())),
+                )),
+            }
+        } else if input.is_dir() {
+            walk_dir(input, opts, &mut list);
+        } else {
+            // 直接指定的文件不过滤，交给 read_metadata 报告错误
+            list.files.push(input.clone());
+        }
+    }
+    list
+}
+
+/// 按扩展名过滤后加入文件列表
+fn add_filtered(path: PathBuf, opts: &BatchOptions, list: &mut FileList) {
+    if opts.accepts(&path) {
+        list.files.push(path);
+    } else {
+        list.skipped.push(path);
+    }
+}
+
+/// 遍历目录，非递归模式只处理第一层文件
+fn walk_dir(dir: &Path, opts: &BatchOptions, list: &mut FileList) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            list.errors.push((dir.to_path_buf(), Error::Io(e)));
+            return;
+        }
+    };
+    // 排序以保证输出顺序稳定
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            if opts.recursive {
+                walk_dir(&path, opts, list);
+            }
+        } else if path.is_file() {
+            add_filtered(path, opts, list);
+        }
+    }
+}
+
+/// 并行读取多个文件的元数据，单个文件出错不会中断整个批次
+///
+/// # 参数
+/// - `files`: 图片文件列表
+/// - `jobs`: 工作线程数，0 表示使用 CPU 核数
+///
+/// # 返回值
+/// - 与输入顺序一致的处理结果（与线程调度无关）
+pub fn read_metadata_batch(files: &[PathBuf], jobs: usize) -> Vec<BatchItem> {
+    process_batch(files, jobs, |path| read_metadata(path))
+}
+
+/// 并行读取多个文件的元数据，每个结果按输入顺序交给 emit，不等待整个批次结束
+///
+/// # 参数
+/// - `files`: 图片文件列表
+/// - `jobs`: 工作线程数，0 表示使用 CPU 核数
+/// - `emit`: 在调用线程上按输入顺序依次接收每个文件的结果
+pub fn for_each_metadata<G>(files: &[PathBuf], jobs: usize, emit: G)
+where
+    G: FnMut(BatchItem),
+{
+    process_batch_each(files, jobs, |path| read_metadata(path), emit)
+}
+
+/// 用多个线程对每个文件执行同一个处理函数
+///
+/// # 参数
+/// - `files`: 文件列表
+/// - `jobs`: 工作线程数，0 表示使用 CPU 核数
+/// - `f`: 对单个文件的处理函数
+///
+/// # 返回值
+/// - 与输入顺序一致的处理结果
+pub fn process_batch<T, F>(files: &[PathBuf], jobs: usize, f: F) -> Vec<BatchItem<T>>
+where
+    T: Send,
+    F: Fn(&Path) -> Result<T, Error> + Sync,
+{
+    let mut items = Vec::with_capacity(files.len());
+    process_batch_each(files, jobs, f, |item| items.push(item));
+    items
+}
+
+/// 每个工作线程最多领先已输出位置的文件数，决定重排缓冲区的大小
+const REORDER_WINDOW_PER_JOB: usize = 4;
+
+/// 调用线程已输出的文件数，工作线程据此等待；调用线程退出时设为 usize::MAX
+type EmitProgress = (Mutex<usize>, Condvar);
+
+/// 更新已输出的文件数并唤醒等待的工作线程
+fn advance(progress: &EmitProgress, emitted: usize) {
+    *progress.0.lock().unwrap_or_else(PoisonError::into_inner) = emitted;
+    progress.1.notify_all();
+}
+
+/// 调用线程退出时（包括 emit panic）放行所有工作线程，否则它们会一直等待，scope 无法结束
+struct ReleaseWorkers<'a>(&'a EmitProgress);
+
+impl Drop for ReleaseWorkers<'_> {
+    fn drop(&mut self) {
+        advance(self.0, usize::MAX);
+    }
+}
+
+/// 用多个线程对每个文件执行同一个处理函数，结果一就绪就按输入顺序交给 emit
+///
+/// 先完成的结果暂存在按下标索引的重排缓冲区中，直到它之前的文件都已输出。
+/// 工作线程不会领先已输出位置超过 4 × jobs 个文件，遇到很慢的文件时会等待，
+/// 因此重排缓冲区最多保存 4 × jobs 个结果，而不是整个批次
+///
+/// # 参数
+/// - `files`: 文件列表
+/// - `jobs`: 工作线程数，0 表示使用 CPU 核数
+/// - `f`: 对单个文件的处理函数
+/// - `emit`: 在调用线程上按输入顺序依次接收每个文件的结果
+pub fn process_batch_each<T, F, G>(files: &[PathBuf], jobs: usize, f: F, mut emit: G)
+where
+    T: Send,
+    F: Fn(&Path) -> Result<T, Error> + Sync,
+    G: FnMut(BatchItem<T>),
+{
+    let jobs = effective_jobs(jobs).min(files.len().max(1));
+    if jobs <= 1 {
+        for path in files {
+            emit(BatchItem { path: path.clone(), result: f(path) });
+        }
+        return;
+    }
+
+    // 各线程通过共享下标领取任务，结果连同下标发回调用线程
+    let next = AtomicUsize::new(0);
+    let progress: EmitProgress = (Mutex::new(0), Condvar::new());
+    let window = REORDER_WINDOW_PER_JOB * jobs;
+    let (f, next, progress) = (&f, &next, &progress);
+    std::thread::scope(|scope| {
+        let _release = ReleaseWorkers(progress);
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= files.len() {
+                    break;
+                }
+                // 领先太多时等待；下标更小的文件都已被领取，不会因此死锁
+                let mut emitted = progress.0.lock().unwrap_or_else(PoisonError::into_inner);
+                while i >= emitted.saturating_add(window) {
+                    emitted = progress.1.wait(emitted).unwrap_or_else(PoisonError::into_inner);
+                }
+                drop(emitted);
+                // 接收端只会在调用线程 panic 时关闭，此时不必继续处理
+                if tx.send((i, f(&files[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending: HashMap<usize, Result<T, Error>> = HashMap::new();
+        let mut expected = 0usize;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            let before = expected;
+            while let Some(result) = pending.remove(&expected) {
+                emit(BatchItem { path: files[expected].clone(), result });
+                expected += 1;
+            }
+            if expected != before {
+                advance(progress, expected);
+            }
+        }
+    });
+}
+
+/// 计算实际使用的线程数
+fn effective_jobs(jobs: usize) -> usize {
+    if jobs > 0 {
+        jobs
+    } else {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn results_are_emitted_in_input_order() {
+        let files: Vec<PathBuf> = (0..32).map(|i| PathBuf::from(i.to_string())).collect();
+        // 靠前的文件处理得更慢，完成顺序与输入顺序相反
+        let work = |path: &Path| -> Result<usize, Error> {
+            let i: usize = path.to_str().unwrap().parse().unwrap();
+            std::thread::sleep(Duration::from_millis((32 - i as u64) / 4));
+            if i.is_multiple_of(5) {
+                Err(Error::UnsupportedFormat(i.to_string()))
+            } else {
+                Ok(i)
+            }
+        };
+
+        let mut seen = Vec::new();
+        process_batch_each(&files, 8, work, |item| {
+            let i: usize = item.path.to_str().unwrap().parse().unwrap();
+            assert_eq!(item.result.is_ok(), !i.is_multiple_of(5));
+            if let Ok(v) = item.result {
+                assert_eq!(v, i);
+            }
+            seen.push(i);
+        });
+        assert_eq!(seen, (0..32).collect::<Vec<_>>());
+
+        let items = process_batch(&files, 1, work);
+        assert!(items.iter().zip(&files).all(|(item, path)| &item.path == path));
+    }
+
+    #[test]
+    fn workers_stay_within_reorder_window() {
+        let files: Vec<PathBuf> = (0..64).map(|i| PathBuf::from(i.to_string())).collect();
+        let emitted = AtomicUsize::new(0);
+        let max_lead = AtomicUsize::new(0);
+        // 第一个文件很慢，其余文件不能无限领先
+        let work = |path: &Path| -> Result<(), Error> {
+            let i: usize = path.to_str().unwrap().parse().unwrap();
+            max_lead.fetch_max(i - emitted.load(Ordering::SeqCst).min(i), Ordering::SeqCst);
+            if i == 0 {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Ok(())
+        };
+        let mut count = 0;
+        process_batch_each(&files, 2, work, |_| {
+            count += 1;
+            emitted.store(count, Ordering::SeqCst);
+        });
+        assert_eq!(count, 64);
+        assert!(max_lead.load(Ordering::SeqCst) < REORDER_WINDOW_PER_JOB * 2);
+    }
+
+    #[test]
+    fn empty_batch() {
+        let mut count = 0;
+        process_batch_each(&[], 4, |_| Ok(()), |_| count += 1);
+        assert_eq!(count, 0);
+    }
+}
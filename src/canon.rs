@@ -0,0 +1,530 @@
+use crate::{
+    locale::tr,
+    makernote::{format_exposure_time, format_f_number, format_mm, guess_offset_base, read_ifd, u32_at, IfdEntry, MakerNote, MakerNoteContext},
+    tiff::detect_endian,
+};
+
+/// 解码 Canon MakerNote
+///
+/// JPEG / CR2 中的 MakerNote 是一个不带头部的 IFD，值的偏移相对于外层 TIFF 头；
+/// CR3 的 CMT3 盒子则是一个完整的 TIFF 结构，偏移相对于它自己的 TIFF 头
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: MakerNote 在 TIFF 中的偏移和外层字节序
+///
+/// # 返回值
+/// - 解码出的标签；IFD 损坏时返回 None
+pub fn decode_canon_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    let tiff_header = data.starts_with(b"II\x2a\0") || data.starts_with(b"MM\0\x2a");
+    let (endian, ifd_offset, offset_base) = match detect_endian(data).filter(|_| tiff_header) {
+        Some(endian) => {
+            let first = u32_at(data, 4, &endian)? as usize;
+            (endian, first, 0)
+        }
+        None => {
+            let endian = ctx.endian.clone();
+            let base = match ctx.tiff_offset {
+                Some(offset) => offset as i64,
+                None => guess_offset_base(data, 0, &endian).unwrap_or(0),
+            };
+            (endian, 0, base)
+        }
+    };
+    let entries = read_ifd(data, ifd_offset, &endian, offset_base)?;
+    // 焦距以 CameraSettings 中的 FocalUnits（每毫米的单位数）为单位
+    let focal_units = entries
+        .iter()
+        .find(|e| e.tag == 0x0001)
+        .and_then(|e| at(&e.i16s(), 25))
+        .filter(|&u| u > 0)
+        .unwrap_or(1) as f64;
+
+    let mut note = MakerNote::new("Canon", "Canon");
+    for e in &entries {
+        decode_entry(&mut note, e, focal_units);
+    }
+    Some(note)
+}
+
+/// Canon 的 EV 编码：低 5 位为小数部分，0x0C 和 0x14 分别表示 1/3 和 2/3 档
+fn canon_ev(v: i16) -> f64 {
+    let sign = if v < 0 { -1.0 } else { 1.0 };
+    let v = (v as i32).abs();
+    let frac = v & 0x1f;
+    let frac = match frac {
+        0x0c => 32.0 / 3.0,
+        0x14 => 64.0 / 3.0,
+        f => f as f64,
+    };
+    sign * ((v & !0x1f) as f64 + frac) / 32.0
+}
+
+/// EV 值对应的光圈
+fn canon_aperture(v: i16) -> f64 {
+    2f64.powf(canon_ev(v) / 2.0)
+}
+
+/// EV 值对应的曝光时间（秒）
+fn canon_exposure_time(v: i16) -> f64 {
+    2f64.powf(-canon_ev(v))
+}
+
+/// 按数组下标取值，下标超出范围时返回 None
+fn at(values: &[i16], index: usize) -> Option<i16> {
+    values.get(index).copied()
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry, focal_units: f64) {
+    let id = e.tag;
+    match id {
+        0x0001 => decode_camera_settings(note, &e.i16s(), focal_units),
+        0x0002 => {
+            if let Some(&focal) = e.u16s().get(1).filter(|&&v| v > 0) {
+                note.push(id, "FocalLength", tr("Focal Length", "焦距", "焦点距離"), format_mm(focal as f64 / focal_units));
+            }
+        }
+        0x0004 => decode_shot_info(note, &e.i16s()),
+        0x0006 => note.push(id, "CanonImageType", tr("Image Type", "图像类型", "画像タイプ"), e.text()),
+        0x0007 => note.push(id, "CanonFirmwareVersion", tr("Firmware Version", "固件版本", "ファームウェアバージョン"), e.text()),
+        0x0008 => {
+            if let Some(n) = e.u32s().first() {
+                note.push(id, "FileNumber", tr("File Number", "文件编号", "ファイル番号"), format!("{:03}-{:04}", n / 10000, n % 10000));
+            }
+        }
+        0x0009 => note.push(id, "OwnerName", tr("Owner Name", "机主姓名", "所有者名"), e.text()),
+        0x000c => {
+            if let Some(n) = e.u32s().first() {
+                note.push(id, "SerialNumber", tr("Serial Number", "机身序列号", "シリアル番号"), format!("{:010}", n));
+            }
+        }
+        0x0010 => {
+            if let Some(n) = e.u32s().first() {
+                note.push(id, "CanonModelID", tr("Model ID", "机型 ID", "機種 ID"), format!("0x{:08X}", n));
+            }
+        }
+        0x0026 => decode_af_info2(note, &e.u16s()),
+        0x0028 => {
+            let hex: String = e.u8s().iter().map(|b| format!("{:02x}", b)).collect();
+            note.push(id, "ImageUniqueID", tr("Image Unique ID", "图像唯一 ID", "画像ユニーク ID"), hex);
+        }
+        0x0035 => {
+            // TimeInfo：字节数 + 时区（分钟）+ 时区城市 + 夏令时（分钟）
+            let values = e.u32s();
+            if let Some(&tz) = values.get(1) {
+                let tz = tz as i32;
+                note.push(id, "TimeZone", tr("Time Zone", "时区", "タイムゾーン"), format!("{}{:02}:{:02}", if tz < 0 { '-' } else { '+' }, tz.abs() / 60, tz.abs() % 60));
+            }
+            if let Some(&dst) = values.get(3) {
+                note.push(id, "DaylightSavings", tr("Daylight Savings", "夏令时", "夏時間"), if dst == 0 { tr("Off", "关闭", "オフ") } else { tr("On", "开启", "オン") });
+            }
+        }
+        0x0093 => decode_file_info(note, &e.i16s()),
+        0x0095 => note.push(id, "LensModel", tr("Lens Model", "镜头型号", "レンズモデル"), e.text()),
+        0x0096 => note.push(id, "InternalSerialNumber", tr("Internal Serial Number", "内部序列号", "内部シリアル番号"), e.text()),
+        _ => {}
+    }
+}
+
+/// CameraSettings：下标 0 为字节数，之后每个元素是一项设置
+fn decode_camera_settings(note: &mut MakerNote, v: &[i16], units: f64) {
+    const ID: u16 = 0x0001;
+    if let Some(x) = at(v, 1) {
+        note.push(ID, "MacroMode", tr("Macro Mode", "微距模式", "マクロモード"), match x { 1 => tr("Macro", "微距", "マクロ"), 2 => tr("Normal", "普通", "ノーマル"), _ => tr("Unknown", "未知", "不明") });
+    }
+    if let Some(x) = at(v, 2).filter(|&x| x > 0) {
+        note.push(ID, "SelfTimer", tr("Self-timer", "自拍延时", "セルフタイマー"), format!("{:.1} {}", x as f64 / 10.0, tr("s", "秒", "秒")));
+    }
+    if let Some(x) = at(v, 3) {
+        let value = match x {
+            1 => tr("Economy", "经济", "エコノミー"),
+            2 => tr("Normal", "普通", "ノーマル"),
+            3 => tr("Fine", "精细", "ファイン"),
+            4 => "RAW",
+            5 => tr("Super Fine", "超精细", "スーパーファイン"),
+            7 => "CRAW",
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "Quality", tr("Quality", "画质", "画質"), value);
+    }
+    if let Some(x) = at(v, 4) {
+        let value = match x {
+            0 => tr("Off", "关闭", "オフ"),
+            1 => tr("Auto", "自动", "オート"),
+            2 => tr("On", "开启", "オン"),
+            3 => tr("Red-eye Reduction", "防红眼", "赤目軽減"),
+            4 => tr("Slow Sync", "慢速同步", "スローシンクロ"),
+            5 => tr("Auto + Red-eye Reduction", "自动 + 防红眼", "オート + 赤目軽減"),
+            6 => tr("On + Red-eye Reduction", "开启 + 防红眼", "オン + 赤目軽減"),
+            16 => tr("External Flash", "外置闪光灯", "外付けフラッシュ"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "CanonFlashMode", tr("Flash Mode", "闪光模式", "フラッシュモード"), value);
+    }
+    if let Some(x) = at(v, 5) {
+        let value = match x {
+            0 => tr("Single", "单拍", "1 コマ撮影"),
+            1 => tr("Continuous", "连拍", "連続撮影"),
+            2 => tr("Movie", "短片", "動画"),
+            3 => tr("Continuous (speed priority)", "连拍（速度优先）", "連続撮影（速度優先）"),
+            4 => tr("Low-speed Continuous", "低速连拍", "低速連続撮影"),
+            5 => tr("High-speed Continuous", "高速连拍", "高速連続撮影"),
+            6 => tr("Silent Single", "静音单拍", "静音 1 コマ撮影"),
+            9 => tr("Single (silent)", "单拍（静音）", "1 コマ撮影（静音）"),
+            10 => tr("Low-speed Continuous (silent)", "低速连拍（静音）", "低速連続撮影（静音）"),
+            11 => tr("High-speed Continuous (silent)", "高速连拍（静音）", "高速連続撮影（静音）"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "ContinuousDrive", tr("Drive Mode", "驱动模式", "ドライブモード"), value);
+    }
+    if let Some(x) = at(v, 7) {
+        let value = match x {
+            0 => tr("One-shot AF", "单次自动对焦 (One-shot AF)", "ワンショット AF"),
+            1 => tr("AI Servo AF", "人工智能伺服自动对焦 (AI Servo AF)", "AI サーボ AF"),
+            2 => tr("AI Focus AF", "人工智能自动对焦 (AI Focus AF)", "AI フォーカス AF"),
+            3 | 6 => tr("Manual Focus", "手动对焦", "マニュアルフォーカス"),
+            4 => tr("Single", "单次", "シングル"),
+            5 => tr("Continuous", "连续", "コンティニュアス"),
+            16 => tr("Pan Focus", "全景对焦", "パンフォーカス"),
+            256 => tr("One-shot AF (Live View)", "单次自动对焦（实时取景）", "ワンショット AF（ライブビュー）"),
+            257 => tr("AI Servo AF (Live View)", "人工智能伺服自动对焦（实时取景）", "AI サーボ AF（ライブビュー）"),
+            258 => tr("AI Focus AF (Live View)", "人工智能自动对焦（实时取景）", "AI フォーカス AF（ライブビュー）"),
+            512 => tr("Movie Snap Focus", "短片快照对焦", "ムービースナップフォーカス"),
+            519 => tr("Movie Servo AF", "短片伺服自动对焦", "動画サーボ AF"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "FocusMode", tr("Focus Mode", "对焦模式", "フォーカスモード"), value);
+    }
+    if let Some(x) = at(v, 9) {
+        let value = match x {
+            1 => "JPEG",
+            2 => "CRW+THM",
+            3 => "AVI+THM",
+            4 => "TIF",
+            5 => "TIF+JPEG",
+            6 => "CR2",
+            7 => "CR2+JPEG",
+            9 => "MOV",
+            10 => "MP4",
+            11 => "CRM",
+            12 => "CR3",
+            13 => "CR3+JPEG",
+            14 => "HIF",
+            15 => "CR3+HIF",
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "RecordMode", tr("Record Mode", "记录格式", "記録形式"), value);
+    }
+    if let Some(x) = at(v, 17) {
+        let value = match x {
+            0 => tr("Default", "默认", "デフォルト"),
+            1 => tr("Spot", "点测光", "スポット測光"),
+            2 => tr("Average", "平均测光", "平均測光"),
+            3 => tr("Evaluative", "评价测光", "評価測光"),
+            4 => tr("Partial", "局部测光", "部分測光"),
+            5 => tr("Center-weighted Average", "中央重点平均测光", "中央重点平均測光"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "MeteringMode", tr("Metering Mode", "测光模式", "測光モード"), value);
+    }
+    if let Some(x) = at(v, 20) {
+        let value = match x {
+            0 => tr("Scene Intelligent Auto", "场景智能自动", "シーンインテリジェントオート"),
+            1 => tr("Program AE", "程序自动曝光", "プログラム AE"),
+            2 => tr("Shutter Priority", "快门优先", "シャッター優先"),
+            3 => tr("Aperture Priority", "光圈优先", "絞り優先"),
+            4 => tr("Manual", "手动曝光", "マニュアル露出"),
+            5 => tr("Depth-of-field AE", "景深自动曝光", "被写界深度優先 AE"),
+            6 => "M-Dep",
+            7 => tr("Bulb", "B 门", "バルブ"),
+            8 => tr("Flexible-priority AE", "柔性优先自动曝光", "フレキシブル AE"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "CanonExposureMode", tr("Exposure Mode", "曝光模式", "露出モード"), value);
+    }
+    if let Some(x) = at(v, 22).filter(|&x| x != 0 && x != -1) {
+        note.push(ID, "LensType", tr("Lens Type ID", "镜头类型 ID", "レンズタイプ ID"), (x as u16).to_string());
+    }
+    if let Some(x) = at(v, 23).filter(|&x| x > 0) {
+        note.push(ID, "MaxFocalLength", tr("Max Focal Length", "最长焦距", "最長焦点距離"), format_mm(x as u16 as f64 / units));
+    }
+    if let Some(x) = at(v, 24).filter(|&x| x > 0) {
+        note.push(ID, "MinFocalLength", tr("Min Focal Length", "最短焦距", "最短焦点距離"), format_mm(x as u16 as f64 / units));
+    }
+    if let Some(x) = at(v, 26).filter(|&x| x > 0) {
+        note.push(ID, "MaxAperture", tr("Max Aperture", "最大光圈", "開放 F 値"), format_f_number(canon_aperture(x)));
+    }
+    if let Some(x) = at(v, 27).filter(|&x| x > 0) {
+        note.push(ID, "MinAperture", tr("Min Aperture", "最小光圈", "最小絞り"), format_f_number(canon_aperture(x)));
+    }
+    if let Some(x) = at(v, 34) {
+        let value = match x {
+            0 => tr("Off", "关闭", "オフ"),
+            1 => tr("On", "开启", "オン"),
+            2 => tr("Shoot Only", "仅拍摄时", "撮影時のみ"),
+            3 => tr("Panning", "追随", "流し撮り"),
+            4 => tr("Dynamic", "动态", "ダイナミック"),
+            256 => tr("Off (2)", "关闭 (2)", "オフ (2)"),
+            257 => tr("On (2)", "开启 (2)", "オン (2)"),
+            258 => tr("Shoot Only (2)", "仅拍摄时 (2)", "撮影時のみ (2)"),
+            259 => tr("Panning (2)", "追随 (2)", "流し撮り (2)"),
+            260 => tr("Dynamic (2)", "动态 (2)", "ダイナミック (2)"),
+            -1 => tr("n/a", "不适用", "該当なし"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "ImageStabilization", tr("Image Stabilization", "影像稳定器", "手ブレ補正"), value);
+    }
+}
+
+/// ShotInfo：下标 0 为字节数，之后每个元素是一项拍摄参数
+fn decode_shot_info(note: &mut MakerNote, v: &[i16]) {
+    const ID: u16 = 0x0004;
+    // ISO 以 2^(v/32) 编码，BaseISO 为 100 × 2^(v/32) / 32
+    if let Some(x) = at(v, 2).filter(|&x| x != 0) {
+        let iso = 100.0 * 2f64.powf(x as f64 / 32.0) / 32.0;
+        note.push(ID, "BaseISO", tr("Base ISO", "基础 ISO", "ベース ISO"), format!("{}", iso.round()));
+    }
+    if let Some(x) = at(v, 1).filter(|&x| x != 0) {
+        let auto = 100.0 * 2f64.powf(x as f64 / 32.0);
+        note.push(ID, "AutoISO", tr("Auto ISO", "自动 ISO 倍率", "ISO オート倍率"), format!("{}%", auto.round()));
+    }
+    if let Some(x) = at(v, 4).filter(|&x| x != 0) {
+        note.push(ID, "TargetAperture", tr("Target Aperture", "目标光圈", "目標絞り値"), format_f_number(canon_aperture(x)));
+    }
+    if let Some(x) = at(v, 5).filter(|&x| x != 0) {
+        note.push(ID, "TargetExposureTime", tr("Target Exposure Time", "目标曝光时间", "目標露出時間"), format_exposure_time(canon_exposure_time(x)));
+    }
+    if let Some(x) = at(v, 6) {
+        note.push(ID, "ExposureCompensation", tr("Exposure Compensation", "曝光补偿", "露出補正"), format!("{:+.1} EV", canon_ev(x)));
+    }
+    if let Some(x) = at(v, 9) {
+        note.push(ID, "SequenceNumber", tr("Sequence Number", "连拍序号", "連続撮影番号"), x.to_string());
+    }
+    // 机身温度以 128 为 0 °C
+    if let Some(x) = at(v, 12).filter(|&x| x != 0) {
+        note.push(ID, "CameraTemperature", tr("Camera Temperature", "机身温度", "カメラ温度"), format!("{} °C", x - 128));
+    }
+    if let Some(x) = at(v, 16) {
+        note.push(ID, "AutoExposureBracketing", tr("Auto Exposure Bracketing", "自动包围曝光", "オート露出ブラケティング"), match x { 0 => tr("Off", "关闭", "オフ"), -1 => tr("On", "开启", "オン"), _ => tr("On (shot in progress)", "开启（拍摄中）", "オン（撮影中）") });
+    }
+    // 对焦距离以厘米为单位，65535 表示无穷远
+    for (index, name, label) in [(19, "FocusDistanceUpper", tr("Focus Distance Upper", "对焦距离上限", "撮影距離上限")), (20, "FocusDistanceLower", tr("Focus Distance Lower", "对焦距离下限", "撮影距離下限"))] {
+        if let Some(x) = at(v, index).filter(|&x| x != 0) {
+            let value = if x as u16 == 0xffff { tr("Infinity", "无穷远", "無限遠").to_string() } else { format!("{:.2} m", x as u16 as f64 / 100.0) };
+            note.push(ID, name, label, value);
+        }
+    }
+    if let Some(x) = at(v, 21).filter(|&x| x != 0) {
+        note.push(ID, "FNumber", tr("Aperture", "光圈", "絞り値"), format_f_number(canon_aperture(x)));
+    }
+    if let Some(x) = at(v, 22).filter(|&x| x != 0) {
+        note.push(ID, "ExposureTime", tr("Exposure Time", "曝光时间", "露出時間"), format_exposure_time(canon_exposure_time(x)));
+    }
+    if let Some(x) = at(v, 27) {
+        let value = match x {
+            -1 => tr("n/a", "不适用", "該当なし"),
+            0 => tr("None", "不旋转", "回転なし"),
+            1 => tr("Rotate 90 CW", "顺时针 90 度", "時計回りに 90 度"),
+            2 => tr("Rotate 180", "180 度", "180 度"),
+            3 => tr("Rotate 270 CW", "逆时针 90 度", "反時計回りに 90 度"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "AutoRotate", tr("Auto Rotate", "自动旋转", "自動回転"), value);
+    }
+}
+
+/// FileInfo：下标 0 为字节数
+fn decode_file_info(note: &mut MakerNote, v: &[i16]) {
+    const ID: u16 = 0x0093;
+    if let Some(x) = at(v, 3) {
+        let value = match x {
+            0 => tr("Off", "关闭", "オフ"),
+            1 => tr("AEB", "包围曝光 (AEB)", "AEB"),
+            2 => tr("FEB", "闪光包围 (FEB)", "FEB"),
+            3 => tr("ISO", "ISO 包围", "ISO ブラケティング"),
+            4 => tr("White Balance Bracketing", "白平衡包围", "ホワイトバランスブラケティング"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "BracketMode", tr("Bracket Mode", "包围模式", "ブラケットモード"), value);
+        if x != 0 {
+            if let Some(value) = at(v, 4) {
+                note.push(ID, "BracketValue", tr("Bracket Value", "包围值", "ブラケット値"), value.to_string());
+            }
+            if let Some(shot) = at(v, 5) {
+                note.push(ID, "BracketShotNumber", tr("Bracket Shot Number", "包围序号", "ブラケット撮影番号"), shot.to_string());
+            }
+        }
+    }
+    if let Some(x) = at(v, 8) {
+        let value = match x {
+            0 => tr("Off", "关闭", "オフ"),
+            1 => tr("Auto", "自动", "オート"),
+            2 => tr("On", "开启", "オン"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "LongExposureNoiseReduction2", tr("Long Exposure Noise Reduction", "长时间曝光降噪", "長秒時露光のノイズ低減"), value);
+    }
+    if let Some(x) = at(v, 19) {
+        note.push(ID, "LiveViewShooting", tr("Live View Shooting", "实时取景拍摄", "ライブビュー撮影"), if x == 0 { tr("Off", "关闭", "オフ") } else { tr("On", "开启", "オン") });
+    }
+}
+
+/// AFInfo2：字节数、AF 区域模式、AF 点数量、有效 AF 点数量、图像尺寸、
+/// 各 AF 点的宽高和坐标，然后是合焦 AF 点的位图
+fn decode_af_info2(note: &mut MakerNote, v: &[u16]) {
+    const ID: u16 = 0x0026;
+    if let Some(&mode) = v.get(1) {
+        let value = match mode {
+            0 => tr("Off (Manual Focus)", "关闭（手动对焦）", "オフ（マニュアルフォーカス）"),
+            1 => tr("AF Point Expansion", "扩展自动对焦区域", "AF 領域拡大"),
+            2 => tr("Single-point AF", "单点自动对焦", "1 点 AF"),
+            4 => tr("Multi-point AF", "多点自动对焦", "多点 AF"),
+            5 => tr("Face Detect AF", "面部检测", "顔検出 AF"),
+            6 => tr("Face + Tracking", "面部 + 追踪", "顔 + 追尾"),
+            7 => tr("Zone AF", "区域自动对焦", "ゾーン AF"),
+            8 => tr("AF Point Expansion (4 point)", "扩展自动对焦区域（4 点）", "AF 領域拡大（4 点）"),
+            9 => tr("Spot AF", "定点自动对焦", "スポット 1 点 AF"),
+            10 => tr("AF Point Expansion (8 point)", "扩展自动对焦区域（8 点）", "AF 領域拡大（8 点）"),
+            11 => tr("Flexizone Multi (49 point)", "自由区多点（49 点）", "フレキシゾーンマルチ（49 点）"),
+            12 => tr("Flexizone Multi (9 point)", "自由区多点（9 点）", "フレキシゾーンマルチ（9 点）"),
+            13 => tr("Flexizone Single", "自由区单点", "フレキシゾーンシングル"),
+            14 => tr("Large Zone AF", "大区域自动对焦", "ラージゾーン AF"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "AFAreaMode", tr("AF Area Mode", "AF 区域模式", "AF エリアモード"), value);
+    }
+    let Some(&num_points) = v.get(2) else { return };
+    note.push(ID, "NumAFPoints", tr("Num AF Points", "AF 点数量", "AF 測距点数"), num_points.to_string());
+    if let Some(&valid) = v.get(3) {
+        note.push(ID, "ValidAFPoints", tr("Valid AF Points", "有效 AF 点数量", "有効 AF 測距点数"), valid.to_string());
+    }
+    // 8 个固定字段 + 宽、高、X、Y 四组数组，之后是合焦 AF 点位图（每 16 个点一个元素）
+    let n = num_points as usize;
+    let start = 8 + 4 * n;
+    let Some(bits) = v.get(start..start + n.div_ceil(16)) else { return };
+    let in_focus: Vec<String> = (0..n).filter(|i| bits[i / 16] & (1 << (i % 16)) != 0).map(|i| i.to_string()).collect();
+    let value = if in_focus.is_empty() { tr("None", "无", "なし").to_string() } else { in_focus.join(",") };
+    note.push(ID, "AFPointsInFocus", tr("AF Points In Focus", "合焦 AF 点", "合焦 AF 測距点"), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makernote::test_util::{build_ifd, u16s, u32s, value};
+    use little_exif::endian::Endian;
+
+    /// 按下标设置 i16 数组，其余为 0
+    fn array(len: usize, values: &[(usize, i16)]) -> Vec<u16> {
+        let mut out = vec![0u16; len];
+        out[0] = (len * 2) as u16;
+        for &(i, v) in values {
+            out[i] = v as u16;
+        }
+        out
+    }
+
+    /// JPEG / CR2 形式：不带头部的 IFD，偏移相对于外层 TIFF 头
+    fn decode(entries: &[(u16, u16, Vec<u8>)]) -> MakerNote {
+        let endian = Endian::Little;
+        let ctx = MakerNoteContext { make: Some("Canon"), model: Some("Canon EOS R5"), tiff_offset: Some(1000), endian: endian.clone() };
+        decode_canon_maker_note(&build_ifd(entries, 1000, &endian), &ctx).unwrap()
+    }
+
+    #[test]
+    fn ev_encoding() {
+        assert_eq!(canon_ev(0x40), 2.0);
+        // 0x0C / 0x14 表示 1/3 和 2/3 档
+        assert!((canon_ev(0x2c) - 4.0 / 3.0).abs() < 1e-9);
+        assert!((canon_ev(-0x14) + 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(canon_ev(0x10), 0.5);
+        assert_eq!(format_f_number(canon_aperture(0x60)), "f/2.8");
+        assert_eq!(format_exposure_time(canon_exposure_time(0xe0)), "1/128 s");
+    }
+
+    #[test]
+    fn camera_settings() {
+        let le = Endian::Little;
+        // FocalUnits = 10，焦距按 1/10 mm 记录
+        let settings = array(35, &[(1, 1), (3, 4), (7, 1), (20, 3), (22, 61182u16 as i16), (23, 700), (24, 240), (25, 10), (26, 0x60), (34, 1)]);
+        let note = decode(&[(0x0001, 3, u16s(&settings, &le)), (0x0002, 3, u16s(&[0, 500, 0, 0], &le))]);
+        assert_eq!((note.vendor, note.format.as_str()), ("Canon", "Canon"));
+        assert_eq!(value(&note, "MacroMode"), "Macro");
+        assert_eq!(value(&note, "Quality"), "RAW");
+        assert_eq!(value(&note, "FocusMode"), "AI Servo AF");
+        assert_eq!(value(&note, "CanonExposureMode"), "Aperture Priority");
+        assert_eq!(value(&note, "LensType"), "61182");
+        assert_eq!(value(&note, "MaxFocalLength"), "70 mm");
+        assert_eq!(value(&note, "MinFocalLength"), "24 mm");
+        assert_eq!(value(&note, "FocalLength"), "50 mm");
+        assert_eq!(value(&note, "MaxAperture"), "f/2.8");
+        assert_eq!(value(&note, "ImageStabilization"), "On");
+        // 值为 0 的自拍延时不输出
+        assert!(note.find("SelfTimer").is_none());
+
+        // 数组较短时只解码存在的下标
+        let note = decode(&[(0x0001, 3, u16s(&array(4, &[(3, 3)]), &le))]);
+        assert_eq!(value(&note, "Quality"), "Fine");
+        assert!(note.find("FocusMode").is_none());
+    }
+
+    #[test]
+    fn shot_info() {
+        let le = Endian::Little;
+        let info = array(30, &[(2, 160), (6, -0x14), (12, 153), (19, -1), (20, 150), (21, 0x60), (22, 0xe0), (27, 3)]);
+        let note = decode(&[(0x0004, 3, u16s(&info, &le))]);
+        assert_eq!(value(&note, "BaseISO"), "100");
+        assert_eq!(value(&note, "ExposureCompensation"), "-0.7 EV");
+        assert_eq!(value(&note, "CameraTemperature"), "25 °C");
+        assert_eq!(value(&note, "FocusDistanceUpper"), "Infinity");
+        assert_eq!(value(&note, "FocusDistanceLower"), "1.50 m");
+        assert_eq!(value(&note, "FNumber"), "f/2.8");
+        assert_eq!(value(&note, "ExposureTime"), "1/128 s");
+        assert_eq!(value(&note, "AutoRotate"), "Rotate 270 CW");
+        assert!(note.find("AutoISO").is_none());
+    }
+
+    #[test]
+    fn af_info2_bitmap() {
+        let le = Endian::Little;
+        // 19 个 AF 点：8 个固定字段 + 4 × 19 个尺寸坐标，之后是 2 个元素的位图
+        let mut af = vec![0u16; 8 + 4 * 19 + 2];
+        let size = (af.len() * 2) as u16;
+        af[..4].copy_from_slice(&[size, 2, 19, 19]);
+        af[84] = 1 << 3 | 1 << 15;
+        af[85] = 1 << 2;
+        let note = decode(&[(0x0026, 3, u16s(&af, &le))]);
+        assert_eq!(value(&note, "AFAreaMode"), "Single-point AF");
+        assert_eq!(value(&note, "NumAFPoints"), "19");
+        assert_eq!(value(&note, "AFPointsInFocus"), "3,15,18");
+
+        // 位图缺失时不输出合焦点
+        let note = decode(&[(0x0026, 3, u16s(&af[..85], &le))]);
+        assert_eq!(value(&note, "ValidAFPoints"), "19");
+        assert!(note.find("AFPointsInFocus").is_none());
+    }
+
+    #[test]
+    fn cr3_tiff_header() {
+        for (endian, magic) in [(Endian::Little, b"II\x2a\0"), (Endian::Big, b"MM\0\x2a")] {
+            // CMT3：完整的 TIFF 结构，偏移相对于它自己的 TIFF 头，与外层上下文无关
+            let mut data = magic.to_vec();
+            data.extend(u32s(&[8], &endian));
+            data.extend(build_ifd(
+                &[
+                    (0x0008, 4, u32s(&[1234567], &endian)),
+                    (0x0009, 2, b"Jane Doe\0".to_vec()),
+                    (0x0095, 2, b"RF24-70mm F2.8 L IS USM\0".to_vec()),
+                ],
+                8,
+                &endian,
+            ));
+            let other = match endian { Endian::Little => Endian::Big, Endian::Big => Endian::Little };
+            let ctx = MakerNoteContext { make: Some("Canon"), model: Some("Canon EOS R5"), tiff_offset: Some(5000), endian: other };
+            let note = decode_canon_maker_note(&data, &ctx).unwrap();
+            assert_eq!(value(&note, "FileNumber"), "123-4567");
+            assert_eq!(value(&note, "OwnerName"), "Jane Doe");
+            assert_eq!(value(&note, "LensModel"), "RF24-70mm F2.8 L IS USM");
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use little_exif::{endian::Endian, exif_tag::ExifTag, rational::uR64};
+
+use crate::{
+    makernote::{format_exposure_time, format_f_number, format_mm},
+    locale::{current_locale, tr, Locale},
+    tags::{find_tag, get_tag_raw_value},
+};
+
+/// 35mm 全画幅的对角线长度（毫米）
+const FULL_FRAME_DIAGONAL: f64 = 43.266615;
+
+/// 由原始 EXIF 标签推算出的复合标签
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeTag {
+    /// 英文名称，与 exiftool 的 Composite 标签一致
+    pub name: &'static str,
+    /// 当前语言下的名称
+    pub label: &'static str,
+    /// 格式化后的值
+    pub value: String,
+}
+
+/// 由原始 EXIF 标签计算复合标签
+///
+/// 包括 GPS 十进制坐标、曝光值与光值、快门与光圈（EXIF 中只有 APEX 值时由其换算）、
+/// 35mm 等效焦距与裁切系数、视角、超焦距与景深、像素数，以及带时区偏移的拍摄时间；
+/// 缺少所需标签的项不输出
+///
+/// # 参数
+/// - `tags`: EXIF 标签列表
+/// - `endian`: 字节序信息
+///
+/// # 返回值
+/// - 复合标签列表，按上述顺序排列
+pub fn compute_composite_tags(tags: &[ExifTag], endian: &Endian) -> Vec<CompositeTag> {
+    let mut out = Vec::new();
+    let mut push = |name, label, value: String| out.push(CompositeTag { name, label, value });
+
+    if let Some((lat, lon)) = gps_position(tags) {
+        push("GPSPosition", tr("GPS Position", "GPS 坐标", "GPS 位置"), format!("{:.6}, {:.6}", lat, lon));
+    }
+    if let Some(alt) = gps_altitude(tags) {
+        push("GPSAltitude", tr("GPS Altitude", "GPS 海拔", "GPS 高度"), format!("{:.1} {}", alt, meters()));
+    }
+
+    let exposure_time = exposure_time(tags);
+    let f_number = f_number(tags);
+    if let Some(t) = exposure_time {
+        push("ShutterSpeed", tr("Shutter Speed", "快门速度", "シャッタースピード"), format_exposure_time(t));
+    }
+    if let Some(n) = f_number {
+        push("Aperture", tr("Aperture", "光圈", "絞り"), format_f_number(n));
+    }
+    if let (Some(t), Some(n)) = (exposure_time, f_number) {
+        // EV = log2(N²/t)，光值再按 ISO 100 归一化
+        let ev = (n * n / t).log2();
+        push("ExposureValue", tr("Exposure Value", "曝光值", "露出値"), format!("{:.1} EV", ev));
+        if let Some(iso) = iso(tags) {
+            push("LightValue", tr("Light Value", "光值", "ライトバリュー"), format!("{:.1}", ev - (iso / 100.0).log2()));
+        }
+    }
+
+    let focal_length = rational(find_tag(tags, &ExifTag::FocalLength(vec![])));
+    let scale = scale_factor_35efl(tags, endian, focal_length);
+    if let Some(scale) = scale {
+        push("ScaleFactor35efl", tr("Scale Factor To 35 mm Equivalent", "裁切系数", "35mm 換算倍率"), format!("{:.2}", scale));
+    }
+    if let (Some(f), Some(scale)) = (focal_length, scale) {
+        let f35 = f * scale;
+        push(
+            "FocalLength35efl",
+            tr("Focal Length (35 mm equivalent)", "35mm 等效焦距", "焦点距離（35mm 換算）"),
+            format!("{}{}", format_mm(f), parenthesized(format!("{} {}", tr("35 mm equivalent:", "等效", "35mm 換算"), format_mm(f35)))),
+        );
+        // 按 36 mm 画幅宽度计算水平视角
+        let fov = 2.0 * (18.0 / f35).atan().to_degrees();
+        push("FOV", tr("Field Of View", "视角", "画角"), format!("{:.1}°", fov));
+    }
+    if let (Some(f), Some(n), Some(scale)) = (focal_length, f_number, scale) {
+        // 弥散圆直径取对角线的 1/1440
+        let coc = FULL_FRAME_DIAGONAL / scale / 1440.0;
+        push("CircleOfConfusion", tr("Circle Of Confusion", "弥散圆", "許容錯乱円"), format!("{:.3} mm", coc));
+        let hyperfocal = f * f / (n * coc) / 1000.0;
+        push("HyperfocalDistance", tr("Hyperfocal Distance", "超焦距", "過焦点距離"), format!("{:.2} {}", hyperfocal, meters()));
+        if let Some(s) = rational(find_tag(tags, &ExifTag::SubjectDistance(vec![]))).filter(|&s| s > 0.0) {
+            push("DOF", tr("Depth Of Field", "景深", "被写界深度"), depth_of_field(s, hyperfocal));
+        }
+    }
+
+    if let Some((w, h)) = image_size(tags, endian) {
+        let megapixels = (w * h) as f64 / 1_000_000.0;
+        push("Megapixels", tr("Megapixels", "像素数", "画素数"), format!("{:.1} {}{}", megapixels, tr("MP", "百万像素", "メガピクセル"), parenthesized(format!("{} × {}", w, h))));
+    }
+    if let Some(time) = capture_time(tags) {
+        push("SubSecDateTimeOriginal", tr("Date/Time Original (with offset)", "拍摄时间（含时区）", "撮影日時（時差付き）"), time);
+    }
+    out
+}
+
+/// 米的单位
+fn meters() -> &'static str {
+    tr("m", "米", "m")
+}
+
+/// 加上括号：英文为半角并以空格分隔，中日文为全角
+fn parenthesized(text: String) -> String {
+    match current_locale() {
+        Locale::En => format!(" ({})", text),
+        Locale::ZhCn | Locale::Ja => format!("（{}）", text),
+    }
+}
+
+/// 有理数标签的第一个值，分母为 0 时返回 None
+fn rational(tag: Option<&ExifTag>) -> Option<f64> {
+    let value = match tag? {
+        ExifTag::ExposureTime(v)
+        | ExifTag::FNumber(v)
+        | ExifTag::ApertureValue(v)
+        | ExifTag::FocalLength(v)
+        | ExifTag::SubjectDistance(v)
+        | ExifTag::FocalPlaneXResolution(v)
+        | ExifTag::FocalPlaneYResolution(v)
+        | ExifTag::GPSAltitude(v) => v.first()?,
+        ExifTag::ShutterSpeedValue(v) => {
+            let r = v.first()?;
+            return (r.denominator != 0).then(|| r.nominator as f64 / r.denominator as f64);
+        }
+        _ => return None,
+    };
+    (value.denominator != 0).then(|| value.nominator as f64 / value.denominator as f64)
+}
+
+/// 整数标签的第一个值，兼容按 SHORT 或 LONG 存储的情况
+fn integer(tags: &[ExifTag], tag: &ExifTag, endian: &Endian) -> Option<u64> {
+    get_tag_raw_value(find_tag(tags, tag)?, endian).as_array()?.first()?.as_u64()
+}
+
+/// 文本标签的值，去掉首尾空白
+fn text(tags: &[ExifTag], tag: &ExifTag) -> Option<String> {
+    match find_tag(tags, tag)? {
+        ExifTag::GPSLatitudeRef(s)
+        | ExifTag::GPSLongitudeRef(s)
+        | ExifTag::DateTimeOriginal(s)
+        | ExifTag::CreateDate(s)
+        | ExifTag::OffsetTimeOriginal(s)
+        | ExifTag::OffsetTimeDigitized(s)
+        | ExifTag::SubSecTimeOriginal(s)
+        | ExifTag::SubSecTimeDigitized(s) => {
+            let s = s.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            (!s.is_empty()).then(|| s.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// 度、分、秒三个有理数换算为十进制度数
+fn dms_to_degrees(v: &[uR64]) -> Option<f64> {
+    let part = |r: &uR64| (r.denominator != 0).then(|| r.nominator as f64 / r.denominator as f64);
+    match v {
+        [d, m, s, ..] => Some(part(d)? + part(m)? / 60.0 + part(s)? / 3600.0),
+        [d] => part(d),
+        _ => None,
+    }
+}
+
+/// 带符号的十进制纬度和经度，南纬和西经为负
+fn gps_position(tags: &[ExifTag]) -> Option<(f64, f64)> {
+    let ExifTag::GPSLatitude(lat) = find_tag(tags, &ExifTag::GPSLatitude(vec![]))? else { return None };
+    let ExifTag::GPSLongitude(lon) = find_tag(tags, &ExifTag::GPSLongitude(vec![]))? else { return None };
+    let mut lat = dms_to_degrees(lat)?;
+    let mut lon = dms_to_degrees(lon)?;
+    if text(tags, &ExifTag::GPSLatitudeRef(String::new())).is_some_and(|r| r.eq_ignore_ascii_case("S")) {
+        lat = -lat;
+    }
+    if text(tags, &ExifTag::GPSLongitudeRef(String::new())).is_some_and(|r| r.eq_ignore_ascii_case("W")) {
+        lon = -lon;
+    }
+    Some((lat, lon))
+}
+
+/// 带符号的海拔（米），GPSAltitudeRef 为 1 时在海平面以下
+fn gps_altitude(tags: &[ExifTag]) -> Option<f64> {
+    let alt = rational(find_tag(tags, &ExifTag::GPSAltitude(vec![])))?;
+    let below = matches!(find_tag(tags, &ExifTag::GPSAltitudeRef(vec![])), Some(ExifTag::GPSAltitudeRef(v)) if v.first() == Some(&1));
+    Some(if below { -alt } else { alt })
+}
+
+/// 曝光时间（秒）：优先取 ExposureTime，否则由 APEX 快门值换算，t = 2^-Tv
+fn exposure_time(tags: &[ExifTag]) -> Option<f64> {
+    rational(find_tag(tags, &ExifTag::ExposureTime(vec![])))
+        .filter(|&t| t > 0.0)
+        .or_else(|| rational(find_tag(tags, &ExifTag::ShutterSpeedValue(vec![]))).map(|tv| 2f64.powf(-tv)))
+}
+
+/// 光圈值：优先取 FNumber，否则由 APEX 光圈值换算，N = 2^(Av/2)
+fn f_number(tags: &[ExifTag]) -> Option<f64> {
+    rational(find_tag(tags, &ExifTag::FNumber(vec![])))
+        .filter(|&n| n > 0.0)
+        .or_else(|| rational(find_tag(tags, &ExifTag::ApertureValue(vec![]))).map(|av| 2f64.powf(av / 2.0)))
+}
+
+fn iso(tags: &[ExifTag]) -> Option<f64> {
+    match find_tag(tags, &ExifTag::ISO(vec![]))? {
+        ExifTag::ISO(v) => v.first().filter(|&&iso| iso > 0).map(|&iso| iso as f64),
+        _ => None,
+    }
+}
+
+/// 裁切系数：优先用 FocalLengthIn35mmFormat / FocalLength，
+/// 否则由焦平面分辨率和图像尺寸算出传感器对角线
+fn scale_factor_35efl(tags: &[ExifTag], endian: &Endian, focal_length: Option<f64>) -> Option<f64> {
+    let f35 = integer(tags, &ExifTag::FocalLengthIn35mmFormat(vec![]), endian).filter(|&v| v > 0);
+    if let (Some(f35), Some(f)) = (f35, focal_length.filter(|&f| f > 0.0)) {
+        return Some(f35 as f64 / f);
+    }
+
+    let x_res = rational(find_tag(tags, &ExifTag::FocalPlaneXResolution(vec![]))).filter(|&v| v > 0.0)?;
+    let y_res = rational(find_tag(tags, &ExifTag::FocalPlaneYResolution(vec![]))).filter(|&v| v > 0.0)?;
+    let unit_mm = match integer(tags, &ExifTag::FocalPlaneResolutionUnit(vec![]), endian).unwrap_or(2) {
+        3 => 10.0,
+        4 => 1.0,
+        _ => 25.4,
+    };
+    let (w, h) = image_size(tags, endian)?;
+    let diagonal = (w as f64 / x_res * unit_mm).hypot(h as f64 / y_res * unit_mm);
+    (diagonal > 0.0).then(|| FULL_FRAME_DIAGONAL / diagonal)
+}
+
+/// 图像宽高：优先取 Exif IFD 中的 ExifImageWidth / ExifImageHeight
+fn image_size(tags: &[ExifTag], endian: &Endian) -> Option<(u64, u64)> {
+    let exif = (
+        integer(tags, &ExifTag::ExifImageWidth(vec![]), endian),
+        integer(tags, &ExifTag::ExifImageHeight(vec![]), endian),
+    );
+    let (w, h) = match exif {
+        (Some(w), Some(h)) => (w, h),
+        _ => (
+            integer(tags, &ExifTag::ImageWidth(vec![]), endian)?,
+            integer(tags, &ExifTag::ImageHeight(vec![]), endian)?,
+        ),
+    };
+    (w > 0 && h > 0).then_some((w, h))
+}
+
+/// 按对焦距离 s 和超焦距 H（均为米）计算景深：近点 sH/(H+s)，远点 sH/(H-s)，s ≥ H 时远点为无穷远
+fn depth_of_field(s: f64, hyperfocal: f64) -> String {
+    let (m, inf) = (meters(), tr("inf", "无穷远", "無限遠"));
+    let near = s * hyperfocal / (hyperfocal + s);
+    if s >= hyperfocal {
+        return format!("{}{}", tr("infinite", "无穷大", "無限大"), parenthesized(format!("{:.2} {} - {}", near, m, inf)));
+    }
+    let far = s * hyperfocal / (hyperfocal - s);
+    format!("{:.2} {}{}", far - near, m, parenthesized(format!("{:.2} - {:.2} {}", near, far, m)))
+}
+
+/// 拍摄时间，"YYYY:MM:DD HH:MM:SS" 加上亚秒和时区偏移，规范为 ISO 8601 格式
+///
+/// 没有 DateTimeOriginal 时使用 CreateDate（EXIF 的 DateTimeDigitized）
+fn capture_time(tags: &[ExifTag]) -> Option<String> {
+    let (datetime, subsec, offset) = match text(tags, &ExifTag::DateTimeOriginal(String::new())) {
+        Some(dt) => (
+            dt,
+            text(tags, &ExifTag::SubSecTimeOriginal(String::new())),
+            text(tags, &ExifTag::OffsetTimeOriginal(String::new())),
+        ),
+        None => (
+            text(tags, &ExifTag::CreateDate(String::new()))?,
+            text(tags, &ExifTag::SubSecTimeDigitized(String::new())),
+            text(tags, &ExifTag::OffsetTimeDigitized(String::new())),
+        ),
+    };
+    let (date, time) = datetime.split_once(' ')?;
+    let date_parts: Vec<&str> = date.split(':').collect();
+    let time_parts: Vec<&str> = time.split(':').collect();
+    let valid = |parts: &[&str], first_len: usize| {
+        parts.len() == 3
+            && parts.iter().all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+            && parts[0].len() == first_len
+    };
+    // 未知日期常写成全是空格或 0000:00:00
+    if !valid(&date_parts, 4) || !valid(&time_parts, 2) || date_parts[0] == "0000" {
+        return None;
+    }
+
+    let mut out = format!("{}-{}-{}T{}", date_parts[0], date_parts[1], date_parts[2], time);
+    if let Some(subsec) = subsec.filter(|s| s.bytes().all(|b| b.is_ascii_digit())) {
+        out.push('.');
+        out.push_str(&subsec);
+    }
+    if let Some(offset) = offset.filter(|o| is_utc_offset(o)) {
+        out.push_str(&offset);
+    }
+    Some(out)
+}
+
+/// 是否为 "+HH:MM" / "-HH:MM" 形式的时区偏移
+fn is_utc_offset(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 6
+        && matches!(b[0], b'+' | b'-')
+        && b[1..3].iter().all(u8::is_ascii_digit)
+        && b[3] == b':'
+        && b[4..6].iter().all(u8::is_ascii_digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use little_exif::rational::iR64;
+
+    fn r(nominator: u32, denominator: u32) -> uR64 {
+        uR64 { nominator, denominator }
+    }
+
+    fn value<'a>(tags: &'a [CompositeTag], name: &str) -> Option<&'a str> {
+        tags.iter().find(|t| t.name == name).map(|t| t.value.as_str())
+    }
+
+    fn compute(tags: &[ExifTag]) -> Vec<CompositeTag> {
+        compute_composite_tags(tags, &Endian::Little)
+    }
+
+    #[test]
+    fn exposure_and_light_value() {
+        let tags = [ExifTag::ExposureTime(vec![r(1, 128)]), ExifTag::FNumber(vec![r(8, 1)]), ExifTag::ISO(vec![400])];
+        let out = compute(&tags);
+        assert_eq!(value(&out, "ShutterSpeed"), Some("1/128 s"));
+        assert_eq!(value(&out, "Aperture"), Some("f/8"));
+        // log2(8² × 128) = 13，ISO 400 比 ISO 100 高 2 档
+        assert_eq!(value(&out, "ExposureValue"), Some("13.0 EV"));
+        assert_eq!(value(&out, "LightValue"), Some("11.0"));
+    }
+
+    #[test]
+    fn exposure_from_apex_values() {
+        let tags = [
+            ExifTag::ShutterSpeedValue(vec![iR64 { nominator: 7, denominator: 1 }]),
+            ExifTag::ApertureValue(vec![r(6, 1)]),
+        ];
+        let out = compute(&tags);
+        assert_eq!(value(&out, "ShutterSpeed"), Some("1/128 s"));
+        assert_eq!(value(&out, "Aperture"), Some("f/8"));
+        assert_eq!(value(&out, "ExposureValue"), Some("13.0 EV"));
+        // 没有 ISO 时不计算光值
+        assert_eq!(value(&out, "LightValue"), None);
+    }
+
+    #[test]
+    fn hyperfocal_dof_and_fov() {
+        let mut tags = vec![
+            ExifTag::FocalLength(vec![r(50, 1)]),
+            ExifTag::FocalLengthIn35mmFormat(vec![50]),
+            ExifTag::FNumber(vec![r(8, 1)]),
+            ExifTag::SubjectDistance(vec![r(5, 1)]),
+        ];
+        let out = compute(&tags);
+        assert_eq!(value(&out, "ScaleFactor35efl"), Some("1.00"));
+        assert_eq!(value(&out, "FocalLength35efl"), Some("50 mm (35 mm equivalent: 50 mm)"));
+        assert_eq!(value(&out, "FOV"), Some("39.6°"));
+        assert_eq!(value(&out, "CircleOfConfusion"), Some("0.030 mm"));
+        // H = f² / (N × c) = 2500 / (8 × 0.030046) mm
+        assert_eq!(value(&out, "HyperfocalDistance"), Some("10.40 m"));
+        assert_eq!(value(&out, "DOF"), Some("6.25 m (3.38 - 9.63 m)"));
+
+        // 对焦距离超过超焦距时远点为无穷远
+        tags[3] = ExifTag::SubjectDistance(vec![r(20, 1)]);
+        assert_eq!(value(&compute(&tags), "DOF"), Some("infinite (6.84 m - inf)"));
+    }
+
+    #[test]
+    fn scale_factor_from_focal_plane_resolution() {
+        // 36 × 24 mm 传感器：6000 像素 / 36 mm，分辨率单位为毫米
+        let tags = [
+            ExifTag::FocalLength(vec![r(50, 1)]),
+            ExifTag::ExifImageWidth(vec![6000]),
+            ExifTag::ExifImageHeight(vec![4000]),
+            ExifTag::FocalPlaneXResolution(vec![r(6000, 36)]),
+            ExifTag::FocalPlaneYResolution(vec![r(4000, 24)]),
+            ExifTag::FocalPlaneResolutionUnit(vec![4]),
+        ];
+        let out = compute(&tags);
+        assert_eq!(value(&out, "ScaleFactor35efl"), Some("1.00"));
+        assert_eq!(value(&out, "Megapixels"), Some("24.0 MP (6000 × 4000)"));
+    }
+
+    #[test]
+    fn gps_signs() {
+        let dms = vec![r(33, 1), r(30, 1), r(0, 1)];
+        let mut tags = vec![
+            ExifTag::GPSLatitudeRef("S".to_string()),
+            ExifTag::GPSLatitude(dms.clone()),
+            ExifTag::GPSLongitudeRef("W".to_string()),
+            ExifTag::GPSLongitude(dms.clone()),
+            ExifTag::GPSAltitudeRef(vec![1]),
+            ExifTag::GPSAltitude(vec![r(125, 10)]),
+        ];
+        let out = compute(&tags);
+        assert_eq!(value(&out, "GPSPosition"), Some("-33.500000, -33.500000"));
+        assert_eq!(value(&out, "GPSAltitude"), Some("-12.5 m"));
+
+        tags[0] = ExifTag::GPSLatitudeRef("N".to_string());
+        tags[2] = ExifTag::GPSLongitudeRef("E".to_string());
+        tags[4] = ExifTag::GPSAltitudeRef(vec![0]);
+        let out = compute(&tags);
+        assert_eq!(value(&out, "GPSPosition"), Some("33.500000, 33.500000"));
+        assert_eq!(value(&out, "GPSAltitude"), Some("12.5 m"));
+    }
+
+    #[test]
+    fn capture_time_with_subsec_and_offset() {
+        let tags = [
+            ExifTag::DateTimeOriginal("2024:07:14 18:05:09".to_string()),
+            ExifTag::SubSecTimeOriginal("42".to_string()),
+            ExifTag::OffsetTimeOriginal("-07:00".to_string()),
+        ];
+        assert_eq!(capture_time(&tags).as_deref(), Some("2024-07-14T18:05:09.42-07:00"));
+
+        // 没有 DateTimeOriginal 时使用 CreateDate 及其对应的亚秒和时区
+        let tags = [
+            ExifTag::CreateDate("2024:07:14 18:05:09".to_string()),
+            ExifTag::SubSecTimeDigitized("5".to_string()),
+            ExifTag::OffsetTimeDigitized("+09:00".to_string()),
+        ];
+        assert_eq!(capture_time(&tags).as_deref(), Some("2024-07-14T18:05:09.5+09:00"));
+    }
+
+    #[test]
+    fn capture_time_rejects_invalid_values() {
+        let time = |dt: &str| capture_time(&[ExifTag::DateTimeOriginal(dt.to_string())]);
+        assert_eq!(time("0000:00:00 00:00:00"), None);
+        assert_eq!(time("    :  :     :  :  "), None);
+        assert_eq!(time("2024-07-14 18:05:09"), None);
+        assert_eq!(time("2024:07:14"), None);
+        assert_eq!(time("24:07:14 18:05:09"), None);
+        // 非法的亚秒和时区被忽略
+        let tags = [
+            ExifTag::DateTimeOriginal("2024:07:14 18:05:09".to_string()),
+            ExifTag::SubSecTimeOriginal("4a".to_string()),
+            ExifTag::OffsetTimeOriginal("+0900".to_string()),
+        ];
+        assert_eq!(capture_time(&tags).as_deref(), Some("2024-07-14T18:05:09"));
+    }
+}
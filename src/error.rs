@@ -0,0 +1,101 @@
+use std::fmt;
+
+use crate::locale::tr;
+
+/// 读取元数据时可能出现的错误
+#[derive(Debug)]
+pub enum Error {
+    /// 文件读写错误
+    Io(std::io::Error),
+    /// 不支持的图片格式（扩展名或文件签名不符）
+    UnsupportedFormat(String),
+    /// 数据块在文件结尾前被截断
+    TruncatedChunk { chunk: String, offset: usize },
+    /// 数据块 CRC 校验失败
+    BadCrc { chunk: String, offset: usize },
+    /// zlib 等压缩数据解压失败
+    Decompression(String),
+    /// EXIF（TIFF 结构）数据损坏
+    MalformedTiff(String),
+    /// XMP 数据不是合法的 XML
+    MalformedXmp(String),
+    /// 文件结构不完整（如 IHDR 不在开头、IEND 之后还有数据）
+    Integrity(String),
+}
+
+impl Error {
+    /// 获取错误对应的进程退出码
+    ///
+    /// 每种错误使用不同的非零退出码，1 保留给命令行用法错误
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io(_) => 2,
+            Error::UnsupportedFormat(_) => 3,
+            Error::TruncatedChunk { .. } => 4,
+            Error::BadCrc { .. } => 5,
+            Error::Decompression(_) => 6,
+            Error::MalformedTiff(_) => 7,
+            Error::MalformedXmp(_) => 8,
+            Error::Integrity(_) => 9,
+        }
+    }
+
+    /// 文件签名与扩展名表示的格式不符
+    pub(crate) fn invalid_signature(format: &str) -> Error {
+        Error::UnsupportedFormat(format!(
+            "{} ({})",
+            format,
+            tr("invalid file signature", "文件签名无效", "ファイルシグネチャが不正")
+        ))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}: {}", tr("failed to read file", "文件读取失败", "ファイルの読み込みに失敗しました"), e),
+            Error::UnsupportedFormat(ext) => {
+                write!(f, "{}: {}", tr("unsupported image format", "不支持的图片格式", "未対応の画像形式"), ext)
+            }
+            Error::TruncatedChunk { chunk, offset } => write!(
+                f,
+                "{} {} {} ({} {})",
+                tr("chunk", "数据块", "チャンク"),
+                chunk,
+                tr("is truncated", "被截断", "が途切れています"),
+                tr("offset", "偏移", "オフセット"),
+                offset
+            ),
+            Error::BadCrc { chunk, offset } => write!(
+                f,
+                "{} {} ({} {}) {}",
+                tr("chunk", "数据块", "チャンク"),
+                chunk,
+                tr("offset", "偏移", "オフセット"),
+                offset,
+                tr("failed CRC check", "CRC 校验失败", "の CRC 検査に失敗しました")
+            ),
+            Error::Decompression(msg) => write!(f, "{}: {}", tr("decompression failed", "解压缩失败", "展開に失敗しました"), msg),
+            Error::MalformedTiff(msg) => write!(f, "{}: {}", tr("malformed EXIF data", "EXIF 数据损坏", "EXIF データが破損しています"), msg),
+            Error::MalformedXmp(msg) => write!(f, "{}: {}", tr("malformed XMP data", "XMP 数据损坏", "XMP データが破損しています"), msg),
+            Error::Integrity(msg) => {
+                write!(f, "{}: {}", tr("incomplete file structure", "文件结构不完整", "ファイル構造が不完全です"), msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
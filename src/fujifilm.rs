@@ -0,0 +1,329 @@
+use little_exif::endian::Endian;
+
+use crate::{
+    locale::tr,
+    makernote::{read_ifd, u32_at, unknown_value, IfdEntry, MakerNote, MakerNoteContext},
+};
+
+/// 解码 Fujifilm MakerNote
+///
+/// 头部为 "FUJIFILM" + 4 字节 IFD 偏移；不论外层字节序如何都是小端，偏移相对于 MakerNote 开头
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `_ctx`: 未使用，Fujifilm MakerNote 是自包含的
+///
+/// # 返回值
+/// - 解码出的标签；缺少头部或 IFD 损坏时返回 None
+pub fn decode_fujifilm_maker_note(data: &[u8], _ctx: &MakerNoteContext) -> Option<MakerNote> {
+    if !data.starts_with(b"FUJIFILM") {
+        return None;
+    }
+    let endian = Endian::Little;
+    let first = u32_at(data, 8, &endian)? as usize;
+    let entries = read_ifd(data, first, &endian, 0)?;
+
+    let mut note = MakerNote::new("Fujifilm", "Fujifilm");
+    for e in &entries {
+        decode_entry(&mut note, e);
+    }
+    Some(note)
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry) {
+    let id = e.tag;
+    let Some(v) = e.first_int() else {
+        match id {
+            0x0010 => note.push(id, "InternalSerialNumber", tr("Internal Serial Number", "内部序列号", "内部シリアル番号"), e.text()),
+            0x1000 => note.push(id, "Quality", tr("Quality", "画质", "画質"), e.text()),
+            _ => {}
+        }
+        return;
+    };
+    match id {
+        0x0000 => note.push(id, "Version", tr("MakerNote Version", "MakerNote 版本", "MakerNote バージョン"), e.text()),
+        0x1001 => note.push(id, "Sharpness", tr("Sharpness", "锐度", "シャープネス"), sharpness_name(v)),
+        0x1002 => note.push(id, "WhiteBalance", tr("White Balance", "白平衡", "ホワイトバランス"), white_balance_name(v)),
+        0x1003 => match monochrome_name(v) {
+            // 黑白类胶片模拟记录在饱和度中
+            Some(film) => note.push(id, "FilmMode", tr("Film Mode", "胶片模拟", "フィルムシミュレーション"), film),
+            None => note.push(id, "Saturation", tr("Saturation", "饱和度", "彩度"), saturation_name(v)),
+        },
+        0x100a => {
+            let values = e.u32s();
+            if let [red, blue, ..] = values[..] {
+                note.push(
+                    id,
+                    "WhiteBalanceFineTune",
+                    tr("White Balance Fine Tune", "白平衡微调", "ホワイトバランス微調整"),
+                    format!("{} {:+}{}{} {:+}", tr("Red", "红", "赤"), red as i32 / 20, tr(", ", "，", "、"), tr("Blue", "蓝", "青"), blue as i32 / 20),
+                );
+            }
+        }
+        0x1010 => {
+            let value = match v {
+                0 => tr("Auto", "自动", "オート"),
+                1 => tr("On", "开启", "オン"),
+                2 => tr("Off", "关闭", "オフ"),
+                3 => tr("Red-eye Reduction", "防红眼", "赤目軽減"),
+                4 => tr("External", "外置", "外部"),
+                16 => tr("Commander", "指令模式", "コマンダー"),
+                0x8000 => tr("Not Attached", "未使用", "未使用"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "FujiFlashMode", tr("Flash Mode", "闪光模式", "フラッシュモード"), value);
+        }
+        0x1021 => note.push(id, "FocusMode", tr("Focus Mode", "对焦模式", "フォーカスモード"), if v == 0 { tr("Auto", "自动", "オート") } else { tr("Manual", "手动", "マニュアル") }),
+        0x1031 => {
+            let value = match v {
+                0x0 => tr("Auto", "自动", "オート"),
+                0x1 => tr("Portrait", "人像", "ポートレート"),
+                0x2 => tr("Landscape", "风景", "風景"),
+                0x3 => tr("Macro", "微距", "マクロ"),
+                0x4 => tr("Sports", "运动", "スポーツ"),
+                0x5 => tr("Night Scene", "夜景", "夜景"),
+                0x6 => tr("Program AE", "程序自动曝光", "プログラム AE"),
+                0x100 => tr("Aperture Priority", "光圈优先", "絞り優先"),
+                0x200 => tr("Shutter Priority", "快门优先", "シャッター優先"),
+                0x300 => tr("Manual", "手动", "マニュアル"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "PictureMode", tr("Shooting Mode", "拍摄模式", "撮影モード"), value);
+        }
+        0x1047 | 0x1048 => {
+            let value = match v {
+                0 => tr("Off", "关闭", "オフ"),
+                32 => tr("Weak", "弱", "弱"),
+                64 => tr("Strong", "强", "強"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            if id == 0x1047 {
+                note.push(id, "GrainEffectRoughness", tr("Grain Effect Roughness", "颗粒效果", "グレイン・エフェクト"), value);
+            } else {
+                note.push(id, "ColorChromeEffect", tr("Color Chrome Effect", "彩色效果", "カラークロームエフェクト"), value);
+            }
+        }
+        0x104c => {
+            let value = match v {
+                0 => tr("Off", "关闭", "オフ"),
+                16 => tr("Small", "小", "小"),
+                32 => tr("Large", "大", "大"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "GrainEffectSize", tr("Grain Effect Size", "颗粒大小", "グレイン・エフェクトの粒度"), value);
+        }
+        0x104e => {
+            let value = match v {
+                0 => tr("Off", "关闭", "オフ"),
+                32 => tr("Weak", "弱", "弱"),
+                64 => tr("Strong", "强", "強"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "ColorChromeFXBlue", tr("Color Chrome FX Blue", "彩色效果（蓝色）", "カラークロームブルー"), value);
+        }
+        0x1050 => {
+            let value = match v {
+                0 => tr("Mechanical", "机械快门", "メカニカルシャッター"),
+                1 => tr("Electronic", "电子快门", "電子シャッター"),
+                2 => tr("Electronic (long shutter speed)", "电子快门（长曝光）", "電子シャッター（長秒）"),
+                3 => tr("Electronic Front Curtain", "电子前帘快门", "電子先幕シャッター"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "ShutterType", tr("Shutter Type", "快门类型", "シャッター方式"), value);
+        }
+        0x1401 => {
+            let value = match v {
+                1 => tr("Standard", "标准", "標準"),
+                3 => tr("Wide", "宽", "ワイド"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "DynamicRange", tr("Dynamic Range", "动态范围", "ダイナミックレンジ"), value);
+        }
+        0x1402 => note.push(id, "FilmMode", tr("Film Mode", "胶片模拟", "フィルムシミュレーション"), film_mode_name(v)),
+        0x1403 => {
+            let value = match v {
+                0x0 => tr("Auto", "自动", "オート"),
+                0x1 => tr("Manual", "手动", "マニュアル"),
+                0x100 => tr("Standard (100%)", "标准 (100%)", "標準 (100%)"),
+                0x200 => tr("Wide 1 (230%)", "宽 1 (230%)", "ワイド 1 (230%)"),
+                0x201 => tr("Wide 2 (400%)", "宽 2 (400%)", "ワイド 2 (400%)"),
+                0x8000 => tr("Film Mode", "胶片模拟", "フィルムシミュレーション"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(id, "DynamicRangeSetting", tr("Dynamic Range Setting", "动态范围设定", "ダイナミックレンジ設定"), value);
+        }
+        0x1404 => note.push(id, "DevelopmentDynamicRange", tr("Development Dynamic Range", "显影动态范围", "現像ダイナミックレンジ"), format!("{}%", v)),
+        0x1438 => note.push(id, "ImageCount", tr("Image Count", "拍摄张数", "撮影枚数"), (v & 0x7fff).to_string()),
+        _ => {}
+    }
+}
+
+fn sharpness_name(v: i64) -> String {
+    match v {
+        0x0 => tr("-4 (softest)", "-4（最柔和）", "-4（最弱）"),
+        0x1 => tr("-3 (very soft)", "-3（很柔和）", "-3（かなり弱）"),
+        0x2 => tr("-2 (soft)", "-2（柔和）", "-2（弱）"),
+        0x3 => tr("0 (normal)", "0（标准）", "0（標準）"),
+        0x4 => tr("+2 (hard)", "+2（强）", "+2（強）"),
+        0x5 => tr("+3 (very hard)", "+3（很强）", "+3（かなり強）"),
+        0x6 => tr("+4 (hardest)", "+4（最强）", "+4（最強）"),
+        0x82 => tr("-1 (medium soft)", "-1（中等偏柔和）", "-1（やや弱）"),
+        0x84 => tr("+1 (medium hard)", "+1（中等偏强）", "+1（やや強）"),
+        0x8000 => tr("Film Mode", "胶片模拟", "フィルムシミュレーション"),
+        0xffff => tr("n/a", "不适用", "該当なし"),
+        _ => return unknown_value(format_args!("0x{:X}", v)),
+    }
+    .to_string()
+}
+
+fn saturation_name(v: i64) -> String {
+    match v {
+        0x0 => tr("0 (normal)", "0（标准）", "0（標準）"),
+        0x80 => tr("+1 (medium high)", "+1（中等偏高）", "+1（やや高）"),
+        0x100 => tr("+2 (high)", "+2（高）", "+2（高）"),
+        0x180 => tr("-1 (medium low)", "-1（中等偏低）", "-1（やや低）"),
+        0x200 => tr("Low", "低", "低"),
+        0x400 => tr("-2 (low)", "-2（低）", "-2（低）"),
+        0x4c0 => tr("+3 (very high)", "+3（很高）", "+3（かなり高）"),
+        0x4e0 => tr("+4 (highest)", "+4（最高）", "+4（最高）"),
+        0x8000 => tr("Film Mode", "胶片模拟", "フィルムシミュレーション"),
+        _ => return unknown_value(format_args!("0x{:X}", v)),
+    }
+    .to_string()
+}
+
+fn white_balance_name(v: i64) -> String {
+    match v {
+        0x0 => tr("Auto", "自动", "オート"),
+        0x1 => tr("Auto (white priority)", "自动（白色优先）", "オート（ホワイト優先）"),
+        0x2 => tr("Auto (ambiance priority)", "自动（环境优先）", "オート（雰囲気優先）"),
+        0x100 => tr("Daylight", "日光", "晴天"),
+        0x200 => tr("Cloudy", "阴天", "曇天"),
+        0x300 => tr("Daylight Fluorescent", "日光色荧光灯", "昼光色蛍光灯"),
+        0x301 => tr("Day White Fluorescent", "日光白荧光灯", "昼白色蛍光灯"),
+        0x302 => tr("White Fluorescent", "白色荧光灯", "白色蛍光灯"),
+        0x303 => tr("Warm White Fluorescent", "暖白色荧光灯", "温白色蛍光灯"),
+        0x304 => tr("Living Room Warm White Fluorescent", "生活暖白色荧光灯", "電球色蛍光灯"),
+        0x400 => tr("Incandescent", "白炽灯", "電球"),
+        0x500 => tr("Flash", "闪光灯", "フラッシュ"),
+        0x600 => tr("Underwater", "水下", "水中"),
+        0xf00 => tr("Custom", "自定义", "カスタム"),
+        0xff0 => tr("Color Temperature", "色温", "色温度"),
+        _ => return unknown_value(format_args!("0x{:X}", v)),
+    }
+    .to_string()
+}
+
+/// 彩色胶片模拟（FilmMode 标签）
+fn film_mode_name(v: i64) -> String {
+    match v {
+        0x000 => tr("F0/Standard (PROVIA)", "F0 / 标准 (PROVIA)", "F0 / スタンダード (PROVIA)"),
+        0x100 => tr("F1/Studio Portrait", "F1 / 影棚人像", "F1 / スタジオポートレート"),
+        0x110 => tr("F1a/Studio Portrait Enhanced Saturation", "F1a / 影棚人像（增强饱和度）", "F1a / スタジオポートレート（彩度強調）"),
+        0x120 => tr("F1b/Studio Portrait Smooth Skin Tone (ASTIA)", "F1b / 影棚人像（柔和肤色）", "F1b / スタジオポートレート（滑らかな肌色）"),
+        0x130 => tr("F1c/Studio Portrait Increased Sharpness", "F1c / 影棚人像（提高锐度）", "F1c / スタジオポートレート（シャープネス強調）"),
+        0x200 => tr("F2/Fujichrome (Velvia)", "F2 / 正片 (Velvia)", "F2 / フジクローム (Velvia)"),
+        0x300 => tr("F3/Studio Portrait Ex", "F3 / 影棚人像 Ex", "F3 / スタジオポートレート Ex"),
+        0x400 => "F4 / Velvia",
+        0x500 => "PRO Neg. Std",
+        0x501 => "PRO Neg. Hi",
+        0x600 => "Classic Chrome",
+        0x700 => "ETERNA",
+        0x800 => "Classic Negative",
+        0x900 => tr("Bleach Bypass", "ETERNA 漂白效果", "ETERNA ブリーチバイパス"),
+        0xa00 => "Nostalgic Neg.",
+        0xb00 => "REALA ACE",
+        _ => return unknown_value(format_args!("0x{:X}", v)),
+    }
+    .to_string()
+}
+
+/// 记录在饱和度标签中的黑白胶片模拟
+fn monochrome_name(v: i64) -> Option<&'static str> {
+    Some(match v {
+        0x300 => tr("Monochrome", "黑白", "モノクロ"),
+        0x301 => tr("Monochrome + R Filter", "黑白 + 红色滤镜", "モノクロ + R フィルター"),
+        0x302 => tr("Monochrome + Ye Filter", "黑白 + 黄色滤镜", "モノクロ + Ye フィルター"),
+        0x303 => tr("Monochrome + G Filter", "黑白 + 绿色滤镜", "モノクロ + G フィルター"),
+        0x310 => tr("Sepia", "棕褐色", "セピア"),
+        0x500 => "ACROS",
+        0x501 => tr("ACROS + R Filter", "ACROS + 红色滤镜", "ACROS + R フィルター"),
+        0x502 => tr("ACROS + Ye Filter", "ACROS + 黄色滤镜", "ACROS + Ye フィルター"),
+        0x503 => tr("ACROS + G Filter", "ACROS + 绿色滤镜", "ACROS + G フィルター"),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makernote::test_util::{build_ifd, u16s, u32s, value};
+
+    /// "FUJIFILM" + IFD 偏移 12，IFD 中的偏移相对于 MakerNote 开头
+    fn maker_note(entries: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let endian = Endian::Little;
+        let mut data = b"FUJIFILM".to_vec();
+        data.extend(u32s(&[12], &endian));
+        data.extend(build_ifd(entries, 12, &endian));
+        data
+    }
+
+    fn decode(entries: &[(u16, u16, Vec<u8>)]) -> MakerNote {
+        // 外层 TIFF 为大端时 MakerNote 仍是小端
+        let ctx = MakerNoteContext { make: Some("FUJIFILM"), model: Some("X-T5"), tiff_offset: Some(1000), endian: Endian::Big };
+        decode_fujifilm_maker_note(&maker_note(entries), &ctx).unwrap()
+    }
+
+    #[test]
+    fn decodes_settings() {
+        let le = Endian::Little;
+        let note = decode(&[
+            (0x0000, 7, b"0130".to_vec()),
+            (0x0010, 2, b"FPX 20240514 A1B2C3\0".to_vec()),
+            (0x1001, 3, u16s(&[0x84], &le)),
+            (0x1002, 3, u16s(&[0x302], &le)),
+            (0x1003, 3, u16s(&[0x100], &le)),
+            (0x100a, 9, u32s(&[40, -60i32 as u32], &le)),
+            (0x1402, 3, u16s(&[0x600], &le)),
+            (0x1404, 3, u16s(&[200], &le)),
+            (0x1438, 4, u32s(&[0x8005], &le)),
+        ]);
+        assert_eq!((note.vendor, note.format.as_str()), ("Fujifilm", "Fujifilm"));
+        assert_eq!(value(&note, "Version"), "0130");
+        assert_eq!(value(&note, "InternalSerialNumber"), "FPX 20240514 A1B2C3");
+        assert_eq!(value(&note, "Sharpness"), "+1 (medium hard)");
+        assert_eq!(value(&note, "WhiteBalance"), "White Fluorescent");
+        assert_eq!(value(&note, "Saturation"), "+2 (high)");
+        // 微调值以 20 为一档
+        assert_eq!(value(&note, "WhiteBalanceFineTune"), "Red +2, Blue -3");
+        assert_eq!(value(&note, "FilmMode"), "Classic Chrome");
+        assert_eq!(value(&note, "DevelopmentDynamicRange"), "200%");
+        // 最高位不属于张数
+        assert_eq!(value(&note, "ImageCount"), "5");
+    }
+
+    #[test]
+    fn monochrome_film_mode_from_saturation() {
+        let note = decode(&[(0x1003, 3, u16s(&[0x501], &Endian::Little))]);
+        assert_eq!(value(&note, "FilmMode"), "ACROS + R Filter");
+        assert!(note.find("Saturation").is_none());
+
+        let note = decode(&[(0x1001, 3, u16s(&[0x7], &Endian::Little)), (0x1002, 3, u16s(&[0x123], &Endian::Little))]);
+        assert_eq!(value(&note, "Sharpness"), "Unknown (0x7)");
+        assert_eq!(value(&note, "WhiteBalance"), "Unknown (0x123)");
+    }
+
+    #[test]
+    fn rejects_missing_header_or_bad_ifd() {
+        let ctx = MakerNoteContext { make: Some("FUJIFILM"), model: None, tiff_offset: None, endian: Endian::Little };
+        let mut data = maker_note(&[(0x1001, 3, u16s(&[3], &Endian::Little))]);
+        assert!(decode_fujifilm_maker_note(&data, &ctx).is_some());
+        data[..8].copy_from_slice(b"FUJIFILL");
+        assert!(decode_fujifilm_maker_note(&data, &ctx).is_none());
+        // IFD 偏移超出范围
+        let mut data = b"FUJIFILM".to_vec();
+        data.extend(u32s(&[400], &Endian::Little));
+        assert!(decode_fujifilm_maker_note(&data, &ctx).is_none());
+    }
+}
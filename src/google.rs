@@ -0,0 +1,90 @@
+use crate::{
+    locale::tr,
+    makernote::MakerNote,
+    xmp::{XmpProperty, XmpValue},
+};
+
+/// Google 相机（Pixel）XMP 命名空间
+pub const NS_GCAMERA: &str = "http://ns.google.com/photos/1.0/camera/";
+/// Motion Photo 容器描述的命名空间
+pub const NS_GCONTAINER: &str = "http://ns.google.com/photos/1.0/container/";
+pub const NS_GCONTAINER_ITEM: &str = "http://ns.google.com/photos/1.0/container/item/";
+
+/// 提取 Google 相机的厂商标签
+///
+/// Pixel 等 Google 相机不写 IFD 格式的 MakerNote，厂商信息保存在 GCamera 命名空间的 XMP 属性中；
+/// 这里把它们整理成与 MakerNote 相同的形式，tag_id 均为 0
+///
+/// # 参数
+/// - `props`: 文件中的 XMP 属性
+///
+/// # 返回值
+/// - 厂商标签；没有任何 GCamera 属性时返回 None
+pub fn decode_google_camera_xmp(props: &[XmpProperty]) -> Option<MakerNote> {
+    let text = |local: &str| props.iter().find(|p| p.is(NS_GCAMERA, local)).and_then(|p| p.value.as_text());
+    let mut note = MakerNote::new("Google", "Google Camera XMP");
+
+    let flag = |v: &str| if v == "1" || v.eq_ignore_ascii_case("true") { tr("Yes", "是", "はい") } else { tr("No", "否", "いいえ") };
+    if let Some(v) = text("MotionPhoto") {
+        note.push(0, "MotionPhoto", tr("Motion Photo", "动态照片", "モーションフォト"), flag(v));
+    }
+    if let Some(v) = text("MotionPhotoVersion") {
+        note.push(0, "MotionPhotoVersion", tr("Motion Photo Version", "动态照片版本", "モーションフォトバージョン"), v);
+    }
+    if let Some(v) = text("MotionPhotoPresentationTimestampUs").and_then(|v| v.parse::<i64>().ok()) {
+        // -1 表示未指定
+        if v >= 0 {
+            note.push(
+                0,
+                "MotionPhotoPresentationTimestampUs",
+                tr("Motion Photo Presentation Timestamp", "静态帧时间点", "静止フレームの時刻"),
+                format!("{:.3} {}", v as f64 / 1_000_000.0, tr("s", "秒", "秒")),
+            );
+        }
+    }
+    if let Some(v) = text("MicroVideo") {
+        note.push(0, "MicroVideo", tr("Micro Video", "动态照片（旧版）", "モーションフォト（旧形式）"), flag(v));
+    }
+    if let Some(v) = text("MicroVideoOffset") {
+        note.push(0, "MicroVideoOffset", tr("Micro Video Offset", "视频距文件末尾的字节数", "ファイル末尾からの動画オフセット"), v);
+    }
+    if let Some(length) = motion_photo_video_length(props) {
+        note.push(0, "MotionPhotoVideoLength", tr("Embedded Video Length", "内嵌视频长度", "埋め込み動画の長さ"), format!("{} {}", length, tr("bytes", "字节", "バイト")));
+    }
+    if let Some(v) = text("SpecialTypeID") {
+        note.push(0, "SpecialTypeID", tr("Special Type ID", "特殊类型", "特殊タイプ"), v);
+    }
+    if let Some(v) = text("BurstID") {
+        note.push(0, "BurstID", tr("Burst ID", "连拍 ID", "連写 ID"), v);
+    }
+    if let Some(v) = text("BurstPrimary") {
+        note.push(0, "BurstPrimary", tr("Burst Primary", "连拍主图", "連写の代表画像"), flag(v));
+    }
+    if let Some(v) = text("PortraitNote") {
+        note.push(0, "PortraitNote", tr("Portrait Note", "人像模式说明", "ポートレートモードの注記"), v);
+    }
+    if let Some(v) = text("HdrPlusMakernote") {
+        // 加密的 protobuf，只给出大小
+        note.push(
+            0,
+            "HdrPlusMakernote",
+            tr("HDR+ Maker Note", "HDR+ 数据", "HDR+ データ"),
+            format!("{} {}", v.len(), tr("bytes (Base64, encrypted)", "字节（Base64，已加密）", "バイト（Base64、暗号化済み）")),
+        );
+    }
+    (!note.tags.is_empty()).then_some(note)
+}
+
+/// Container:Directory 中语义为 MotionPhoto 的条目长度，即附加在 JPEG 之后的视频大小
+fn motion_photo_video_length(props: &[XmpProperty]) -> Option<u64> {
+    let directory = props.iter().find(|p| p.is(NS_GCONTAINER, "Directory"))?;
+    let XmpValue::Seq(items) = &directory.value else { return None };
+    items.iter().find_map(|item| {
+        let item = item.field(NS_GCONTAINER, "Item").unwrap_or(item);
+        let semantic = item.field(NS_GCONTAINER_ITEM, "Semantic")?.as_text()?;
+        if semantic != "MotionPhoto" {
+            return None;
+        }
+        item.field(NS_GCONTAINER_ITEM, "Length")?.as_text()?.parse().ok()
+    })
+}
@@ -0,0 +1,631 @@
+use std::{
+    fs::File,
+    path::Path,
+    io::Read,
+};
+
+use crate::{error::Error, locale::tr, png::decompress_zlib};
+
+/// HEIF / AVIF 文件信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeifInfo {
+    /// ftyp 中的主品牌（如 "heic"、"avif"、"mif1"）
+    pub major_brand: String,
+    /// ftyp 中的兼容品牌
+    pub compatible_brands: Vec<String>,
+    /// 主图像的项目编号（pitm）
+    pub primary_item: Option<u32>,
+    /// 主图像的项目类型（如 "hvc1"、"av01"、"grid"）
+    pub primary_item_type: Option<String>,
+    /// 主图像宽度（ispe）
+    pub width: Option<u32>,
+    /// 主图像高度（ispe）
+    pub height: Option<u32>,
+    /// 逆时针旋转角度（irot），单位为度
+    pub rotation: Option<u16>,
+    /// 镜像轴（imir）：0 = 垂直轴（左右翻转），1 = 水平轴（上下翻转）
+    pub mirror: Option<u8>,
+    /// iinf 中的项目总数
+    pub item_count: usize,
+}
+
+/// HEIF / AVIF 文件中找到的元数据
+#[derive(Debug, Clone, Default)]
+pub struct HeifMetadataChunks {
+    /// EXIF 数据（TIFF 结构，已去掉偏移前缀）
+    pub exif: Option<Vec<u8>>,
+    /// XMP 数据包
+    pub xmp: Option<Vec<u8>>,
+    /// 文件信息
+    pub info: HeifInfo,
+}
+
+/// 一个 ISOBMFF 盒子
+pub(crate) struct BoxRef<'a> {
+    pub(crate) box_type: [u8; 4],
+    /// 盒子在文件中的起始偏移
+    pub(crate) offset: usize,
+    /// 盒子内容（不含头部）
+    pub(crate) body: &'a [u8],
+    /// 内容在文件中的起始偏移
+    pub(crate) body_offset: usize,
+}
+
+impl BoxRef<'_> {
+    pub(crate) fn name(&self) -> String {
+        String::from_utf8_lossy(&self.box_type).to_string()
+    }
+
+    pub(crate) fn truncated(&self) -> Error {
+        Error::TruncatedChunk { chunk: self.name(), offset: self.offset }
+    }
+}
+
+/// 按大端顺序读取盒子内容
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        ByteReader { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let out = self.data.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(out)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// 读取 0、4 或 8 字节的整数（iloc 中的可变长度字段）
+    fn uint(&mut self, size: u8) -> Option<u64> {
+        match size {
+            0 => Some(0),
+            4 => self.u32().map(u64::from),
+            8 => self.bytes(8).map(|b| u64::from_be_bytes(b.try_into().unwrap())),
+            _ => None,
+        }
+    }
+
+    /// 读取以空字符结尾的字符串，缺少结尾空字符时读到末尾
+    fn cstr(&mut self) -> String {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        self.pos += (end + 1).min(rest.len());
+        String::from_utf8_lossy(&rest[..end]).to_string()
+    }
+
+    /// FullBox 的版本号和 24 位标志
+    fn version_flags(&mut self) -> Option<(u8, u32)> {
+        let v = self.u32()?;
+        Some(((v >> 24) as u8, v & 0x00ff_ffff))
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.data[self.pos.min(self.data.len())..]
+    }
+}
+
+/// ISOBMFF 盒子头部
+pub(crate) struct BoxHeader {
+    pub(crate) box_type: [u8; 4],
+    /// 头部长度：8，带 64 位长度时为 16
+    pub(crate) header_len: usize,
+    /// 整个盒子的长度（含头部）
+    pub(crate) size: u64,
+}
+
+/// 读取一个盒子头部并检查长度
+///
+/// # 参数
+/// - `data`: 从盒子开头起的数据，至少包含完整的头部
+/// - `available`: 从盒子开头到所在范围结尾的字节数，size 为 0 的盒子延伸到这里
+/// - `offset`: 盒子在文件中的偏移，用于错误信息
+///
+/// # 返回值
+/// - 头部信息，保证 header_len <= size <= available；头部不完整或长度越界时返回 Error::TruncatedChunk
+pub(crate) fn read_box_header(data: &[u8], available: u64, offset: usize) -> Result<BoxHeader, Error> {
+    let truncated = |chunk: String| Error::TruncatedChunk { chunk, offset };
+    let Some(head) = data.get(..8) else {
+        return Err(truncated(String::new()));
+    };
+    let box_type = [head[4], head[5], head[6], head[7]];
+    let name = String::from_utf8_lossy(&box_type).to_string();
+    // size 为 1 时后面是 64 位长度，为 0 时盒子一直延伸到结尾
+    let (size, header_len) = match u32::from_be_bytes([head[0], head[1], head[2], head[3]]) {
+        0 => (available, 8),
+        1 => {
+            let large = data.get(8..16).ok_or_else(|| truncated(name.clone()))?;
+            (u64::from_be_bytes(large.try_into().unwrap()), 16)
+        }
+        n => (n as u64, 8),
+    };
+    // 64 位长度可能是构造的极大值，只与剩余长度比较，调用方据此计算结尾不会溢出
+    if size < header_len as u64 || size > available {
+        return Err(truncated(name));
+    }
+    Ok(BoxHeader { box_type, header_len, size })
+}
+
+/// 拆分一段数据中的连续盒子
+///
+/// # 参数
+/// - `data`: 盒子序列
+/// - `base`: data 在文件中的起始偏移，用于错误信息
+pub(crate) fn parse_boxes(data: &[u8], base: usize) -> Result<Vec<BoxRef<'_>>, Error> {
+    let mut boxes = Vec::new();
+    let mut pos = 0usize;
+    while pos < data.len() {
+        let offset = base + pos;
+        let rest = &data[pos..];
+        let header = read_box_header(rest, rest.len() as u64, offset)?;
+        let end = pos + header.size as usize;
+        boxes.push(BoxRef {
+            box_type: header.box_type,
+            offset,
+            body: &data[pos + header.header_len..end],
+            body_offset: offset + header.header_len,
+        });
+        pos = end;
+    }
+    Ok(boxes)
+}
+
+/// iinf 中的一个项目
+struct ItemInfo {
+    id: u32,
+    item_type: [u8; 4],
+    content_type: String,
+    content_encoding: String,
+}
+
+/// iloc 中一个项目的数据位置
+struct ItemLocation {
+    id: u32,
+    /// 0 = 文件偏移，1 = meta 中 idat 盒子内的偏移
+    construction_method: u8,
+    /// (偏移, 长度)，长度为 0 表示一直到数据结尾
+    extents: Vec<(u64, u64)>,
+}
+
+/// 遍历 HEIF / AVIF 的 ISOBMFF 盒子，提取 EXIF、XMP 和主图像的尺寸、旋转信息，只读取文件
+///
+/// # 参数
+/// - `path`: HEIC / HEIF / AVIF 文件路径
+///
+/// # 返回值
+/// - 找到的元数据（EXIF / XMP 都可能为空）
+/// - 文件无法读取、签名错误或盒子截断时返回 Error
+pub fn extract_metadata_from_heif(path: &Path) -> Result<HeifMetadataChunks, Error> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    extract_metadata_from_heif_bytes(&buf)
+}
+
+/// 遍历内存中 HEIF / AVIF 文件的盒子，参见 [`extract_metadata_from_heif`]
+///
+/// # 参数
+/// - `buf`: 完整的文件内容
+///
+/// # 返回值
+/// - 找到的元数据；签名错误或盒子截断时返回 Error
+pub fn extract_metadata_from_heif_bytes(buf: &[u8]) -> Result<HeifMetadataChunks, Error> {
+
+    // 第一个盒子必须是 ftyp
+    if buf.len() < 12 || &buf[4..8] != b"ftyp" {
+        return Err(Error::UnsupportedFormat(format!("heif ({})", tr("missing ftyp box", "缺少 ftyp 盒子", "ftyp ボックスがありません"))));
+    }
+
+    let mut found = HeifMetadataChunks::default();
+    let top = parse_boxes(buf, 0)?;
+    let mut meta_box = None;
+    for b in &top {
+        match &b.box_type {
+            b"ftyp" => {
+                if b.body.len() < 8 {
+                    return Err(b.truncated());
+                }
+                found.info.major_brand = String::from_utf8_lossy(&b.body[..4]).to_string();
+                found.info.compatible_brands = b.body[8..]
+                    .chunks_exact(4)
+                    .map(|c| String::from_utf8_lossy(c).to_string())
+                    .collect();
+            }
+            b"meta" if meta_box.is_none() => meta_box = Some(b),
+            _ => {}
+        }
+    }
+    let meta_box = match meta_box {
+        Some(b) => b,
+        None => return Ok(found),
+    };
+
+    // meta 是 FullBox：4 字节版本和标志之后是子盒子
+    if meta_box.body.len() < 4 {
+        return Err(meta_box.truncated());
+    }
+    let children = parse_boxes(&meta_box.body[4..], meta_box.body_offset + 4)?;
+
+    let mut items = Vec::new();
+    let mut locations = Vec::new();
+    let mut properties: Vec<&BoxRef> = Vec::new();
+    let mut associations: Vec<(u32, Vec<u16>)> = Vec::new();
+    let mut idat: &[u8] = &[];
+    let mut ipco_boxes = Vec::new();
+    let mut ipma_boxes = Vec::new();
+    for child in &children {
+        match &child.box_type {
+            b"pitm" => {
+                let mut r = ByteReader::new(child.body);
+                let (version, _) = r.version_flags().ok_or_else(|| child.truncated())?;
+                let id = if version == 0 { r.u16().map(u32::from) } else { r.u32() };
+                found.info.primary_item = Some(id.ok_or_else(|| child.truncated())?);
+            }
+            b"iinf" => items = parse_iinf(child)?,
+            b"iloc" => locations = parse_iloc(child)?,
+            b"idat" => idat = child.body,
+            b"iprp" => {
+                for b in parse_boxes(child.body, child.body_offset)? {
+                    match &b.box_type {
+                        b"ipco" => ipco_boxes.push(b),
+                        b"ipma" => ipma_boxes.push(b),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let ipco_children: Vec<Vec<BoxRef>> = ipco_boxes
+        .iter()
+        .map(|b| parse_boxes(b.body, b.body_offset))
+        .collect::<Result<_, _>>()?;
+    for list in &ipco_children {
+        properties.extend(list.iter());
+    }
+    for b in &ipma_boxes {
+        associations.extend(parse_ipma(b)?);
+    }
+
+    found.info.item_count = items.len();
+    found.info.primary_item_type = found
+        .info
+        .primary_item
+        .and_then(|id| items.iter().find(|i| i.id == id))
+        .map(|i| String::from_utf8_lossy(&i.item_type).to_string());
+
+    // 主图像的属性：ispe（尺寸）、irot（旋转）、imir（镜像）
+    if let Some(primary) = found.info.primary_item {
+        let indices = associations.iter().filter(|(id, _)| *id == primary).flat_map(|(_, p)| p.iter());
+        for &index in indices {
+            // 属性下标从 1 开始，0 表示没有关联
+            let prop = match (index as usize).checked_sub(1).and_then(|i| properties.get(i)) {
+                Some(p) => p,
+                None => continue,
+            };
+            let mut r = ByteReader::new(prop.body);
+            match &prop.box_type {
+                b"ispe" => {
+                    r.version_flags();
+                    if let (Some(w), Some(h)) = (r.u32(), r.u32()) {
+                        found.info.width = Some(w);
+                        found.info.height = Some(h);
+                    }
+                }
+                b"irot" => found.info.rotation = r.u8().map(|a| (a & 0x03) as u16 * 90),
+                b"imir" => found.info.mirror = r.u8().map(|a| a & 0x01),
+                _ => {}
+            }
+        }
+    }
+
+    // Exif 项目和 MIME 类型为 application/rdf+xml 的项目（XMP）
+    for item in &items {
+        let is_exif = &item.item_type == b"Exif";
+        let is_xmp = &item.item_type == b"mime" && item.content_type == "application/rdf+xml";
+        if !(is_exif && found.exif.is_none() || is_xmp && found.xmp.is_none()) {
+            continue;
+        }
+        let loc = match locations.iter().find(|l| l.id == item.id) {
+            Some(l) => l,
+            None => continue,
+        };
+        let data = read_item_data(loc, buf, idat).ok_or_else(|| Error::TruncatedChunk {
+            chunk: if is_exif { "Exif".to_string() } else { "mime".to_string() },
+            offset: loc.extents.first().map(|e| e.0 as usize).unwrap_or(0),
+        })?;
+        if is_exif {
+            // 4 字节大端偏移（TIFF 头相对于此后数据的位置），通常跳过 "Exif\0\0"
+            if data.len() < 4 {
+                return Err(Error::TruncatedChunk { chunk: "Exif".to_string(), offset: 0 });
+            }
+            let skip = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+            let tiff = data.get(4 + skip..).ok_or_else(|| {
+                Error::MalformedTiff(format!("{} {}", tr("TIFF offset in Exif item out of range:", "Exif 项目中的 TIFF 偏移超出范围:", "Exif アイテム内の TIFF オフセットが範囲外:"), skip))
+            })?;
+            found.exif = Some(tiff.to_vec());
+        } else if item.content_encoding == "deflate" {
+            found.xmp = Some(decompress_zlib(&data)?);
+        } else {
+            found.xmp = Some(data);
+        }
+    }
+
+    Ok(found)
+}
+
+/// 解析 iinf 盒子中的 infe 项目（只支持版本 2 和 3）
+fn parse_iinf(b: &BoxRef) -> Result<Vec<ItemInfo>, Error> {
+    let mut r = ByteReader::new(b.body);
+    let (version, _) = r.version_flags().ok_or_else(|| b.truncated())?;
+    let count = if version == 0 { r.u16().map(u32::from) } else { r.u32() };
+    count.ok_or_else(|| b.truncated())?;
+    let entries_offset = b.body_offset + r.pos;
+
+    let mut items = Vec::new();
+    for infe in parse_boxes(r.rest(), entries_offset)? {
+        if &infe.box_type != b"infe" {
+            continue;
+        }
+        let mut r = ByteReader::new(infe.body);
+        let (version, _) = r.version_flags().ok_or_else(|| infe.truncated())?;
+        if version < 2 {
+            continue;
+        }
+        let id = if version == 2 { r.u16().map(u32::from) } else { r.u32() };
+        let id = id.ok_or_else(|| infe.truncated())?;
+        r.u16().ok_or_else(|| infe.truncated())?; // item_protection_index
+        let item_type: [u8; 4] = r.bytes(4).ok_or_else(|| infe.truncated())?.try_into().unwrap();
+        let _name = r.cstr();
+        let (content_type, content_encoding) = if &item_type == b"mime" {
+            (r.cstr(), r.cstr())
+        } else {
+            (String::new(), String::new())
+        };
+        items.push(ItemInfo { id, item_type, content_type, content_encoding });
+    }
+    Ok(items)
+}
+
+/// 解析 iloc 盒子
+fn parse_iloc(b: &BoxRef) -> Result<Vec<ItemLocation>, Error> {
+    let mut r = ByteReader::new(b.body);
+    let parse = |r: &mut ByteReader| -> Option<Vec<ItemLocation>> {
+        let (version, _) = r.version_flags()?;
+        let sizes = r.u16()?;
+        let offset_size = (sizes >> 12) as u8;
+        let length_size = (sizes >> 8 & 0x0f) as u8;
+        let base_offset_size = (sizes >> 4 & 0x0f) as u8;
+        let index_size = if version == 1 || version == 2 { (sizes & 0x0f) as u8 } else { 0 };
+        let count = if version < 2 { r.u16()? as u32 } else { r.u32()? };
+
+        let mut locations = Vec::new();
+        for _ in 0..count {
+            let id = if version < 2 { r.u16()? as u32 } else { r.u32()? };
+            let construction_method = if version == 1 || version == 2 { (r.u16()? & 0x0f) as u8 } else { 0 };
+            r.u16()?; // data_reference_index
+            let base = r.uint(base_offset_size)?;
+            let extent_count = r.u16()?;
+            let mut extents = Some(Vec::new());
+            for _ in 0..extent_count {
+                r.uint(index_size)?;
+                let offset = r.uint(offset_size)?;
+                let length = r.uint(length_size)?;
+                // base + offset 溢出说明条目是构造的，丢弃该项目，但仍要读完它的所有区段
+                extents = extents.and_then(|mut e| {
+                    e.push((base.checked_add(offset)?, length));
+                    Some(e)
+                });
+            }
+            if let Some(extents) = extents {
+                locations.push(ItemLocation { id, construction_method, extents });
+            }
+        }
+        Some(locations)
+    };
+    parse(&mut r).ok_or_else(|| b.truncated())
+}
+
+/// 解析 ipma 盒子
+///
+/// # 返回值
+/// - (项目编号, 关联的属性下标列表)
+fn parse_ipma(b: &BoxRef) -> Result<Vec<(u32, Vec<u16>)>, Error> {
+    let mut r = ByteReader::new(b.body);
+    let parse = |r: &mut ByteReader| -> Option<Vec<(u32, Vec<u16>)>> {
+        let (version, flags) = r.version_flags()?;
+        let count = r.u32()?;
+        let mut out = Vec::new();
+        for _ in 0..count {
+            let id = if version < 1 { r.u16()? as u32 } else { r.u32()? };
+            let n = r.u8()?;
+            let mut indices = Vec::new();
+            for _ in 0..n {
+                // 最高位是 essential 标志，其余为属性下标
+                let index = if flags & 1 != 0 { r.u16()? & 0x7fff } else { (r.u8()? & 0x7f) as u16 };
+                indices.push(index);
+            }
+            out.push((id, indices));
+        }
+        Some(out)
+    };
+    parse(&mut r).ok_or_else(|| b.truncated())
+}
+
+/// 按 iloc 中的位置拼接项目数据
+///
+/// # 返回值
+/// - 位置超出文件或 idat 范围、构造方法不支持时返回 None
+fn read_item_data(loc: &ItemLocation, file: &[u8], idat: &[u8]) -> Option<Vec<u8>> {
+    let source = match loc.construction_method {
+        0 => file,
+        1 => idat,
+        _ => return None,
+    };
+    let mut out = Vec::new();
+    for &(offset, length) in &loc.extents {
+        let start = usize::try_from(offset).ok()?;
+        let end = if length == 0 { source.len() } else { start.checked_add(usize::try_from(length).ok()?)? };
+        out.extend_from_slice(source.get(start..end)?);
+    }
+    Some(out)
+}
+
+/// ISOBMFF 容器测试共用的构造工具
+#[cfg(test)]
+pub(crate) mod test_util {
+    /// 构造一个 32 位长度的盒子
+    pub(crate) fn make_box(box_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        [&(8 + data.len() as u32).to_be_bytes()[..], box_type, data].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_util::make_box;
+
+    fn box_ref<'a>(box_type: &[u8; 4], body: &'a [u8]) -> BoxRef<'a> {
+        BoxRef { box_type: *box_type, offset: 100, body, body_offset: 108 }
+    }
+
+    #[test]
+    fn box_headers() {
+        let file = [make_box(b"ftyp", b"heic"), [&[0, 0, 0, 1][..], b"mdat", &20u64.to_be_bytes(), b"abcd"].concat()].concat();
+        let boxes = parse_boxes(&file, 100).unwrap();
+        assert_eq!(boxes.len(), 2);
+        assert_eq!((&boxes[0].box_type, boxes[0].offset, boxes[0].body), (b"ftyp", 100, &b"heic"[..]));
+        // 64 位长度的头部为 16 字节
+        assert_eq!((&boxes[1].box_type, boxes[1].body_offset, boxes[1].body), (b"mdat", 128, &b"abcd"[..]));
+
+        // size 为 0 的盒子延伸到结尾
+        let header = read_box_header(&[0, 0, 0, 0, b'm', b'd', b'a', b't'], 50, 0).unwrap();
+        assert_eq!((header.size, header.header_len), (50, 8));
+
+        // 64 位长度为极大值、超出剩余长度、小于头部或头部不完整
+        let huge = [&[0, 0, 0, 1][..], b"mdat", &[0xff; 8]].concat();
+        assert!(matches!(read_box_header(&huge, 16, 7), Err(Error::TruncatedChunk { ref chunk, offset: 7 }) if chunk == "mdat"));
+        assert!(read_box_header(&make_box(b"free", b"ab"), 9, 0).is_err());
+        assert!(read_box_header(&[0, 0, 0, 4, b'f', b'r', b'e', b'e'], 8, 0).is_err());
+        assert!(read_box_header(&huge[..12], 12, 0).is_err());
+        assert!(matches!(parse_boxes(&[0, 0, 0], 5), Err(Error::TruncatedChunk { offset: 5, .. })));
+    }
+
+    #[test]
+    fn iloc_version1_with_base_offset_and_idat() {
+        // 偏移和长度各 4 字节，基准偏移 4 字节，没有区段下标
+        let mut body = vec![1, 0, 0, 0, 0x44, 0x40, 0, 2];
+        // 项目 1：文件偏移，基准 1000 + 区段 (24, 8)、(40, 0)
+        body.extend([0, 1, 0, 0, 0, 0]);
+        body.extend(1000u32.to_be_bytes());
+        body.extend([0, 2]);
+        body.extend([24u32.to_be_bytes(), 8u32.to_be_bytes(), 40u32.to_be_bytes(), 0u32.to_be_bytes()].concat());
+        // 项目 2：idat 内的偏移
+        body.extend([0, 2, 0, 1, 0, 0]);
+        body.extend(0u32.to_be_bytes());
+        body.extend([0, 1]);
+        body.extend([2u32.to_be_bytes(), 3u32.to_be_bytes()].concat());
+
+        let locations = parse_iloc(&box_ref(b"iloc", &body)).unwrap();
+        assert_eq!(locations.len(), 2);
+        assert_eq!((locations[0].id, locations[0].construction_method), (1, 0));
+        assert_eq!(locations[0].extents, [(1024, 8), (1040, 0)]);
+        assert_eq!((locations[1].id, locations[1].construction_method), (2, 1));
+
+        let file: Vec<u8> = (0..=255u8).cycle().take(1050).collect();
+        let data = read_item_data(&locations[0], &file, &[]).unwrap();
+        assert_eq!(data, [&file[1024..1032], &file[1040..]].concat());
+        assert_eq!(read_item_data(&locations[1], &file, b"abcdefg").unwrap(), b"cde");
+        // 超出 idat 范围
+        assert_eq!(read_item_data(&locations[1], &file, b"abc"), None);
+    }
+
+    #[test]
+    fn iloc_overflowing_entry_is_dropped() {
+        // 版本 0，偏移和基准偏移各 8 字节，长度 4 字节
+        let mut body = vec![0, 0, 0, 0, 0x84, 0x80, 0, 2];
+        body.extend([0, 1, 0, 0]);
+        body.extend(u64::MAX.to_be_bytes());
+        body.extend([0, 2]);
+        body.extend([&1u64.to_be_bytes()[..], &4u32.to_be_bytes(), &0u64.to_be_bytes(), &4u32.to_be_bytes()].concat());
+        // 溢出的项目之后的项目仍然正常解析
+        body.extend([0, 2, 0, 0]);
+        body.extend(16u64.to_be_bytes());
+        body.extend([0, 1]);
+        body.extend([&0u64.to_be_bytes()[..], &4u32.to_be_bytes()].concat());
+
+        let locations = parse_iloc(&box_ref(b"iloc", &body)).unwrap();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].id, 2);
+        assert_eq!(locations[0].extents, [(16, 4)]);
+    }
+
+    #[test]
+    fn iloc_truncated() {
+        let body = [0, 0, 0, 0, 0x44, 0x00, 0, 1, 0, 1, 0, 0, 0, 1, 0, 0];
+        assert!(matches!(parse_iloc(&box_ref(b"iloc", &body)), Err(Error::TruncatedChunk { offset: 100, .. })));
+    }
+
+    #[test]
+    fn ipma_small_and_large_indices() {
+        // 版本 0，1 字节下标；最高位是 essential 标志
+        let body = [0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 2, 0x81, 0x03, 0, 2, 0];
+        assert_eq!(parse_ipma(&box_ref(b"ipma", &body)).unwrap(), [(1, vec![1, 3]), (2, vec![])]);
+        // 版本 1、标志 1：4 字节项目编号，2 字节下标
+        let body = [1, 0, 0, 1, 0, 0, 0, 1, 0, 1, 0, 0, 1, 0x80, 0x02];
+        assert_eq!(parse_ipma(&box_ref(b"ipma", &body)).unwrap(), [(65536, vec![2])]);
+        // 条目数超过实际数据
+        let body = [0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 1, 0x81];
+        assert!(parse_ipma(&box_ref(b"ipma", &body)).is_err());
+    }
+
+    #[test]
+    fn primary_item_properties_and_exif() {
+        let infe = |id: u8, item_type: &[u8; 4]| make_box(b"infe", &[&[2, 0, 0, 0, 0, id, 0, 0][..], item_type, b"\0"].concat());
+        let iinf = make_box(b"iinf", &[&[0, 0, 0, 0, 0, 2][..], &infe(1, b"hvc1"), &infe(2, b"Exif")].concat());
+        let pitm = make_box(b"pitm", &[0, 0, 0, 0, 0, 1]);
+        let ipco = make_box(
+            b"ipco",
+            &[
+                make_box(b"ispe", &[&[0; 4][..], &4032u32.to_be_bytes(), &3024u32.to_be_bytes()].concat()),
+                make_box(b"irot", &[1]),
+                make_box(b"imir", &[1]),
+            ]
+            .concat(),
+        );
+        // 下标 0 表示没有关联，超出范围的下标被忽略
+        let ipma = make_box(b"ipma", &[0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 5, 0, 1, 0x82, 3, 9]);
+        let iprp = make_box(b"iprp", &[ipco, ipma].concat());
+        let exif = b"\0\0\0\x06Exif\0\0II\x2a\0\x08\0\0\0";
+        // Exif 项目放在 idat 中
+        let iloc = make_box(b"iloc", &[1, 0, 0, 0, 0x44, 0x00, 0, 1, 0, 2, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 18]);
+        let idat = make_box(b"idat", exif);
+        let meta = make_box(b"meta", &[&[0; 4][..], &pitm, &iinf, &iloc, &idat, &iprp].concat());
+        let file = [make_box(b"ftyp", b"heic\0\0\0\0mif1heic"), meta].concat();
+
+        let found = extract_metadata_from_heif_bytes(&file).unwrap();
+
+        assert_eq!(found.info.major_brand, "heic");
+        assert_eq!(found.info.compatible_brands, ["mif1", "heic"]);
+        assert_eq!(found.info.item_count, 2);
+        assert_eq!(found.info.primary_item_type.as_deref(), Some("hvc1"));
+        assert_eq!((found.info.width, found.info.height), (Some(4032), Some(3024)));
+        assert_eq!(found.info.rotation, Some(90));
+        assert_eq!(found.info.mirror, Some(1));
+        assert_eq!(found.exif.as_deref(), Some(&exif[10..]));
+        assert_eq!(found.xmp, None);
+    }
+}
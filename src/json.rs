@@ -0,0 +1,318 @@
+use little_exif::{
+    exif_tag::ExifTag,
+    endian::Endian,
+};
+
+use std::path::Path;
+
+use crate::{
+    ImageMetadata,
+    ContainerInfo,
+    TiffInfo,
+    XmpValue,
+    locale::current_locale,
+    png::{png_color_type_name, png_srgb_intent_name, PngAncillary, PngBackground, PngIntegrityReport, PngIssue},
+    tags::{
+        get_canonical_tag_name,
+        get_human_readable_tag_name,
+        get_tag_description,
+        get_ifd_name,
+        get_tag_raw_value,
+        get_tag_value_string,
+    },
+};
+
+/// 将单个 EXIF 标签转换为 JSON 对象
+pub fn tag_to_json(tag: &ExifTag, endian: &Endian) -> serde_json::Value {
+    serde_json::json!({
+        "tag_id": tag.as_u16(),
+        "ifd": get_ifd_name(tag.get_group()),
+        "name": get_canonical_tag_name(tag),
+        "localized_name": get_human_readable_tag_name(tag),
+        "description": get_tag_description(tag),
+        "raw_value": get_tag_raw_value(tag, endian),
+        "value": get_tag_value_string(tag, endian),
+    })
+}
+
+/// 将 XMP 属性值转换为 JSON
+///
+/// 简单值为字符串，Seq / Bag 为数组，结构体为对象；
+/// 所有项都带 xml:lang 的 Alt 为以语言为键的对象，否则为数组
+pub fn xmp_value_to_json(value: &XmpValue) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        XmpValue::Simple(s) => Value::from(s.as_str()),
+        XmpValue::Seq(items) | XmpValue::Bag(items) => {
+            Value::from(items.iter().map(xmp_value_to_json).collect::<Vec<_>>())
+        }
+        XmpValue::Alt(items) if items.iter().all(|(lang, _)| lang.is_some()) => Value::Object(
+            items.iter()
+                .map(|(lang, v)| (lang.clone().unwrap_or_default(), xmp_value_to_json(v)))
+                .collect(),
+        ),
+        XmpValue::Alt(items) => Value::from(items.iter().map(|(_, v)| xmp_value_to_json(v)).collect::<Vec<_>>()),
+        XmpValue::Struct(fields) => Value::Object(
+            fields.iter().map(|f| (f.name.clone(), xmp_value_to_json(&f.value))).collect(),
+        ),
+    }
+}
+
+/// 将图片元数据转换为 JSON 文档
+///
+/// ### 参数
+/// - `meta`: 图片元数据
+///
+/// ### 返回值
+/// - 包含文件信息、容器信息、EXIF 标签和 XMP 属性的 JSON 对象
+pub fn to_json(meta: &ImageMetadata) -> serde_json::Value {
+    let png = match &meta.container {
+        Some(ContainerInfo::Png(h)) => serde_json::json!({
+            "width": h.width,
+            "height": h.height,
+            "bit_depth": h.bit_depth,
+            "color_type": h.color_type,
+            "color_type_name": png_color_type_name(h.color_type),
+            "compression_method": h.compression_method,
+            "filter_method": h.filter_method,
+            "interlace_method": h.interlace_method,
+        }),
+        _ => serde_json::Value::Null,
+    };
+    let webp = match &meta.container {
+        Some(ContainerInfo::Webp(info)) => serde_json::json!({
+            "encoding": info.encoding,
+            "flags": info.flags,
+            "width": info.width,
+            "height": info.height,
+            "icc_size": info.icc_profile.as_ref().map(|p| p.len()),
+            "animation": info.animation.as_ref().map(|a| serde_json::json!({
+                "background_color": format!("#{:08X}", a.background_color),
+                "loop_count": a.loop_count,
+                "total_duration_ms": a.total_duration(),
+                "frames": a.frames.iter().map(|f| serde_json::json!({
+                    "x_offset": f.x_offset,
+                    "y_offset": f.y_offset,
+                    "width": f.width,
+                    "height": f.height,
+                    "duration_ms": f.duration,
+                    "dispose_to_background": f.dispose_to_background,
+                    "blend": f.blend,
+                })).collect::<Vec<_>>(),
+            })),
+        }),
+        _ => serde_json::Value::Null,
+    };
+    let jxl = match &meta.container {
+        Some(ContainerInfo::Jxl(info)) => serde_json::json!({
+            "container": info.container,
+            "width": info.width,
+            "height": info.height,
+            "boxes": info.boxes,
+            "jumbf_size": info.jumbf.as_ref().map(|j| j.len()),
+        }),
+        _ => serde_json::Value::Null,
+    };
+    let heif = match &meta.container {
+        Some(ContainerInfo::Heif(info)) => serde_json::json!({
+            "major_brand": info.major_brand,
+            "compatible_brands": info.compatible_brands,
+            "primary_item": info.primary_item,
+            "primary_item_type": info.primary_item_type,
+            "item_count": info.item_count,
+            "width": info.width,
+            "height": info.height,
+            "rotation": info.rotation,
+            "mirror": info.mirror,
+        }),
+        _ => serde_json::Value::Null,
+    };
+    let tiff = match &meta.container {
+        Some(ContainerInfo::Tiff(info)) => tiff_to_json(info),
+        _ => serde_json::Value::Null,
+    };
+    let raw = match &meta.container {
+        Some(ContainerInfo::Raw(info)) => serde_json::json!({
+            "format": info.format,
+            "camera": info.camera,
+            "tiff_offset": info.tiff_offset,
+            "maker_note": info.maker_note.as_ref().map(|n| serde_json::json!({
+                "vendor": n.vendor,
+                "offset": n.file_offset,
+                "tiff_offset": n.tiff_offset,
+                "length": n.data.len(),
+            })),
+            "previews": info.previews.iter().map(|p| serde_json::json!({
+                "source": p.source,
+                "offset": p.offset,
+                "length": p.length,
+                "width": p.width,
+                "height": p.height,
+            })).collect::<Vec<_>>(),
+            "tiff": info.tiff.as_ref().map(tiff_to_json),
+        }),
+        _ => serde_json::Value::Null,
+    };
+    serde_json::json!({
+        "file": meta.path.display().to_string(),
+        "format": meta.format,
+        "source": meta.source.map(|s| s.as_str()),
+        "endian": format!("{:?}", meta.endian),
+        "locale": current_locale().code(),
+        "png": png,
+        "webp": webp,
+        "jxl": jxl,
+        "heif": heif,
+        "tiff": tiff,
+        "raw": raw,
+        "png_ancillary": meta.png_ancillary.as_ref().map(ancillary_to_json),
+        "png_issues": meta.png_issues.iter().map(issue_to_json).collect::<Vec<_>>(),
+        "iptc_size": meta.iptc.as_ref().map(|d| d.len()),
+        "png_text": meta.png_text.iter()
+            .map(|t| serde_json::json!({
+                "chunk": t.chunk,
+                "keyword": t.keyword,
+                "language": t.language,
+                "text": t.text,
+            }))
+            .collect::<Vec<_>>(),
+        "tags": meta.exif_tags.iter().map(|t| tag_to_json(t, &meta.endian)).collect::<Vec<_>>(),
+        "maker_note": meta.maker_note.as_ref().map(|n| serde_json::json!({
+            "vendor": n.vendor,
+            "format": n.format,
+            "tags": n.tags.iter().map(|t| serde_json::json!({
+                "tag_id": t.tag_id,
+                "name": t.name,
+                "localized_name": t.label,
+                "value": t.value,
+            })).collect::<Vec<_>>(),
+        })),
+        "composite": meta.composite_tags.iter().map(|t| serde_json::json!({
+            "name": t.name,
+            "localized_name": t.label,
+            "value": t.value,
+        })).collect::<Vec<_>>(),
+        "live_photo": meta.live_photo.as_ref().map(|l| serde_json::json!({
+            "content_identifier": l.content_identifier,
+            "video": l.video.as_ref().map(|v| v.display().to_string()),
+        })),
+        "xmp": meta.xmp_properties.iter()
+            .map(|p| serde_json::json!({ "name": p.name, "namespace": p.namespace, "value": xmp_value_to_json(&p.value) }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// 将 TIFF 结构转换为 JSON 对象，按 IFD 列出全部标签
+fn tiff_to_json(info: &TiffInfo) -> serde_json::Value {
+    serde_json::json!({
+        "byte_order": if info.endian == Endian::Big { "MM" } else { "II" },
+        "big_tiff": info.big_tiff,
+        "magic": info.magic,
+        "dng_version": info.dng_version(),
+        "ifds": info.ifds.iter().map(|ifd| serde_json::json!({
+            "name": ifd.name,
+            "offset": ifd.offset,
+            "tags": ifd.entries.iter().map(|e| {
+                let mut tag = tag_to_json(&e.tag, &info.endian);
+                tag["ifd"] = serde_json::Value::from(ifd.name.as_str());
+                tag["type"] = serde_json::Value::from(e.type_code);
+                tag["count"] = serde_json::Value::from(e.count);
+                tag
+            }).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// 将 PNG 辅助数据块信息转换为 JSON 对象，缺少的数据块为 null
+fn ancillary_to_json(info: &PngAncillary) -> serde_json::Value {
+    serde_json::json!({
+        "phys": info.physical_size.map(|p| serde_json::json!({
+            "pixels_per_unit_x": p.pixels_per_unit_x,
+            "pixels_per_unit_y": p.pixels_per_unit_y,
+            "unit": p.unit,
+            "dpi": p.dpi().map(|(x, y)| [x, y]),
+        })),
+        "time": info.modified.map(|t| t.to_string()),
+        "gamma": info.gamma,
+        "chrm": info.chromaticities.map(|c| serde_json::json!({
+            "white": [c.white_x, c.white_y],
+            "red": [c.red_x, c.red_y],
+            "green": [c.green_x, c.green_y],
+            "blue": [c.blue_x, c.blue_y],
+        })),
+        "srgb": info.srgb_intent.map(|i| serde_json::json!({
+            "intent": i,
+            "name": png_srgb_intent_name(i),
+        })),
+        "iccp": info.icc_profile.as_ref().map(|p| serde_json::json!({
+            "name": p.name,
+            "size": p.profile.len(),
+        })),
+        "sbit": info.significant_bits,
+        "bkgd": info.background.map(|bg| match bg {
+            PngBackground::PaletteIndex(i) => serde_json::json!({ "palette_index": i }),
+            PngBackground::Gray(g) => serde_json::json!({ "gray": g }),
+            PngBackground::Rgb(r, g, b) => serde_json::json!({ "rgb": [r, g, b] }),
+        }),
+        "trns": info.transparency.is_some(),
+        "apng": info.animation.as_ref().map(|a| serde_json::json!({
+            "num_frames": a.num_frames,
+            "num_plays": a.num_plays,
+            "total_duration": a.total_duration(),
+            "frames": a.frames.iter().map(|f| serde_json::json!({
+                "sequence_number": f.sequence_number,
+                "width": f.width,
+                "height": f.height,
+                "x_offset": f.x_offset,
+                "y_offset": f.y_offset,
+                "delay": f.delay_seconds(),
+                "dispose_op": f.dispose_op,
+                "blend_op": f.blend_op,
+            })).collect::<Vec<_>>(),
+        })),
+    })
+}
+
+/// 将 PNG 完整性检查报告转换为 JSON 文档
+///
+/// ### 参数
+/// - `path`: PNG 文件路径
+/// - `report`: 完整性检查报告
+///
+/// ### 返回值
+/// - 包含数据块列表和问题列表的 JSON 对象
+pub fn integrity_report_to_json(path: &Path, report: &PngIntegrityReport) -> serde_json::Value {
+    serde_json::json!({
+        "file": path.display().to_string(),
+        "valid": report.is_ok(),
+        "chunks": report.chunks.iter()
+            .map(|c| serde_json::json!({
+                "type": c.chunk_type,
+                "offset": c.offset,
+                "length": c.length,
+                "crc": format!("{:08X}", c.crc),
+                "crc_ok": c.crc_ok,
+            }))
+            .collect::<Vec<_>>(),
+        "issues": report.issues.iter().map(issue_to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// 将单个完整性问题转换为 JSON 对象
+fn issue_to_json(issue: &PngIssue) -> serde_json::Value {
+    let (chunk, offset) = match issue {
+        PngIssue::BadCrc { chunk, offset, .. } | PngIssue::Truncated { chunk, offset } => {
+            (Some(chunk.as_str()), Some(*offset))
+        }
+        PngIssue::Undecodable { chunk, offset, .. } => (Some(chunk.as_str()), Some(*offset)),
+        PngIssue::IhdrNotFirst { chunk } => (Some(chunk.as_str()), Some(8)),
+        PngIssue::MissingIend { offset } | PngIssue::DataAfterIend { offset, .. } => (None, Some(*offset)),
+    };
+    serde_json::json!({
+        "kind": issue.kind(),
+        "chunk": chunk.filter(|c| !c.is_empty()),
+        "offset": offset,
+        "message": issue.to_string(),
+        "exit_code": issue.to_error().exit_code(),
+    })
+}
@@ -0,0 +1,327 @@
+use std::{
+    fs::File,
+    path::Path,
+    io::Read,
+};
+
+use crate::{error::Error, heif::parse_boxes, locale::tr};
+
+/// 裸码流的签名
+const CODESTREAM_SIG: &[u8] = &[0xff, 0x0a];
+/// ISOBMFF 容器的签名（第一个 "JXL " 盒子）
+const CONTAINER_SIG: &[u8] = &[0, 0, 0, 0x0c, b'J', b'X', b'L', b' ', 0x0d, 0x0a, 0x87, 0x0a];
+/// brob 盒子解压后的长度上限，EXIF / XMP 一般只有几十 KB
+const MAX_BROB_SIZE: u64 = 64 * 1024 * 1024;
+
+/// JPEG XL 文件信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JxlInfo {
+    /// 是否为 ISOBMFF 容器格式（否则为裸码流，不含任何元数据）
+    pub container: bool,
+    /// 码流 SizeHeader 中的图像宽度
+    pub width: Option<u32>,
+    /// 码流 SizeHeader 中的图像高度
+    pub height: Option<u32>,
+    /// 按顺序列出的盒子类型，brob 压缩的盒子记为 "brob(Exif)" 形式
+    pub boxes: Vec<String>,
+    /// jumb 盒子（JUMBF，如 C2PA 内容凭证）的内容
+    pub jumbf: Option<Vec<u8>>,
+}
+
+/// JPEG XL 文件中找到的元数据
+#[derive(Debug, Clone, Default)]
+pub struct JxlMetadataChunks {
+    /// EXIF 数据（TIFF 结构，已去掉 4 字节偏移前缀）
+    pub exif: Option<Vec<u8>>,
+    /// XMP 数据包
+    pub xmp: Option<Vec<u8>>,
+    /// 文件信息
+    pub info: JxlInfo,
+}
+
+/// 解析 JPEG XL 文件的盒子结构，提取 EXIF、XMP、JUMBF 和图像尺寸，只读取文件
+///
+/// # 参数
+/// - `path`: JPEG XL 文件路径
+///
+/// # 返回值
+/// - 找到的元数据；裸码流只有图像尺寸
+/// - 文件无法读取、签名错误、盒子截断或 Brotli 解压失败时返回 Error
+pub fn extract_metadata_from_jxl(path: &Path) -> Result<JxlMetadataChunks, Error> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    extract_metadata_from_jxl_bytes(&buf)
+}
+
+/// 解析内存中 JPEG XL 文件的盒子结构，参见 [`extract_metadata_from_jxl`]
+///
+/// # 参数
+/// - `buf`: 完整的 JPEG XL 文件内容
+///
+/// # 返回值
+/// - 找到的元数据；签名错误、盒子截断或 Brotli 解压失败时返回 Error
+pub fn extract_metadata_from_jxl_bytes(buf: &[u8]) -> Result<JxlMetadataChunks, Error> {
+    let mut found = JxlMetadataChunks::default();
+    if buf.starts_with(CODESTREAM_SIG) {
+        set_size(&mut found.info, buf);
+        return Ok(found);
+    }
+    if !buf.starts_with(CONTAINER_SIG) {
+        return Err(Error::invalid_signature("jxl"));
+    }
+    found.info.container = true;
+
+    for b in parse_boxes(buf, 0)? {
+        // brob 盒子：4 字节原始盒子类型 + Brotli 压缩的内容
+        if &b.box_type == b"brob" {
+            if b.body.len() < 4 {
+                return Err(Error::TruncatedChunk { chunk: b.name(), offset: b.offset });
+            }
+            let inner_type: [u8; 4] = [b.body[0], b.body[1], b.body[2], b.body[3]];
+            found.info.boxes.push(format!("brob({})", String::from_utf8_lossy(&inner_type)));
+            let inner = decompress_brotli(&b.body[4..], MAX_BROB_SIZE)?;
+            apply_box(&mut found, &inner_type, &inner, b.offset)?;
+        } else {
+            found.info.boxes.push(b.name());
+            apply_box(&mut found, &b.box_type, b.body, b.offset)?;
+        }
+    }
+
+    Ok(found)
+}
+
+/// 处理一个（已解压的）盒子
+fn apply_box(found: &mut JxlMetadataChunks, box_type: &[u8; 4], data: &[u8], offset: usize) -> Result<(), Error> {
+    match box_type {
+        // Exif 盒子：4 字节大端偏移（TIFF 头相对于此后数据的位置）+ 数据
+        b"Exif" if found.exif.is_none() => {
+            if data.len() < 4 {
+                return Err(Error::TruncatedChunk { chunk: "Exif".to_string(), offset });
+            }
+            let skip = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+            let tiff = data.get(4 + skip..).ok_or_else(|| {
+                Error::MalformedTiff(format!("{} {}", tr("TIFF offset in Exif box out of range:", "Exif 盒子中的 TIFF 偏移超出范围:", "Exif ボックス内の TIFF オフセットが範囲外:"), skip))
+            })?;
+            found.exif = Some(tiff.to_vec());
+        }
+        b"xml " if found.xmp.is_none() => found.xmp = Some(data.to_vec()),
+        b"jumb" if found.info.jumbf.is_none() => found.info.jumbf = Some(data.to_vec()),
+        b"jxlc" => set_size(&mut found.info, data),
+        // jxlp 盒子：4 字节序号 + 部分码流，SizeHeader 在第一个部分中
+        b"jxlp" if data.len() > 4 => {
+            let index = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) & 0x7fff_ffff;
+            if index == 0 {
+                set_size(&mut found.info, &data[4..]);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 解压 Brotli 数据
+///
+/// # 参数
+/// - `data`: 压缩数据
+/// - `limit`: 解压后的长度上限，防止构造的数据耗尽内存
+///
+/// # 返回值
+/// - 解压后的数据；数据损坏或超过上限时返回 Error::Decompression
+fn decompress_brotli(data: &[u8], limit: u64) -> Result<Vec<u8>, Error> {
+    let label = tr("Brotli in brob box", "brob 盒子 Brotli", "brob ボックスの Brotli");
+    let mut out = Vec::new();
+    // 多读一个字节以判断是否超过上限
+    brotli_decompressor::Decompressor::new(data, 4096)
+        .take(limit + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| Error::Decompression(format!("{}: {}", label, e)))?;
+    if out.len() as u64 > limit {
+        return Err(Error::Decompression(format!(
+            "{}: {} {} {}",
+            label,
+            tr("decompressed size exceeds", "解压后超过", "展開後のサイズが上限を超過:"),
+            limit,
+            tr("bytes", "字节", "バイト")
+        )));
+    }
+    Ok(out)
+}
+
+/// 从码流开头的 SizeHeader 读取图像尺寸
+fn set_size(info: &mut JxlInfo, codestream: &[u8]) {
+    if info.width.is_none() {
+        if let Some((width, height)) = read_size_header(codestream) {
+            info.width = Some(width);
+            info.height = Some(height);
+        }
+    }
+}
+
+/// 按低位在前的顺序读取码流中的位
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, n: usize) -> Option<u32> {
+        let mut v = 0u32;
+        for i in 0..n {
+            let byte = *self.data.get(self.pos / 8)?;
+            v |= (((byte >> (self.pos % 8)) & 1) as u32) << i;
+            self.pos += 1;
+        }
+        Some(v)
+    }
+
+    /// SizeHeader 中的 U32(Bits(9), Bits(13), Bits(18), Bits(30)) + 1
+    fn read_dimension(&mut self) -> Option<u32> {
+        let bits = [9, 13, 18, 30][self.read(2)? as usize];
+        Some(self.read(bits)? + 1)
+    }
+}
+
+/// 解析 SizeHeader
+///
+/// # 返回值
+/// - (宽度, 高度)，码流签名不对或数据不足时返回 None
+fn read_size_header(codestream: &[u8]) -> Option<(u32, u32)> {
+    if !codestream.starts_with(CODESTREAM_SIG) {
+        return None;
+    }
+    let mut r = BitReader { data: &codestream[2..], pos: 0 };
+    let small = r.read(1)? == 1;
+    let height = if small { (r.read(5)? + 1) * 8 } else { r.read_dimension()? };
+    let ratio = r.read(3)?;
+    // 宽高比为 0 时单独编码宽度，否则由高度按固定比例计算
+    let width = match ratio {
+        0 if small => (r.read(5)? + 1) * 8,
+        0 => r.read_dimension()?,
+        _ => {
+            let (num, den) = [(1, 1), (12, 10), (4, 3), (3, 2), (16, 9), (5, 4), (2, 1)][ratio as usize - 1];
+            (height as u64 * num / den) as u32
+        }
+    };
+    Some((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heif::test_util::make_box;
+
+    /// 按低位在前的顺序写入位，用于构造 SizeHeader
+    #[derive(Default)]
+    struct BitWriter {
+        data: Vec<u8>,
+        pos: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, n: usize, v: u32) -> &mut Self {
+            for i in 0..n {
+                if self.pos.is_multiple_of(8) {
+                    self.data.push(0);
+                }
+                self.data[self.pos / 8] |= (((v >> i) & 1) as u8) << (self.pos % 8);
+                self.pos += 1;
+            }
+            self
+        }
+
+        fn codestream(&self) -> Vec<u8> {
+            [CODESTREAM_SIG, &self.data].concat()
+        }
+    }
+
+    /// 只包含一个未压缩元块的 Brotli 流（WBITS = 16）
+    fn brotli_stored(data: &[u8]) -> Vec<u8> {
+        // ISLAST = 0，MNIBBLES = 4，MLEN - 1，ISUNCOMPRESSED = 1，然后补齐到字节边界
+        let header = ((data.len() as u32 - 1) << 4) | 1 << 20;
+        // 最后是 ISLAST = 1、ISLASTEMPTY = 1 的空元块
+        [&header.to_le_bytes()[..3], data, &[0x03]].concat()
+    }
+
+    #[test]
+    fn size_header_small() {
+        // 高度和宽度都是 8 的倍数且不超过 256 时使用 5 位编码
+        let cs = BitWriter::default().write(1, 1).write(5, 31).write(3, 0).write(5, 15).codestream();
+        assert_eq!(read_size_header(&cs), Some((128, 256)));
+        // 宽高比 4:3
+        let cs = BitWriter::default().write(1, 1).write(5, 5).write(3, 3).codestream();
+        assert_eq!(read_size_header(&cs), Some((64, 48)));
+    }
+
+    #[test]
+    fn size_header_dimensions() {
+        // 高度 1080 用 Bits(13)，宽高比 16:9
+        let cs = BitWriter::default().write(1, 0).write(2, 1).write(13, 1079).write(3, 5).codestream();
+        assert_eq!(read_size_header(&cs), Some((1920, 1080)));
+        // 宽度单独编码，用 Bits(30) 和 Bits(9)
+        let cs = BitWriter::default()
+            .write(1, 0)
+            .write(2, 3)
+            .write(30, 99_999)
+            .write(3, 0)
+            .write(2, 0)
+            .write(9, 0)
+            .codestream();
+        assert_eq!(read_size_header(&cs), Some((1, 100_000)));
+    }
+
+    #[test]
+    fn size_header_truncated_or_unsigned() {
+        let cs = BitWriter::default().write(1, 0).write(2, 3).write(30, 99_999).codestream();
+        assert_eq!(read_size_header(&cs), None);
+        assert_eq!(read_size_header(&[0xff, 0x0a]), None);
+        assert_eq!(read_size_header(&[0x0a, 0xff, 0x00]), None);
+    }
+
+    #[test]
+    fn brob_boxes_are_decompressed() {
+        let exif = [&[0, 0, 0, 0][..], b"MM\0\x2a\0\0\0\x08"].concat();
+        let mut brob_exif = b"Exif".to_vec();
+        brob_exif.extend(brotli_stored(&exif));
+        let mut brob_xmp = b"xml ".to_vec();
+        brob_xmp.extend(brotli_stored(b"<x:xmpmeta/>"));
+        let cs = BitWriter::default().write(1, 1).write(5, 5).write(3, 3).codestream();
+        let jxlp = [&0x8000_0000u32.to_be_bytes()[..], &cs].concat();
+
+        let file = [
+            CONTAINER_SIG.to_vec(),
+            make_box(b"ftyp", b"jxl \0\0\0\0jxl "),
+            make_box(b"brob", &brob_exif),
+            make_box(b"brob", &brob_xmp),
+            make_box(b"jxlp", &jxlp),
+        ]
+        .concat();
+        let found = extract_metadata_from_jxl_bytes(&file).unwrap();
+        assert_eq!(found.exif.as_deref(), Some(&exif[4..]));
+        assert_eq!(found.xmp.as_deref(), Some(&b"<x:xmpmeta/>"[..]));
+        assert_eq!((found.info.width, found.info.height), (Some(64), Some(48)));
+        assert_eq!(found.info.boxes, ["JXL ", "ftyp", "brob(Exif)", "brob(xml )", "jxlp"]);
+    }
+
+    #[test]
+    fn brotli_output_is_limited() {
+        let data = brotli_stored(b"abcdef");
+        assert_eq!(decompress_brotli(&data, 6).unwrap(), b"abcdef");
+        assert!(matches!(decompress_brotli(&data, 5), Err(Error::Decompression(_))));
+    }
+
+    #[test]
+    fn malformed_boxes() {
+        // brob 盒子不足 4 字节
+        let file = [CONTAINER_SIG, &make_box(b"brob", b"Ex")].concat();
+        assert!(matches!(extract_metadata_from_jxl_bytes(&file), Err(Error::TruncatedChunk { offset: 12, .. })));
+        // Brotli 数据损坏
+        let file = [CONTAINER_SIG, &make_box(b"brob", b"Exif\xff\xff\xff\xff")].concat();
+        assert!(matches!(extract_metadata_from_jxl_bytes(&file), Err(Error::Decompression(_))));
+        // 64 位长度为极大值，不能溢出
+        let file = [CONTAINER_SIG, &[0, 0, 0, 1], b"jxlc", &[0xff; 8]].concat();
+        assert!(matches!(extract_metadata_from_jxl_bytes(&file), Err(Error::TruncatedChunk { offset: 12, .. })));
+        // 长度小于盒子头部
+        let file = [CONTAINER_SIG, &[0, 0, 0, 4], b"jxlc"].concat();
+        assert!(matches!(extract_metadata_from_jxl_bytes(&file), Err(Error::TruncatedChunk { offset: 12, .. })));
+    }
+}
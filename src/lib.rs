@@ -0,0 +1,466 @@
+//! 图片 EXIF / XMP 元数据读取库
+//!
+//! 入口函数为 [`read_metadata`]，返回的 [`ImageMetadata`] 中包含 EXIF 标签、
+//! XMP 属性以及容器（如 PNG IHDR）信息。
+
+pub mod apple;
+pub mod batch;
+pub mod canon;
+pub mod composite;
+pub mod error;
+pub mod fujifilm;
+pub mod google;
+pub mod heif;
+pub mod json;
+pub mod jxl;
+pub mod locale;
+pub mod makernote;
+pub mod nikon;
+pub mod olympus;
+pub mod png;
+pub mod raw;
+pub mod samsung;
+pub mod sony;
+pub mod tags;
+pub mod tiff;
+pub mod webp;
+pub mod xmp;
+pub mod xmp_writer;
+
+use little_exif::{
+    metadata::Metadata,
+    exif_tag::ExifTag,
+    filetype::FileExtension,
+    endian::Endian,
+};
+use std::{
+    str::FromStr,
+    path::{Path, PathBuf},
+};
+
+pub use apple::{LivePhoto, LivePhotoIndex};
+pub use composite::CompositeTag;
+pub use error::Error;
+pub use heif::HeifInfo;
+pub use jxl::JxlInfo;
+pub use locale::Locale;
+pub use makernote::{MakerNote, MakerNoteTag};
+pub use tiff::TiffInfo;
+pub use png::{PngAncillary, PngIhdr, PngIssue, PngTextEntry};
+pub use raw::RawInfo;
+pub use webp::WebpInfo;
+pub use xmp::{XmpProperty, XmpValue};
+
+/// 支持的图片格式（扩展名）列表
+pub const SUPPORTED_FORMATS: &[&str] = &[
+    "jpg", "jpeg", "png", "jxl", "webp", "heic", "heif", "hif", "avif", "tif", "tiff", "dng",
+    "nef", "nrw", "cr2", "cr3", "arw", "sr2", "orf", "rw2", "pef", "raf",
+];
+
+/// 元数据来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataSource {
+    /// little_exif 直接读取的 EXIF
+    Exif,
+    /// PNG eXIf 或文本数据块（Raw profile）中的 EXIF
+    PngExif,
+    /// 由 PNG 中的 XMP 映射得到的 EXIF
+    PngXmp,
+    /// 由 PNG 文本关键字（Title、Author 等）映射得到的 EXIF
+    PngText,
+    /// WebP EXIF 数据块中的 EXIF
+    WebpExif,
+    /// 由 WebP 中的 XMP 映射得到的 EXIF
+    WebpXmp,
+    /// JPEG XL Exif 盒子中的 EXIF
+    JxlExif,
+    /// 由 JPEG XL 中的 XMP 映射得到的 EXIF
+    JxlXmp,
+    /// HEIF / AVIF Exif 项目中的 EXIF
+    HeifExif,
+    /// 由 HEIF / AVIF 中的 XMP 映射得到的 EXIF
+    HeifXmp,
+    /// TIFF / DNG 文件本身的 IFD
+    Tiff,
+    /// 相机 RAW 文件中的 EXIF
+    Raw,
+    /// 由相机 RAW 文件中的 XMP 映射得到的 EXIF
+    RawXmp,
+}
+
+impl MetadataSource {
+    /// 获取来源的机器可读名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetadataSource::Exif => "exif",
+            MetadataSource::PngExif => "png-exif",
+            MetadataSource::PngXmp => "png-xmp",
+            MetadataSource::PngText => "png-text",
+            MetadataSource::WebpExif => "webp-exif",
+            MetadataSource::WebpXmp => "webp-xmp",
+            MetadataSource::JxlExif => "jxl-exif",
+            MetadataSource::JxlXmp => "jxl-xmp",
+            MetadataSource::HeifExif => "heif-exif",
+            MetadataSource::HeifXmp => "heif-xmp",
+            MetadataSource::Tiff => "tiff",
+            MetadataSource::Raw => "raw",
+            MetadataSource::RawXmp => "raw-xmp",
+        }
+    }
+}
+
+/// 图片容器信息
+#[derive(Debug, Clone)]
+pub enum ContainerInfo {
+    Png(PngIhdr),
+    Webp(WebpInfo),
+    Jxl(JxlInfo),
+    Heif(HeifInfo),
+    Tiff(TiffInfo),
+    Raw(RawInfo),
+}
+
+/// 一张图片读取到的全部元数据
+#[derive(Debug, Clone)]
+pub struct ImageMetadata {
+    /// 图片文件路径
+    pub path: PathBuf,
+    /// 小写的文件扩展名
+    pub format: String,
+    /// 元数据来源，未找到任何元数据时为 None
+    pub source: Option<MetadataSource>,
+    /// EXIF 数据的字节序
+    pub endian: Endian,
+    /// EXIF 标签列表
+    pub exif_tags: Vec<ExifTag>,
+    /// XMP 属性列表
+    pub xmp_properties: Vec<XmpProperty>,
+    /// 原始 XMP 数据包
+    pub xmp_packet: Option<String>,
+    /// 原始 IPTC-IIM 数据
+    pub iptc: Option<Vec<u8>>,
+    /// PNG 文本数据块（tEXt / zTXt / iTXt）中的关键字和文本
+    pub png_text: Vec<PngTextEntry>,
+    /// PNG 辅助数据块（pHYs、tIME、iCCP、APNG 等），非 PNG 文件为 None
+    pub png_ancillary: Option<PngAncillary>,
+    /// 读取 PNG 时因 CRC 错误或无法解码而跳过的数据块
+    pub png_issues: Vec<PngIssue>,
+    /// 容器信息
+    pub container: Option<ContainerInfo>,
+    /// 按厂商解码的 MakerNote，厂商或格式不支持时为 None
+    pub maker_note: Option<MakerNote>,
+    /// Live Photo 配对信息，MakerNote 中没有 ContentIdentifier 时为 None；
+    /// 配套视频需要用 [`LivePhotoIndex`] 单独查找
+    pub live_photo: Option<LivePhoto>,
+    /// 由 EXIF 标签推算出的复合标签（GPS 十进制坐标、曝光值、等效焦距、景深等）
+    pub composite_tags: Vec<CompositeTag>,
+}
+
+/// 读取图片的元数据
+///
+/// 只读取该文件本身，Live Photo 的配套视频由 [`LivePhotoIndex`] 单独配对
+///
+/// # 参数
+/// - `path`: 图片文件路径
+///
+/// # 返回值
+/// - 成功时返回 ImageMetadata，文件无法读取或格式不支持时返回 Error
+pub fn read_metadata(path: impl AsRef<Path>) -> Result<ImageMetadata, Error> {
+    let mut meta = read_container_metadata(path.as_ref())?;
+    // Google 相机没有 IFD 格式的 MakerNote，厂商信息在 XMP 中
+    meta.maker_note = decode_maker_note(&meta).or_else(|| google::decode_google_camera_xmp(&meta.xmp_properties));
+    meta.live_photo = meta.maker_note.as_ref().and_then(|n| n.find("ContentIdentifier")).map(|tag| LivePhoto {
+        content_identifier: tag.value.clone(),
+        video: None,
+    });
+    meta.composite_tags = composite::compute_composite_tags(&meta.exif_tags, &meta.endian);
+    Ok(meta)
+}
+
+/// 按文件格式读取 EXIF、XMP 和容器信息
+fn read_container_metadata(path: &Path) -> Result<ImageMetadata, Error> {
+
+    // 检查文件是否存在且为普通文件
+    if !path.is_file() {
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{}: {}", locale::tr("not found or not a file", "文件不存在或不是文件", "ファイルが存在しないか通常のファイルではありません"), path.display()),
+        )));
+    }
+
+    // 获取文件扩展名并转换为小写
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if !SUPPORTED_FORMATS.contains(&extension.as_str()) {
+        return Err(Error::UnsupportedFormat(extension));
+    }
+
+    let mut meta = ImageMetadata {
+        path: path.to_path_buf(),
+        format: extension.clone(),
+        source: None,
+        endian: Endian::Little,
+        exif_tags: Vec::new(),
+        xmp_properties: Vec::new(),
+        xmp_packet: None,
+        iptc: None,
+        png_text: Vec::new(),
+        png_ancillary: None,
+        png_issues: Vec::new(),
+        container: None,
+        maker_note: None,
+        live_photo: None,
+        composite_tags: Vec::new(),
+    };
+
+    // PNG 文件读取 IHDR 信息
+    if extension == "png" {
+        meta.container = png::read_png_ihdr(path).map(ContainerInfo::Png);
+    }
+
+    // WebP 由 RIFF 数据块解析处理，同时读取 EXIF 和 XMP
+    if extension == "webp" {
+        let chunks = webp::extract_metadata_from_webp(path)?;
+        apply_embedded_metadata(
+            &mut meta,
+            chunks.exif,
+            chunks.xmp,
+            MetadataSource::WebpExif,
+            MetadataSource::WebpXmp,
+        )?;
+        meta.container = Some(ContainerInfo::Webp(chunks.info));
+        return Ok(meta);
+    }
+
+    // JPEG XL 由盒子解析处理，支持 brob 压缩的 Exif / xml 盒子
+    if extension == "jxl" {
+        let chunks = jxl::extract_metadata_from_jxl(path)?;
+        apply_embedded_metadata(
+            &mut meta,
+            chunks.exif,
+            chunks.xmp,
+            MetadataSource::JxlExif,
+            MetadataSource::JxlXmp,
+        )?;
+        meta.container = Some(ContainerInfo::Jxl(chunks.info));
+        return Ok(meta);
+    }
+
+    // HEIC / HEIF / AVIF 由 ISOBMFF 项目解析处理
+    if matches!(extension.as_str(), "heic" | "heif" | "hif" | "avif") {
+        let chunks = heif::extract_metadata_from_heif(path)?;
+        apply_embedded_metadata(
+            &mut meta,
+            chunks.exif,
+            chunks.xmp,
+            MetadataSource::HeifExif,
+            MetadataSource::HeifXmp,
+        )?;
+        meta.container = Some(ContainerInfo::Heif(chunks.info));
+        return Ok(meta);
+    }
+
+    // TIFF / DNG 本身就是 EXIF 的存储结构，直接遍历全部 IFD
+    if matches!(extension.as_str(), "tif" | "tiff" | "dng") {
+        let data = std::fs::read(path)?;
+        let info = tiff::parse_tiff(&data)?;
+        apply_tiff_info(&mut meta, info)?;
+        return Ok(meta);
+    }
+
+    // 相机 RAW 文件：基于 TIFF 的格式遍历 IFD，RAF / CR3 先从容器中找到 TIFF 结构
+    if matches!(
+        extension.as_str(),
+        "nef" | "nrw" | "cr2" | "cr3" | "arw" | "sr2" | "orf" | "rw2" | "pef" | "raf"
+    ) {
+        let chunks = raw::extract_metadata_from_raw(path, &extension)?;
+        if let Some(info) = &chunks.info.tiff {
+            meta.endian = info.endian.clone();
+            meta.exif_tags = info.primary_tags();
+            meta.source = Some(MetadataSource::Raw);
+        }
+        apply_embedded_metadata(&mut meta, None, chunks.xmp, MetadataSource::Raw, MetadataSource::RawXmp)?;
+        meta.container = Some(ContainerInfo::Raw(chunks.info));
+        return Ok(meta);
+    }
+
+    // 其余非 PNG 文件使用 little_exif 库直接读取 EXIF 元数据
+    // PNG 始终由下面的数据块解析处理：它能给出更具体的错误，
+    // 并且 little_exif 遇到损坏的 Raw profile 数据会直接 panic
+    if extension != "png" {
+        // 确认扩展名可以转换为库支持的文件类型枚举
+        FileExtension::from_str(&extension)
+            .map_err(|_| Error::UnsupportedFormat(extension.clone()))?;
+        match Metadata::new_from_path(path) {
+            Ok(metadata) => {
+                meta.endian = metadata.get_endian();
+                meta.exif_tags = metadata.into_iter().cloned().collect();
+                meta.source = Some(MetadataSource::Exif);
+            }
+            Err(e) => {
+                if let Some(err) = classify_little_exif_error(e) {
+                    return Err(err);
+                }
+            }
+        }
+        if matches!(extension.as_str(), "jpg" | "jpeg") {
+            read_jpeg_xmp(&mut meta)?;
+        }
+        return Ok(meta);
+    }
+
+    // 从 PNG 数据块中提取 EXIF 和 XMP
+    let chunks = png::extract_metadata_from_png(path)?;
+    meta.iptc = chunks.iptc;
+    apply_embedded_metadata(
+        &mut meta,
+        chunks.exif,
+        chunks.xmp,
+        MetadataSource::PngExif,
+        MetadataSource::PngXmp,
+    )?;
+
+    // PNG 文本关键字映射出的标签只补充 EXIF / XMP 中没有的
+    let text_tags = png::png_text_to_exif_tags(&chunks.text, &meta.endian);
+    if !text_tags.is_empty() && meta.source.is_none() {
+        meta.source = Some(MetadataSource::PngText);
+    }
+    for tag in text_tags {
+        if tags::find_tag(&meta.exif_tags, &tag).is_none() {
+            meta.exif_tags.push(tag);
+        }
+    }
+    meta.png_text = chunks.text;
+    meta.png_ancillary = Some(chunks.ancillary);
+    meta.png_issues = chunks.issues;
+
+    Ok(meta)
+}
+
+/// 解析容器中找到的 EXIF（TIFF 结构）和 XMP 数据包，写入 ImageMetadata
+///
+/// # 参数
+/// - `meta`: 要填充的元数据
+/// - `exif`: 不带 "Exif\0\0" 头的 TIFF 结构
+/// - `xmp`: XMP 数据包
+/// - `exif_source` / `xmp_source`: 标签分别来自 EXIF 和 XMP 时记录的来源
+///
+/// # 返回值
+/// - EXIF 或 XMP 数据损坏时返回 Error
+fn apply_embedded_metadata(
+    meta: &mut ImageMetadata,
+    exif: Option<Vec<u8>>,
+    xmp: Option<Vec<u8>>,
+    exif_source: MetadataSource,
+    xmp_source: MetadataSource,
+) -> Result<(), Error> {
+    if let Some(exif_bytes) = exif {
+        let metadata = Metadata::new_from_vec(&exif_bytes, FileExtension::TIFF)
+            .map_err(|e| Error::MalformedTiff(e.to_string()))?;
+        meta.endian = metadata.get_endian(); // 获取字节序（大端或小端）
+        meta.exif_tags = metadata.into_iter().cloned().collect();
+        meta.source = Some(exif_source);
+    }
+
+    // 直接在内存中解析 XMP 数据包
+    if let Some(xmp_bytes) = xmp {
+        let packet = String::from_utf8_lossy(&xmp_bytes).into_owned();
+        let properties = xmp::parse_xmp_properties(&packet)?;
+        // 没有 EXIF 时才用 XMP 映射出的标签
+        if meta.source.is_none() {
+            meta.exif_tags = xmp::xmp_properties_to_exif_tags(&properties);
+            meta.source = Some(xmp_source);
+        }
+        meta.xmp_properties = properties;
+        meta.xmp_packet = Some(packet);
+    }
+    Ok(())
+}
+
+/// 读取 JPEG APP1 段中的 XMP 数据包（little_exif 只读取 EXIF）
+///
+/// XMP 只用于补充厂商信息（如 Google 相机的 GCamera 属性），损坏时忽略，不影响 EXIF 的结果
+fn read_jpeg_xmp(meta: &mut ImageMetadata) -> Result<(), Error> {
+    let data = std::fs::read(&meta.path)?;
+    let (_, xmp) = raw::find_jpeg_app1(&data);
+    if let Some(xmp_bytes) = xmp {
+        let packet = String::from_utf8_lossy(xmp_bytes).trim_end_matches('\0').to_string();
+        if let Ok(properties) = xmp::parse_xmp_properties(&packet) {
+            meta.xmp_properties = properties;
+            meta.xmp_packet = Some(packet);
+        }
+    }
+    Ok(())
+}
+
+/// 将解析出的 TIFF 结构写入 ImageMetadata：主图像标签作为 EXIF，IFD0 中的 XMP（0x02BC）一并解析
+fn apply_tiff_info(meta: &mut ImageMetadata, info: TiffInfo) -> Result<(), Error> {
+    meta.endian = info.endian.clone();
+    meta.exif_tags = info.primary_tags();
+    meta.source = Some(MetadataSource::Tiff);
+
+    if let Some(xmp_bytes) = info.xmp_packet() {
+        let packet = String::from_utf8_lossy(&xmp_bytes).trim_end_matches('\0').to_string();
+        meta.xmp_properties = xmp::parse_xmp_properties(&packet)?;
+        meta.xmp_packet = Some(packet);
+    }
+    meta.container = Some(ContainerInfo::Tiff(info));
+    Ok(())
+}
+
+/// 找到 MakerNote 数据并按厂商解码
+///
+/// RAW 文件使用 RAW 解析时记录的 MakerNote（CR3 的 CMT3 不在 EXIF 标签中），
+/// 其余格式使用 EXIF 中的 MakerNote 标签
+fn decode_maker_note(meta: &ImageMetadata) -> Option<MakerNote> {
+    let text = |id: u16| {
+        meta.exif_tags.iter().find(|t| t.as_u16() == id).and_then(|t| match t {
+            ExifTag::Make(s) | ExifTag::Model(s) => Some(s.trim_end_matches('\0').trim()),
+            _ => None,
+        })
+    };
+    let (data, tiff_offset) = match &meta.container {
+        Some(ContainerInfo::Raw(RawInfo { maker_note: Some(block), .. })) => (block.data.as_slice(), Some(block.tiff_offset)),
+        container => {
+            let data = meta.exif_tags.iter().find_map(|t| match t {
+                ExifTag::MakerNote(v) => Some(v.as_slice()),
+                _ => None,
+            })?;
+            // TIFF / DNG 知道 MakerNote 在 TIFF 中的偏移，little_exif 读取的 EXIF 不知道
+            let offset = match container {
+                Some(ContainerInfo::Tiff(info)) => info
+                    .ifd("ExifIFD")
+                    .and_then(|ifd| ifd.find(raw::TAG_MAKER_NOTE))
+                    .map(|e| e.value_offset as u64),
+                _ => None,
+            };
+            (data, offset)
+        }
+    };
+    let ctx = makernote::MakerNoteContext {
+        make: text(0x010f),
+        model: text(0x0110),
+        tiff_offset,
+        endian: meta.endian.clone(),
+    };
+    makernote::decode_maker_note(data, &ctx)
+}
+
+/// 将 little_exif 返回的 I/O 错误归类
+///
+/// # 返回值
+/// - 文件中没有元数据时返回 None，其余情况返回对应的 Error
+fn classify_little_exif_error(e: std::io::Error) -> Option<Error> {
+    let msg = e.to_string();
+    match e.kind() {
+        std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => Some(Error::Io(e)),
+        std::io::ErrorKind::UnexpectedEof => Some(Error::Io(e)),
+        _ if msg.contains("signature") => Some(Error::UnsupportedFormat(msg)),
+        // "No EXIF data found!"、"No metadata found!" 等表示文件本身没有 EXIF
+        _ if msg.starts_with("No ") => None,
+        _ => Some(Error::MalformedTiff(msg)),
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 标签名称和值的显示语言
+///
+/// 只影响面向人的文本；JSON 中的 `name` 等机器键始终是英文规范名称
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 英语，未指定语言或语言不受支持时使用
+    #[default]
+    En,
+    /// 简体中文
+    ZhCn,
+    /// 日语
+    Ja,
+}
+
+/// 当前语言，进程内全局生效
+static CURRENT: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+impl Locale {
+    /// 解析语言代码
+    ///
+    /// 接受 "en"、"zh-CN"、"ja" 以及 "zh_CN.UTF-8"、"ja_JP.eucJP@euro" 这类 POSIX 区域设置，
+    /// 不区分大小写；"C" 和 "POSIX" 视为英语
+    ///
+    /// # 返回值
+    /// - 对应的语言，不支持时返回 None
+    pub fn parse(code: &str) -> Option<Locale> {
+        let code = code.split(['.', '@']).next().unwrap_or_default().to_ascii_lowercase().replace('_', "-");
+        let language = code.split('-').next().unwrap_or_default();
+        match language {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "zh" => Some(Locale::ZhCn),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// 按 LC_ALL、LC_MESSAGES、LANG 的顺序取第一个非空的环境变量确定语言
+    ///
+    /// # 返回值
+    /// - 对应的语言，环境变量都未设置或不受支持时为英语
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// BCP 47 语言代码
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::ZhCn => "zh-CN",
+            Locale::Ja => "ja",
+        }
+    }
+}
+
+/// 设置当前语言
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+/// 获取当前语言
+pub fn current_locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::ZhCn,
+        2 => Locale::Ja,
+        _ => Locale::En,
+    }
+}
+
+/// 按当前语言选择文本
+///
+/// # 参数
+/// - `en`: 英文
+/// - `zh`: 简体中文
+/// - `ja`: 日文
+pub fn tr(en: &'static str, zh: &'static str, ja: &'static str) -> &'static str {
+    match current_locale() {
+        Locale::En => en,
+        Locale::ZhCn => zh,
+        Locale::Ja => ja,
+    }
+}
@@ -0,0 +1,363 @@
+use little_exif::endian::Endian;
+
+use crate::{
+    apple, canon, fujifilm,
+    locale::tr,
+    nikon, olympus, samsung, sony,
+};
+
+/// MakerNote 头部签名与对应的厂商
+const MAKER_NOTE_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"Nikon\0", "Nikon"),
+    (b"OLYMPUS\0", "Olympus"),
+    (b"OLYMP\0", "Olympus"),
+    (b"OM SYSTEM", "Olympus"),
+    (b"FUJIFILM", "Fujifilm"),
+    (b"SONY", "Sony"),
+    (b"Panasonic\0", "Panasonic"),
+    (b"AOC\0", "Pentax"),
+    (b"PENTAX ", "Pentax"),
+    (b"Apple iOS\0", "Apple"),
+    (b"LEICA", "Leica"),
+];
+
+/// 解码后的一个 MakerNote 标签
+#[derive(Debug, Clone, PartialEq)]
+pub struct MakerNoteTag {
+    /// 标签编号；从子结构（如 ShotInfo、LensData）中解出的字段使用所在标签的编号
+    pub tag_id: u16,
+    /// 英文规范名称，如 "ShutterCount"
+    pub name: &'static str,
+    /// 当前语言下的名称
+    pub label: &'static str,
+    /// 格式化后的值
+    pub value: String,
+}
+
+/// 解码后的 MakerNote
+#[derive(Debug, Clone, PartialEq)]
+pub struct MakerNote {
+    /// 厂商，如 "Nikon"
+    pub vendor: &'static str,
+    /// MakerNote 格式，如 "Nikon Type 3"
+    pub format: String,
+    /// 按文件中的顺序列出的标签
+    pub tags: Vec<MakerNoteTag>,
+}
+
+impl MakerNote {
+    pub(crate) fn new(vendor: &'static str, format: &str) -> Self {
+        MakerNote { vendor, format: format.to_string(), tags: Vec::new() }
+    }
+
+    /// 按英文名称查找标签
+    pub fn find(&self, name: &str) -> Option<&MakerNoteTag> {
+        self.tags.iter().find(|t| t.name == name)
+    }
+
+    pub(crate) fn push(&mut self, tag_id: u16, name: &'static str, label: &'static str, value: impl Into<String>) {
+        self.tags.push(MakerNoteTag { tag_id, name, label, value: value.into() });
+    }
+}
+
+/// 解码 MakerNote 时需要的 EXIF 信息
+#[derive(Debug, Clone)]
+pub struct MakerNoteContext<'a> {
+    /// Make 标签
+    pub make: Option<&'a str>,
+    /// Model 标签
+    pub model: Option<&'a str>,
+    /// MakerNote 在 TIFF 数据中的偏移，未知时为 None（部分厂商 MakerNote 内的偏移相对于 TIFF 头）
+    pub tiff_offset: Option<u64>,
+    /// 外层 TIFF 的字节序
+    pub endian: Endian,
+}
+
+/// 根据 MakerNote 头部签名判断厂商，没有签名时（如 Canon）根据 Make 标签判断
+///
+/// # 参数
+/// - `data`: MakerNote 数据
+/// - `make`: Make 标签的值
+pub fn maker_note_vendor(data: &[u8], make: Option<&str>) -> Option<&'static str> {
+    if let Some((_, vendor)) = MAKER_NOTE_SIGNATURES.iter().find(|(sig, _)| data.starts_with(sig)) {
+        return Some(vendor);
+    }
+    let make = make?.to_lowercase();
+    [
+        ("canon", "Canon"),
+        ("nikon", "Nikon"),
+        ("sony", "Sony"),
+        ("fujifilm", "Fujifilm"),
+        ("olympus", "Olympus"),
+        ("om digital", "Olympus"),
+        ("panasonic", "Panasonic"),
+        ("pentax", "Pentax"),
+        ("ricoh", "Pentax"),
+        ("apple", "Apple"),
+        ("samsung", "Samsung"),
+    ]
+    .iter()
+    .find(|(prefix, _)| make.starts_with(prefix))
+    .map(|(_, vendor)| *vendor)
+}
+
+/// 按厂商解码 MakerNote
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: 相机厂商、型号等 EXIF 信息
+///
+/// # 返回值
+/// - 解码出的标签；厂商未知、格式不支持或数据损坏时返回 None
+pub fn decode_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    match maker_note_vendor(data, ctx.make)? {
+        "Nikon" => nikon::decode_nikon_maker_note(data, ctx),
+        "Canon" => canon::decode_canon_maker_note(data, ctx),
+        "Sony" => sony::decode_sony_maker_note(data, ctx),
+        "Fujifilm" => fujifilm::decode_fujifilm_maker_note(data, ctx),
+        "Olympus" => olympus::decode_olympus_maker_note(data, ctx),
+        "Apple" => apple::decode_apple_maker_note(data, ctx),
+        "Samsung" => samsung::decode_samsung_maker_note(data, ctx),
+        _ => None,
+    }
+}
+
+/// 按字节序读取 16 位无符号整数
+pub(crate) fn u16_at(data: &[u8], pos: usize, endian: &Endian) -> Option<u16> {
+    let b = data.get(pos..pos.checked_add(2)?)?;
+    Some(match endian {
+        Endian::Big => u16::from_be_bytes([b[0], b[1]]),
+        Endian::Little => u16::from_le_bytes([b[0], b[1]]),
+    })
+}
+
+/// 按字节序读取 32 位无符号整数
+pub(crate) fn u32_at(data: &[u8], pos: usize, endian: &Endian) -> Option<u32> {
+    let b = data.get(pos..pos.checked_add(4)?)?;
+    Some(match endian {
+        Endian::Big => u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+        Endian::Little => u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+    })
+}
+
+/// 读取以 0 结尾的 ASCII 字符串，去掉首尾空白
+pub(crate) fn c_string(data: &[u8]) -> String {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).trim().to_string()
+}
+
+/// MakerNote IFD 中的一个条目
+pub(crate) struct IfdEntry<'a> {
+    pub tag: u16,
+    pub type_code: u16,
+    /// 条目的值（已按偏移定位）
+    pub data: &'a [u8],
+    /// 条目中值或偏移字段的原始内容（值不超过 4 字节时为值本身）
+    pub value_offset: u32,
+    endian: Endian,
+}
+
+impl IfdEntry<'_> {
+    pub fn u8s(&self) -> &[u8] {
+        self.data
+    }
+
+    pub fn u16s(&self) -> Vec<u16> {
+        (0..self.data.len() / 2).filter_map(|i| u16_at(self.data, i * 2, &self.endian)).collect()
+    }
+
+    pub fn i16s(&self) -> Vec<i16> {
+        self.u16s().into_iter().map(|v| v as i16).collect()
+    }
+
+    pub fn u32s(&self) -> Vec<u32> {
+        (0..self.data.len() / 4).filter_map(|i| u32_at(self.data, i * 4, &self.endian)).collect()
+    }
+
+    /// 无符号有理数（分子, 分母）
+    pub fn rationals(&self) -> Vec<(u32, u32)> {
+        self.u32s().chunks_exact(2).map(|c| (c[0], c[1])).collect()
+    }
+
+    /// 有符号有理数（分子, 分母）
+    pub fn srationals(&self) -> Vec<(i32, i32)> {
+        self.u32s().chunks_exact(2).map(|c| (c[0] as i32, c[1] as i32)).collect()
+    }
+
+    /// 第一个整数值（BYTE、SHORT、LONG 及其有符号形式）
+    pub fn first_int(&self) -> Option<i64> {
+        match self.type_code {
+            1 | 7 => self.data.first().map(|&v| v as i64),
+            6 => self.data.first().map(|&v| v as i8 as i64),
+            3 => self.u16s().first().map(|&v| v as i64),
+            8 => self.i16s().first().map(|&v| v as i64),
+            4 | 13 => self.u32s().first().map(|&v| v as i64),
+            9 => self.u32s().first().map(|&v| v as i32 as i64),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        c_string(self.data)
+    }
+}
+
+/// 读取 MakerNote 中的一个 IFD
+///
+/// # 参数
+/// - `data`: MakerNote（或其内嵌 TIFF）数据
+/// - `offset`: IFD 在 data 中的位置
+/// - `endian`: 字节序
+/// - `offset_base`: 条目中的偏移减去该值后才是在 data 中的位置（偏移相对于外层 TIFF 头时为 MakerNote 在 TIFF 中的偏移）
+///
+/// # 返回值
+/// - 条目列表，超出范围的条目被跳过；IFD 本身不完整时返回 None
+pub(crate) fn read_ifd<'a>(data: &'a [u8], offset: usize, endian: &Endian, offset_base: i64) -> Option<Vec<IfdEntry<'a>>> {
+    let count = u16_at(data, offset, endian)? as usize;
+    // MakerNote 的 IFD 通常只有几十个条目，过大说明偏移或字节序不对
+    if count == 0 || count > 512 || data.len() < offset + 2 + count * 12 {
+        return None;
+    }
+    let mut entries = Vec::new();
+    for i in 0..count {
+        let pos = offset + 2 + i * 12;
+        let tag = u16_at(data, pos, endian)?;
+        let type_code = u16_at(data, pos + 2, endian)?;
+        let count = u32_at(data, pos + 4, endian)?;
+        let size = match type_code {
+            1 | 2 | 6 | 7 => 1usize,
+            3 | 8 => 2,
+            4 | 9 | 11 | 13 => 4,
+            5 | 10 | 12 => 8,
+            _ => continue,
+        };
+        let Some(len) = size.checked_mul(count as usize) else { continue };
+        let value_offset = u32_at(data, pos + 8, endian)?;
+        let value = if len <= 4 {
+            data.get(pos + 8..pos + 8 + len)
+        } else {
+            let start = value_offset as i64 - offset_base;
+            usize::try_from(start).ok().and_then(|s| data.get(s..s.checked_add(len)?))
+        };
+        if let Some(value) = value {
+            entries.push(IfdEntry { tag, type_code, data: value, value_offset, endian: endian.clone() });
+        }
+    }
+    Some(entries)
+}
+
+/// 推测 MakerNote 内偏移的基准
+///
+/// Canon、Sony 等 MakerNote 中的偏移相对于外层 TIFF 头；不知道 MakerNote 在 TIFF 中的位置时，
+/// 假定第一个不在条目中的值紧跟在 IFD（含 4 字节的下一个 IFD 偏移）之后
+///
+/// # 返回值
+/// - 应传给 read_ifd 的 offset_base；IFD 损坏或所有值都在条目中时返回 None
+pub(crate) fn guess_offset_base(data: &[u8], offset: usize, endian: &Endian) -> Option<i64> {
+    let count = u16_at(data, offset, endian)? as usize;
+    let ifd_end = offset + 2 + count * 12 + 4;
+    let min = (0..count)
+        .filter_map(|i| {
+            let pos = offset + 2 + i * 12;
+            let size: u64 = match u16_at(data, pos + 2, endian)? {
+                1 | 2 | 6 | 7 => 1,
+                3 | 8 => 2,
+                4 | 9 | 11 | 13 => 4,
+                5 | 10 | 12 => 8,
+                _ => return None,
+            };
+            (size * u32_at(data, pos + 4, endian)? as u64 > 4).then(|| u32_at(data, pos + 8, endian))?
+        })
+        .min()?;
+    Some(min as i64 - ifd_end as i64)
+}
+
+/// 无法识别的枚举值
+pub(crate) fn unknown_value(v: impl std::fmt::Display) -> String {
+    format!("{} ({})", tr("Unknown", "未知", "不明"), v)
+}
+
+/// 格式化光圈值
+pub(crate) fn format_f_number(f: f64) -> String {
+    format!("f/{:.1}", f).replace(".0", "")
+}
+
+/// 格式化曝光时间（秒）
+pub(crate) fn format_exposure_time(t: f64) -> String {
+    if t > 0.0 && t < 0.25 {
+        format!("1/{} {}", (1.0 / t).round(), tr("s", "秒", "秒"))
+    } else {
+        format!("{:.1} {}", t, tr("s", "秒", "秒")).replace(".0 ", " ")
+    }
+}
+
+/// 格式化焦距（毫米）
+pub(crate) fn format_mm(mm: f64) -> String {
+    format!("{:.1} mm", mm).replace(".0 ", " ")
+}
+
+/// 各厂商 MakerNote 测试共用的构造工具
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::*;
+
+    /// 按字节序编码 SHORT 数组
+    pub(crate) fn u16s(values: &[u16], endian: &Endian) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| match endian {
+                Endian::Big => v.to_be_bytes(),
+                Endian::Little => v.to_le_bytes(),
+            })
+            .collect()
+    }
+
+    /// 按字节序编码 LONG 数组
+    pub(crate) fn u32s(values: &[u32], endian: &Endian) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| match endian {
+                Endian::Big => v.to_be_bytes(),
+                Endian::Little => v.to_le_bytes(),
+            })
+            .collect()
+    }
+
+    /// 构造一个 IFD，超过 4 字节的值依次放在 IFD 之后
+    ///
+    /// # 参数
+    /// - `entries`: (标签, 类型, 已按字节序编码的值)
+    /// - `start`: IFD 相对于偏移基准的位置，值的偏移按此计算
+    /// - `endian`: 字节序
+    pub(crate) fn build_ifd(entries: &[(u16, u16, Vec<u8>)], start: usize, endian: &Endian) -> Vec<u8> {
+        let mut ifd = u16s(&[entries.len() as u16], endian);
+        let mut values: Vec<u8> = Vec::new();
+        let values_start = start + 2 + entries.len() * 12 + 4;
+        for (tag, type_code, data) in entries {
+            let size = match type_code {
+                3 | 8 => 2,
+                4 | 9 | 11 | 13 => 4,
+                5 | 10 | 12 => 8,
+                _ => 1,
+            };
+            ifd.extend(u16s(&[*tag, *type_code], endian));
+            ifd.extend(u32s(&[(data.len() / size) as u32], endian));
+            if data.len() <= 4 {
+                let mut field = data.clone();
+                field.resize(4, 0);
+                ifd.extend(field);
+            } else {
+                ifd.extend(u32s(&[(values_start + values.len()) as u32], endian));
+                values.extend(data);
+            }
+        }
+        ifd.extend([0; 4]);
+        ifd.extend(values);
+        ifd
+    }
+
+    /// 按名称取解码后的值，缺少时 panic
+    pub(crate) fn value<'a>(note: &'a MakerNote, name: &str) -> &'a str {
+        &note.find(name).unwrap_or_else(|| panic!("缺少 {}", name)).value
+    }
+}
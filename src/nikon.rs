@@ -0,0 +1,549 @@
+use crate::{
+    locale::tr,
+    makernote::{c_string, format_f_number, format_mm, read_ifd, u32_at, unknown_value, IfdEntry, MakerNote, MakerNoteContext},
+    tiff::detect_endian,
+};
+
+/// 解密 ShotInfo / LensData 等数据块用的两张替换表，分别由序列号和快门次数索引
+const XLAT: [[u8; 256]; 2] = [
+    [
+        0xc1, 0xbf, 0x6d, 0x0d, 0x59, 0xc5, 0x13, 0x9d, 0x83, 0x61, 0x6b, 0x4f, 0xc7, 0x7f, 0x3d, 0x3d,
+        0x53, 0x59, 0xe3, 0xc7, 0xe9, 0x2f, 0x95, 0xa7, 0x95, 0x1f, 0xdf, 0x7f, 0x2b, 0x29, 0xc7, 0x0d,
+        0xdf, 0x07, 0xef, 0x71, 0x89, 0x3d, 0x13, 0x3d, 0x3b, 0x13, 0xfb, 0x0d, 0x89, 0xc1, 0x65, 0x1f,
+        0xb3, 0x0d, 0x6b, 0x29, 0xe3, 0xfb, 0xef, 0xa3, 0x6b, 0x47, 0x7f, 0x95, 0x35, 0xa7, 0x47, 0x4f,
+        0xc7, 0xf1, 0x59, 0x95, 0x35, 0x11, 0x29, 0x61, 0xf1, 0x3d, 0xb3, 0x2b, 0x0d, 0x43, 0x89, 0xc1,
+        0x9d, 0x9d, 0x89, 0x65, 0xf1, 0xe9, 0xdf, 0xbf, 0x3d, 0x7f, 0x53, 0x97, 0xe5, 0xe9, 0x95, 0x17,
+        0x1d, 0x3d, 0x8b, 0xfb, 0xc7, 0xe3, 0x67, 0xa7, 0x07, 0xf1, 0x71, 0xa7, 0x53, 0xb5, 0x29, 0x89,
+        0xe5, 0x2b, 0xa7, 0x17, 0x29, 0xe9, 0x4f, 0xc5, 0x65, 0x6d, 0x6b, 0xef, 0x0d, 0x89, 0x49, 0x2f,
+        0xb3, 0x43, 0x53, 0x65, 0x1d, 0x49, 0xa3, 0x13, 0x89, 0x59, 0xef, 0x6b, 0xef, 0x65, 0x1d, 0x0b,
+        0x59, 0x13, 0xe3, 0x4f, 0x9d, 0xb3, 0x29, 0x43, 0x2b, 0x07, 0x1d, 0x95, 0x59, 0x59, 0x47, 0xfb,
+        0xe5, 0xe9, 0x61, 0x47, 0x2f, 0x35, 0x7f, 0x17, 0x7f, 0xef, 0x7f, 0x95, 0x95, 0x71, 0xd3, 0xa3,
+        0x0b, 0x71, 0xa3, 0xad, 0x0b, 0x3b, 0xb5, 0xfb, 0xa3, 0xbf, 0x4f, 0x83, 0x1d, 0xad, 0xe9, 0x2f,
+        0x71, 0x65, 0xa3, 0xe5, 0x07, 0x35, 0x3d, 0x0d, 0xb5, 0xe9, 0xe5, 0x47, 0x3b, 0x9d, 0xef, 0x35,
+        0xa3, 0xbf, 0xb3, 0xdf, 0x53, 0xd3, 0x97, 0x53, 0x49, 0x71, 0x07, 0x35, 0x61, 0x71, 0x2f, 0x43,
+        0x2f, 0x11, 0xdf, 0x17, 0x97, 0xfb, 0x95, 0x3b, 0x7f, 0x6b, 0xd3, 0x25, 0xbf, 0xad, 0xc7, 0xc5,
+        0xc5, 0xb5, 0x8b, 0xef, 0x2f, 0xd3, 0x07, 0x6b, 0x25, 0x49, 0x95, 0x25, 0x49, 0x6d, 0x71, 0xc7,
+    ],
+    [
+        0xa7, 0xbc, 0xc9, 0xad, 0x91, 0xdf, 0x85, 0xe5, 0xd4, 0x78, 0xd5, 0x17, 0x46, 0x7c, 0x29, 0x4c,
+        0x4d, 0x03, 0xe9, 0x25, 0x68, 0x11, 0x86, 0xb3, 0xbd, 0xf7, 0x6f, 0x61, 0x22, 0xa2, 0x26, 0x34,
+        0x2a, 0xbe, 0x1e, 0x46, 0x14, 0x68, 0x9d, 0x44, 0x18, 0xc2, 0x40, 0xf4, 0x7e, 0x5f, 0x1b, 0xad,
+        0x0b, 0x94, 0xb6, 0x67, 0xb4, 0x0b, 0xe1, 0xea, 0x95, 0x9c, 0x66, 0xdc, 0xe7, 0x5d, 0x6c, 0x05,
+        0xda, 0xd5, 0xdf, 0x7a, 0xef, 0xf6, 0xdb, 0x1f, 0x82, 0x4c, 0xc0, 0x68, 0x47, 0xa1, 0xbd, 0xee,
+        0x39, 0x50, 0x56, 0x4a, 0xdd, 0xdf, 0xa5, 0xf8, 0xc6, 0xda, 0xca, 0x90, 0xca, 0x01, 0x42, 0x9d,
+        0x8b, 0x0c, 0x73, 0x43, 0x75, 0x05, 0x94, 0xde, 0x24, 0xb3, 0x80, 0x34, 0xe5, 0x2c, 0xdc, 0x9b,
+        0x3f, 0xca, 0x33, 0x45, 0xd0, 0xdb, 0x5f, 0xf5, 0x52, 0xc3, 0x21, 0xda, 0xe2, 0x22, 0x72, 0x6b,
+        0x3e, 0xd0, 0x5b, 0xa8, 0x87, 0x8c, 0x06, 0x5d, 0x0f, 0xdd, 0x09, 0x19, 0x93, 0xd0, 0xb9, 0xfc,
+        0x8b, 0x0f, 0x84, 0x60, 0x33, 0x1c, 0x9b, 0x45, 0xf1, 0xf0, 0xa3, 0x94, 0x3a, 0x12, 0x77, 0x33,
+        0x4d, 0x44, 0x78, 0x28, 0x3c, 0x9e, 0xfd, 0x65, 0x57, 0x16, 0x94, 0x6b, 0xfb, 0x59, 0xd0, 0xc8,
+        0x22, 0x36, 0xdb, 0xd2, 0x63, 0x98, 0x43, 0xa1, 0x04, 0x87, 0x86, 0xf7, 0xa6, 0x26, 0xbb, 0xd6,
+        0x59, 0x4d, 0xbf, 0x6a, 0x2e, 0xaa, 0x2b, 0xef, 0xe6, 0x78, 0xb6, 0x4e, 0xe0, 0x2f, 0xdc, 0x7c,
+        0xbe, 0x57, 0x19, 0x32, 0x7e, 0x2a, 0xd0, 0xb8, 0xba, 0x29, 0x00, 0x3c, 0x52, 0x7d, 0xa8, 0x49,
+        0x3b, 0x2d, 0xeb, 0x25, 0x49, 0xfa, 0xa3, 0xaa, 0x39, 0xa7, 0xc5, 0xa7, 0x50, 0x11, 0x36, 0xfb,
+        0xc6, 0x67, 0x4a, 0xf5, 0xa5, 0x12, 0x65, 0x7e, 0xb0, 0xdf, 0xaf, 0x4e, 0xb3, 0x61, 0x7f, 0x2f,
+    ],
+];
+
+/// Type 3 MakerNote 的头部长度："Nikon\0" + 2 字节版本 + 2 字节保留
+const HEADER_LEN: usize = 10;
+
+/// 解码 Nikon Type 3 MakerNote
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: 相机型号等 EXIF 信息（D50 的序列号密钥特殊）
+///
+/// # 返回值
+/// - 解码出的标签；不是 Type 3 格式或内嵌 TIFF 损坏时返回 None
+pub fn decode_nikon_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    if !data.starts_with(b"Nikon\0") || data.get(6) != Some(&0x02) {
+        return None;
+    }
+    // 内嵌 TIFF 中的偏移都相对于它自己的 TIFF 头
+    let tiff = data.get(HEADER_LEN..)?;
+    let endian = detect_endian(tiff)?;
+    let first = u32_at(tiff, 4, &endian)? as usize;
+    let entries = read_ifd(tiff, first, &endian, 0)?;
+
+    let serial = entries.iter().find(|e| e.tag == 0x001d).map(|e| e.text());
+    let shutter_count = entries.iter().find(|e| e.tag == 0x00a7).and_then(|e| e.u32s().first().copied());
+    let key = serial.as_deref().map(|s| serial_key(s, ctx.model)).zip(shutter_count);
+    let lens_type = entries.iter().find(|e| e.tag == 0x0083).and_then(|e| e.u8s().first().copied());
+
+    let mut note = MakerNote::new("Nikon", "Nikon Type 3");
+    for e in &entries {
+        decode_entry(&mut note, e, key, lens_type);
+    }
+    Some(note)
+}
+
+/// 序列号对应的密钥：纯数字序列号直接使用，否则 D50 为 0x22、其余机型为 0x60
+fn serial_key(serial: &str, model: Option<&str>) -> u32 {
+    match serial.parse::<u64>() {
+        Ok(n) => n as u32,
+        Err(_) if model.is_some_and(|m| m.trim().ends_with("D50")) => 0x22,
+        Err(_) => 0x60,
+    }
+}
+
+/// 用序列号和快门次数解密数据块，start 之前的字节（版本号）不加密
+fn decrypt(data: &[u8], start: usize, serial: u32, count: u32) -> Vec<u8> {
+    let key = count.to_le_bytes().iter().fold(0u8, |k, b| k ^ b);
+    let ci = XLAT[0][(serial & 0xff) as usize];
+    let mut cj = XLAT[1][key as usize];
+    let mut ck = 0x60u8;
+    let mut out = data.to_vec();
+    for b in out.iter_mut().skip(start) {
+        cj = cj.wrapping_add(ci.wrapping_mul(ck));
+        ck = ck.wrapping_add(1);
+        *b ^= cj;
+    }
+    out
+}
+
+/// 缺少解密所需的序列号或快门次数时显示的值
+fn undecryptable() -> &'static str {
+    tr(
+        "cannot decrypt without serial number and shutter count",
+        "缺少序列号或快门次数，无法解密",
+        "シリアル番号またはシャッター回数がないため復号できません",
+    )
+}
+
+/// 数据块开头的 4 字节 ASCII 版本号
+fn block_version(data: &[u8]) -> String {
+    String::from_utf8_lossy(&data[..4.min(data.len())]).to_string()
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry, key: Option<(u32, u32)>, lens_type: Option<u8>) {
+    let id = e.tag;
+    match id {
+        0x0001 => {
+            let v = block_version(e.u8s());
+            let value = match v.parse::<u32>() {
+                Ok(n) => format!("{}.{:02}", n / 100, n % 100),
+                Err(_) => v,
+            };
+            note.push(id, "MakerNoteVersion", tr("MakerNote Version", "MakerNote 版本", "MakerNote バージョン"), value);
+        }
+        0x0002 => {
+            if let Some(iso) = e.u16s().get(1).filter(|&&v| v > 0) {
+                note.push(id, "ISO", tr("ISO", "ISO 感光度", "ISO 感度"), iso.to_string());
+            }
+        }
+        0x0004 => note.push(id, "Quality", tr("Quality", "画质", "画質"), e.text()),
+        0x0005 => note.push(id, "WhiteBalance", tr("White Balance", "白平衡", "ホワイトバランス"), e.text()),
+        0x0006 => note.push(id, "Sharpness", tr("Sharpness", "锐度", "シャープネス"), e.text()),
+        0x0007 => note.push(id, "FocusMode", tr("Focus Mode", "对焦模式", "フォーカスモード"), focus_mode_name(&e.text())),
+        0x0008 => note.push(id, "FlashSetting", tr("Flash Setting", "闪光灯设置", "フラッシュ設定"), e.text()),
+        0x000b => {
+            if let Some(v) = e.i16s().first() {
+                note.push(id, "WhiteBalanceFineTune", tr("White Balance Fine Tune", "白平衡微调", "ホワイトバランス微調整"), format!("{:+}", v));
+            }
+        }
+        0x001d => note.push(id, "SerialNumber", tr("Serial Number", "机身序列号", "シリアル番号"), e.text()),
+        0x001e => {
+            let value = match e.first_int() {
+                Some(1) => "sRGB".to_string(),
+                Some(2) => "Adobe RGB".to_string(),
+                Some(4) => "BT.2100".to_string(),
+                v => unknown_value(v.unwrap_or_default()),
+            };
+            note.push(id, "ColorSpace", tr("Color Space", "色彩空间", "色空間"), value);
+        }
+        0x001f => decode_vr_info(note, e.u8s()),
+        0x0022 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "ActiveD-Lighting", tr("Active D-Lighting", "动态 D-Lighting", "アクティブ D-ライティング"), active_d_lighting_name(v));
+            }
+        }
+        0x0023 => decode_picture_control(note, e.u8s()),
+        0x0083 => {
+            if let Some(&v) = e.u8s().first() {
+                note.push(id, "LensType", tr("Lens Type", "镜头类型", "レンズタイプ"), lens_type_name(v));
+            }
+        }
+        0x0084 => {
+            if let Some(lens) = lens_spec(&e.rationals()) {
+                note.push(id, "Lens", tr("Lens", "镜头规格", "レンズ仕様"), lens);
+            }
+        }
+        0x0089 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "ShootingMode", tr("Shooting Mode", "拍摄模式", "撮影モード"), shooting_mode_name(v as u16));
+            }
+        }
+        0x008b => {
+            if let [a, b, c, ..] = e.u8s() {
+                if *c != 0 {
+                    let stops = *a as f64 * *b as f64 / *c as f64;
+                    note.push(id, "LensFStops", tr("Lens F-Stops", "镜头光圈档数", "レンズ絞り段数"), format!("{:.2}", stops));
+                }
+            }
+        }
+        0x0091 => decode_shot_info(note, e.u8s(), key),
+        0x0093 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "NEFCompression", tr("NEF Compression", "NEF 压缩", "NEF 圧縮"), nef_compression_name(v));
+            }
+        }
+        0x0095 => note.push(id, "NoiseReduction", tr("Noise Reduction", "降噪", "ノイズリダクション"), e.text()),
+        0x0098 => decode_lens_data(note, e.u8s(), key, lens_type),
+        0x00a7 => {
+            if let Some(count) = e.u32s().first() {
+                note.push(id, "ShutterCount", tr("Shutter Count", "快门次数", "シャッター回数"), count.to_string());
+            }
+        }
+        0x00b1 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0 => tr("Off", "关闭", "オフ"),
+                    1 => tr("Minimal", "最低", "最弱"),
+                    2 => tr("Low", "低", "弱"),
+                    3 => tr("Medium Low", "中低", "やや弱"),
+                    4 => tr("Normal", "标准", "標準"),
+                    5 => tr("Medium High", "中高", "やや強"),
+                    6 => tr("High", "高", "強"),
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(id, "HighISONoiseReduction", tr("High ISO Noise Reduction", "高 ISO 降噪", "高感度ノイズ低減"), value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 对焦模式字符串附上说明
+fn focus_mode_name(mode: &str) -> String {
+    let desc = match mode {
+        "AF-S" => tr("Single-servo AF", "单次伺服自动对焦", "シングル AF サーボ"),
+        "AF-C" => tr("Continuous-servo AF", "连续伺服自动对焦", "コンティニュアス AF サーボ"),
+        "AF-A" => tr("Auto-servo AF", "自动伺服自动对焦", "AF サーボモード自動切り換え"),
+        "AF-F" => tr("Full-time-servo AF", "全时伺服自动对焦", "常時 AF サーボ"),
+        "MANUAL" => tr("Manual focus", "手动对焦", "マニュアルフォーカス"),
+        _ => return mode.to_string(),
+    };
+    format!("{} ({})", mode, desc)
+}
+
+fn active_d_lighting_name(v: i64) -> String {
+    match v {
+        0 => tr("Off", "关闭", "しない").to_string(),
+        1 => tr("Low", "低", "弱め").to_string(),
+        3 => tr("Normal", "标准", "標準").to_string(),
+        5 => tr("High", "高", "強め").to_string(),
+        7 => tr("Extra High", "加强", "より強く").to_string(),
+        8..=11 => format!("{} {}", tr("Extra High", "加强", "より強く"), v - 7),
+        0xffff => tr("Auto", "自动", "オート").to_string(),
+        _ => unknown_value(v),
+    }
+}
+
+/// LensType 位标志：MF、D、G、VR、1 系列、FT-1、E（电磁光圈）、AF-P
+fn lens_type_name(v: u8) -> String {
+    let flags = ["MF", "D", "G", "VR", "1", "FT-1", "E", "AF-P"];
+    let names: Vec<&str> = flags.iter().enumerate().filter(|(i, _)| v & (1 << i) != 0).map(|(_, n)| *n).collect();
+    if names.is_empty() {
+        "AF".to_string()
+    } else {
+        names.join(" ")
+    }
+}
+
+/// Lens 标签：最短焦距、最长焦距、最短焦距最大光圈、最长焦距最大光圈
+fn lens_spec(values: &[(u32, u32)]) -> Option<String> {
+    let v: Vec<f64> = values.iter().take(4).map(|&(n, d)| if d == 0 { 0.0 } else { n as f64 / d as f64 }).collect();
+    if v.len() < 4 || v[0] == 0.0 {
+        return None;
+    }
+    let trim = |x: f64| format!("{:.1}", x).trim_end_matches(".0").to_string();
+    let focal = if v[0] == v[1] { trim(v[0]) } else { format!("{}-{}", trim(v[0]), trim(v[1])) };
+    let aperture = if v[2] == v[3] || v[3] == 0.0 { trim(v[2]) } else { format!("{}-{}", trim(v[2]), trim(v[3])) };
+    Some(format!("{}mm f/{}", focal, aperture))
+}
+
+fn shooting_mode_name(v: u16) -> String {
+    let flags = [
+        tr("Continuous", "连拍", "連続撮影"),
+        tr("Delay", "延时", "ディレイ"),
+        tr("PC Control", "PC 控制", "PC 制御"),
+        tr("Self-timer", "自拍", "セルフタイマー"),
+        tr("Exposure Bracketing", "包围曝光", "露出ブラケティング"),
+        tr("Auto ISO", "自动 ISO", "感度自動制御"),
+        tr("White Balance Bracketing", "白平衡包围", "ホワイトバランスブラケティング"),
+        tr("IR Control", "红外遥控", "赤外線リモコン"),
+        tr("D-Lighting Bracketing", "D-Lighting 包围", "D-ライティングブラケティング"),
+    ];
+    let names: Vec<&str> = flags.iter().enumerate().filter(|(i, _)| v & (1 << i) != 0).map(|(_, n)| *n).collect();
+    if names.is_empty() {
+        tr("Single-Frame", "单张", "1 コマ撮影").to_string()
+    } else {
+        names.join(tr(", ", "、", "、"))
+    }
+}
+
+fn nef_compression_name(v: i64) -> String {
+    match v {
+        1 => tr("Lossy (type 1)", "有损（类型 1）", "非可逆（タイプ 1）"),
+        2 => tr("Uncompressed", "无压缩", "非圧縮"),
+        3 => tr("Lossless", "无损", "可逆"),
+        4 => tr("Lossy (type 2)", "有损（类型 2）", "非可逆（タイプ 2）"),
+        5 => tr("Striped packed 12 bits", "条带打包 12 位", "ストライプ パック 12 ビット"),
+        6 => tr("Uncompressed (reduced to 12 bit)", "无压缩（精简 12 位）", "非圧縮（12 ビットに縮小）"),
+        7 => tr("Unpacked 12 bits", "未打包 12 位", "アンパック 12 ビット"),
+        8 => tr("Small", "小尺寸", "スモール"),
+        9 => tr("Packed 12 bits", "打包 12 位", "パック 12 ビット"),
+        10 => tr("Packed 14 bits", "打包 14 位", "パック 14 ビット"),
+        13 => tr("High Efficiency", "高效", "高効率"),
+        14 => tr("High Efficiency*", "高效*", "高効率*"),
+        _ => return unknown_value(v),
+    }
+    .to_string()
+}
+
+/// VRInfo：4 字节版本 + 防抖开关 + 1 字节保留 + 防抖模式
+fn decode_vr_info(note: &mut MakerNote, data: &[u8]) {
+    if let Some(&v) = data.get(4) {
+        let value = match v {
+            0 => tr("n/a", "不适用", "該当なし"),
+            1 => tr("On", "开启", "オン"),
+            2 => tr("Off", "关闭", "オフ"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(0x001f, "VibrationReduction", tr("Vibration Reduction", "减震 (VR)", "手ブレ補正 (VR)"), value);
+    }
+    if let Some(&v) = data.get(6) {
+        let value = match v {
+            0 => tr("Normal", "标准", "ノーマル"),
+            1 => tr("On (1)", "开启 (1)", "オン (1)"),
+            2 => tr("Active", "运动 (Active)", "アクティブ"),
+            3 => tr("Sport", "运动 (Sport)", "スポーツ"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(0x001f, "VRMode", tr("VR Mode", "减震模式", "手ブレ補正モード"), value);
+    }
+}
+
+/// 优化校准的调整值：0x80 为标准，0xFF 表示不适用，其余为相对标准的偏移
+fn picture_control_value(v: u8) -> String {
+    match v {
+        0x80 => tr("Normal", "标准", "標準").to_string(),
+        0xff => tr("n/a", "不适用", "該当なし").to_string(),
+        _ => format!("{:+}", v as i32 - 0x80),
+    }
+}
+
+/// PictureControlData：版本 01xx / 02xx 的名称在 4、基础设定在 24，03xx 的名称在 8、基础设定在 28
+fn decode_picture_control(note: &mut MakerNote, data: &[u8]) {
+    let version = block_version(data);
+    let (name_pos, base_pos) = if version.starts_with("03") { (8, 28) } else { (4, 24) };
+    if let Some(name) = data.get(name_pos..name_pos + 20) {
+        note.push(0x0023, "PictureControlName", tr("Picture Control", "优化校准", "ピクチャーコントロール"), c_string(name));
+    }
+    if let Some(base) = data.get(base_pos..base_pos + 20) {
+        note.push(0x0023, "PictureControlBase", tr("Picture Control Base", "优化校准基础", "ピクチャーコントロールのベース"), c_string(base));
+    }
+    if version.starts_with("03") {
+        return;
+    }
+    if let Some(&v) = data.get(0x30) {
+        let value = match v {
+            0 => tr("Default Settings", "默认设定", "初期設定"),
+            1 => tr("Quick Adjust", "快速调整", "クイック調整"),
+            2 => tr("Full Control", "完全控制", "詳細調整"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(0x0023, "PictureControlAdjust", tr("Picture Control Adjust", "优化校准调整", "ピクチャーコントロール調整"), value);
+    }
+    let fields: [(usize, &'static str, &'static str); 5] = [
+        (0x31, "PictureControlQuickAdjust", tr("Quick Adjust", "快速调整", "クイック調整")),
+        (0x32, "Sharpening", tr("Sharpening", "锐化", "輪郭強調")),
+        (0x33, "Contrast", tr("Contrast", "对比度", "コントラスト")),
+        (0x34, "Brightness", tr("Brightness", "亮度", "明るさ")),
+        (0x35, "Saturation", tr("Saturation", "饱和度", "彩度")),
+    ];
+    for (pos, name, label) in fields {
+        if let Some(&v) = data.get(pos) {
+            note.push(0x0023, name, label, picture_control_value(v));
+        }
+    }
+}
+
+/// ShotInfo：4 字节版本号 + 加密数据，较新机型解密后开头是固件版本
+fn decode_shot_info(note: &mut MakerNote, data: &[u8], key: Option<(u32, u32)>) {
+    note.push(0x0091, "ShotInfoVersion", tr("Shot Info Version", "ShotInfo 版本", "ShotInfo バージョン"), block_version(data));
+    let Some((serial, count)) = key else {
+        note.push(0x0091, "ShotInfo", "ShotInfo", undecryptable());
+        return;
+    };
+    let plain = decrypt(data, 4, serial, count);
+    let firmware = plain.get(4..12).map(c_string).unwrap_or_default();
+    let mut chars = firmware.chars();
+    if chars.next().is_some_and(|c| c.is_ascii_digit()) && chars.next() == Some('.') {
+        note.push(0x0091, "FirmwareVersion", tr("Firmware Version", "固件版本", "ファームウェアバージョン"), firmware);
+    }
+}
+
+/// LensData 中各字段的位置
+struct LensDataLayout {
+    /// 出瞳位置、AF 光圈、对焦位置、对焦距离、焦距（0100 版本没有这些字段）
+    optics: Option<(usize, usize, usize, usize, usize)>,
+    /// LensIDNumber 的位置，之后依次为 LensFStops、最短焦距、最长焦距、两端最大光圈和 MCU 版本
+    lens_id: usize,
+    /// 有效最大光圈
+    effective_max_aperture: Option<usize>,
+}
+
+/// LensData：0100 / 0101 不加密，0201–0204 从第 4 字节开始加密
+fn decode_lens_data(note: &mut MakerNote, data: &[u8], key: Option<(u32, u32)>, lens_type: Option<u8>) {
+    let version = block_version(data);
+    note.push(0x0098, "LensDataVersion", tr("Lens Data Version", "LensData 版本", "LensData バージョン"), version.clone());
+    let layout = match version.as_str() {
+        "0100" => LensDataLayout { optics: None, lens_id: 0x06, effective_max_aperture: None },
+        "0101" | "0201" | "0202" | "0203" => LensDataLayout {
+            optics: Some((0x04, 0x05, 0x08, 0x09, 0x0a)),
+            lens_id: 0x0b,
+            effective_max_aperture: Some(0x12),
+        },
+        "0204" => LensDataLayout {
+            optics: Some((0x04, 0x05, 0x08, 0x0a, 0x0b)),
+            lens_id: 0x0c,
+            effective_max_aperture: Some(0x13),
+        },
+        _ => {
+            note.push(0x0098, "LensData", "LensData", tr("field layout of this version is not decoded", "该版本的字段布局未解码", "このバージョンのフィールド配置は未対応"));
+            return;
+        }
+    };
+    let plain = if version.starts_with("02") {
+        let Some((serial, count)) = key else {
+            note.push(0x0098, "LensData", "LensData", undecryptable());
+            return;
+        };
+        decrypt(data, 4, serial, count)
+    } else {
+        data.to_vec()
+    };
+
+    // 焦距为 5 × 2^(v/24) mm，光圈为 2^(v/24)
+    let focal = |v: u8| 5.0 * 2f64.powf(v as f64 / 24.0);
+    let aperture = |v: u8| 2f64.powf(v as f64 / 24.0);
+    if let Some((exit_pupil, af_aperture, focus_pos, focus_dist, focal_len)) = layout.optics {
+        if let Some(&v) = plain.get(exit_pupil).filter(|&&v| v != 0) {
+            note.push(0x0098, "ExitPupilPosition", tr("Exit Pupil Position", "出瞳位置", "射出瞳位置"), format_mm(2048.0 / v as f64));
+        }
+        if let Some(&v) = plain.get(af_aperture) {
+            note.push(0x0098, "AFAperture", tr("AF Aperture", "AF 光圈", "AF 絞り"), format_f_number(aperture(v)));
+        }
+        if let Some(&v) = plain.get(focus_pos) {
+            note.push(0x0098, "FocusPosition", tr("Focus Position", "对焦位置", "フォーカス位置"), format!("0x{:02X}", v));
+        }
+        if let Some(&v) = plain.get(focus_dist) {
+            let meters = 0.01 * 10f64.powf(v as f64 / 40.0);
+            note.push(0x0098, "FocusDistance", tr("Focus Distance", "对焦距离", "撮影距離"), format!("{:.2} m", meters));
+        }
+        if let Some(&v) = plain.get(focal_len) {
+            note.push(0x0098, "FocalLength", tr("Focal Length", "焦距", "焦点距離"), format_mm(focal(v)));
+        }
+    }
+
+    let Some(id) = plain.get(layout.lens_id..layout.lens_id + 7) else { return };
+    note.push(0x0098, "LensIDNumber", tr("Lens ID Number", "镜头编号", "レンズ ID 番号"), id[0].to_string());
+    note.push(0x0098, "LensFStops", tr("Lens F-Stops", "镜头光圈档数", "レンズ絞り段数"), format!("{:.2}", id[1] as f64 / 12.0));
+    note.push(0x0098, "MinFocalLength", tr("Min Focal Length", "最短焦距", "最短焦点距離"), format_mm(focal(id[2])));
+    note.push(0x0098, "MaxFocalLength", tr("Max Focal Length", "最长焦距", "最長焦点距離"), format_mm(focal(id[3])));
+    note.push(0x0098, "MaxApertureAtMinFocal", tr("Max Aperture at Min Focal", "最短焦距最大光圈", "最短焦点距離での開放 F 値"), format_f_number(aperture(id[4])));
+    note.push(0x0098, "MaxApertureAtMaxFocal", tr("Max Aperture at Max Focal", "最长焦距最大光圈", "最長焦点距離での開放 F 値"), format_f_number(aperture(id[5])));
+    note.push(0x0098, "MCUVersion", tr("MCU Version", "MCU 版本", "MCU バージョン"), id[6].to_string());
+    if let Some(&v) = layout.effective_max_aperture.and_then(|p| plain.get(p)) {
+        note.push(0x0098, "EffectiveMaxAperture", tr("Effective Max Aperture", "有效最大光圈", "実効開放 F 値"), format_f_number(aperture(v)));
+    }
+    // 镜头 ID：LensData 中的 7 个字节加上 LensType，与 exiftool 的 LensID 查表键一致
+    let mut lens_id: Vec<String> = id.iter().map(|b| format!("{:02X}", b)).collect();
+    lens_id.push(format!("{:02X}", lens_type.unwrap_or(0)));
+    note.push(0x0098, "LensID", tr("Lens ID", "镜头 ID", "レンズ ID"), lens_id.join(" "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makernote::test_util::value;
+
+    /// 序列号 4012345、快门次数 12345 时的前 8 个密钥字节（解密全零数据即得到密钥流）
+    const KEYSTREAM: [u8; 8] = [0x18, 0xff, 0x2d, 0xa2, 0x5e, 0x61, 0xab, 0x3c];
+
+    /// 用上面的密钥加密的 0204 版 LensData
+    const LENS_DATA_0204: [u8; 20] = [
+        0x30, 0x32, 0x30, 0x34, 0x58, 0xe7, 0x2d, 0xa2, 0x4e, 0x61, 0xfb, 0x00, 0xb4, 0x03, 0xa5, 0x7a, 0x22, 0x6d,
+        0xf8, 0xdc,
+    ];
+
+    #[test]
+    fn serial_key_by_model() {
+        assert_eq!(serial_key("4012345", Some("NIKON Z 6")), 4012345);
+        assert_eq!(serial_key("No= 3000a1b2", Some("NIKON D50")), 0x22);
+        assert_eq!(serial_key("No= 3000a1b2", Some("NIKON D750")), 0x60);
+        assert_eq!(serial_key("No= 3000a1b2", None), 0x60);
+    }
+
+    #[test]
+    fn decrypt_known_keystream() {
+        let mut data = b"0204".to_vec();
+        data.extend([0u8; 8]);
+        let plain = decrypt(&data, 4, 4012345, 12345);
+        // 版本号不加密
+        assert_eq!(&plain[..4], b"0204");
+        assert_eq!(plain[4..], KEYSTREAM);
+        // 异或加密，解密两次还原
+        assert_eq!(decrypt(&plain, 4, 4012345, 12345), data);
+    }
+
+    #[test]
+    fn shot_info_firmware_version() {
+        let mut data = b"0246".to_vec();
+        data.extend(b"1.10\0\0\0\0".iter().zip(KEYSTREAM).map(|(b, k)| b ^ k));
+        let mut note = MakerNote::new("Nikon", "Nikon Type 3");
+        decode_shot_info(&mut note, &data, Some((4012345, 12345)));
+        assert_eq!(value(&note, "ShotInfoVersion"), "0246");
+        assert_eq!(value(&note, "FirmwareVersion"), "1.10");
+    }
+
+    #[test]
+    fn lens_data_0204() {
+        let mut note = MakerNote::new("Nikon", "Nikon Type 3");
+        decode_lens_data(&mut note, &LENS_DATA_0204, Some((4012345, 12345)), Some(0x06));
+        assert_eq!(value(&note, "LensDataVersion"), "0204");
+        assert_eq!(value(&note, "ExitPupilPosition"), "32 mm");
+        assert_eq!(value(&note, "AFAperture"), "f/2");
+        assert_eq!(value(&note, "FocusPosition"), "0x10");
+        assert_eq!(value(&note, "FocusDistance"), "1.00 m");
+        assert_eq!(value(&note, "FocalLength"), "28.3 mm");
+        assert_eq!(value(&note, "LensIDNumber"), "160");
+        assert_eq!(value(&note, "LensFStops"), "4.00");
+        assert_eq!(value(&note, "MinFocalLength"), "28.3 mm");
+        assert_eq!(value(&note, "MaxApertureAtMinFocal"), "f/2");
+        assert_eq!(value(&note, "MCUVersion"), "15");
+        assert_eq!(value(&note, "EffectiveMaxAperture"), "f/2.2");
+        assert_eq!(value(&note, "LensID"), "A0 30 3C 3C 18 18 0F 06");
+    }
+
+    #[test]
+    fn lens_data_without_key_is_not_decrypted() {
+        let mut note = MakerNote::new("Nikon", "Nikon Type 3");
+        decode_lens_data(&mut note, &LENS_DATA_0204, None, None);
+        assert!(note.find("LensData").is_some());
+        assert!(note.find("LensID").is_none());
+    }
+
+    #[test]
+    fn lens_data_0100_is_plain() {
+        let mut data = b"0100".to_vec();
+        data.extend([0, 0, 0x9a, 0x30, 0x48, 0x48, 0x18, 0x18, 0x02]);
+        let mut note = MakerNote::new("Nikon", "Nikon Type 3");
+        decode_lens_data(&mut note, &data, None, None);
+        assert_eq!(value(&note, "LensIDNumber"), "154");
+        assert_eq!(value(&note, "MinFocalLength"), "40 mm");
+        assert_eq!(value(&note, "LensID"), "9A 30 48 48 18 18 02 00");
+    }
+}
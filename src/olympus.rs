@@ -0,0 +1,373 @@
+use crate::{
+    locale::tr,
+    makernote::{format_f_number, format_mm, guess_offset_base, read_ifd, IfdEntry, MakerNote, MakerNoteContext},
+    tiff::detect_endian,
+};
+
+/// 解码 Olympus / OM System MakerNote
+///
+/// - "OLYMPUS\0" + 字节序 + 版本（12 字节头）和 "OM SYSTEM\0\0\0" + 字节序 + 版本（16 字节头）：
+///   偏移相对于 MakerNote 开头
+/// - "OLYMP\0" + 版本（8 字节头）：旧格式，偏移相对于外层 TIFF 头
+///
+/// 主 IFD 中的 Equipment（0x2010）、CameraSettings（0x2020）和 ImageProcessing（0x2040）
+/// 是子 IFD，类型为 IFD 或 UNDEFINED，值为子 IFD 的偏移
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: MakerNote 在 TIFF 中的偏移和外层字节序（仅旧格式需要）
+///
+/// # 返回值
+/// - 解码出的标签；头部不认识或 IFD 损坏时返回 None
+pub fn decode_olympus_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    let (format, endian, ifd_offset, base) = if data.starts_with(b"OLYMPUS\0") {
+        ("Olympus Type II", detect_endian(data.get(8..)?)?, 12, 0)
+    } else if data.starts_with(b"OM SYSTEM\0") {
+        ("OM System", detect_endian(data.get(12..)?)?, 16, 0)
+    } else if data.starts_with(b"OLYMP\0") {
+        let endian = ctx.endian.clone();
+        let base = match ctx.tiff_offset {
+            Some(offset) => offset as i64,
+            None => guess_offset_base(data, 8, &endian).unwrap_or(0),
+        };
+        ("Olympus Type I", endian, 8, base)
+    } else {
+        return None;
+    };
+    let entries = read_ifd(data, ifd_offset, &endian, base)?;
+
+    let mut note = MakerNote::new("Olympus", format);
+    for e in &entries {
+        let decode: fn(&mut MakerNote, &IfdEntry) = match e.tag {
+            0x2010 => decode_equipment,
+            0x2020 => decode_camera_settings,
+            0x2040 => decode_image_processing,
+            _ => continue,
+        };
+        // 子 IFD 中的偏移与主 IFD 使用相同的基准
+        let Ok(sub) = usize::try_from(e.value_offset as i64 - base) else { continue };
+        if let Some(sub_entries) = read_ifd(data, sub, &endian, base) {
+            for sub_entry in &sub_entries {
+                decode(&mut note, sub_entry);
+            }
+        }
+    }
+    Some(note)
+}
+
+/// 版本号等 4 字节 ASCII
+fn version(e: &IfdEntry) -> String {
+    String::from_utf8_lossy(e.u8s()).trim_end_matches('\0').to_string()
+}
+
+/// Olympus 的光圈编码：√2^(v/256)
+fn olympus_aperture(v: u16) -> f64 {
+    2f64.sqrt().powf(v as f64 / 256.0)
+}
+
+/// Equipment 子 IFD：机身与镜头信息
+fn decode_equipment(note: &mut MakerNote, e: &IfdEntry) {
+    const ID: u16 = 0x2010;
+    let first_u16 = || e.u16s().first().copied().filter(|&v| v != 0);
+    match e.tag {
+        0x0000 => note.push(ID, "EquipmentVersion", tr("Equipment Version", "Equipment 版本", "Equipment バージョン"), version(e)),
+        0x0100 => note.push(ID, "CameraType2", tr("Camera Type", "机型代号", "機種コード"), e.text()),
+        0x0101 => note.push(ID, "SerialNumber", tr("Serial Number", "机身序列号", "シリアル番号"), e.text()),
+        0x0102 => note.push(ID, "InternalSerialNumber", tr("Internal Serial Number", "内部序列号", "内部シリアル番号"), e.text()),
+        0x0103 => {
+            if let Some(&(n, d)) = e.rationals().first().filter(|(_, d)| *d != 0) {
+                note.push(ID, "FocalPlaneDiagonal", tr("Focal Plane Diagonal", "焦平面对角线", "撮像面対角線長"), format_mm(n as f64 / d as f64));
+            }
+        }
+        0x0104 => {
+            if let Some(&v) = e.u32s().first() {
+                note.push(ID, "BodyFirmwareVersion", tr("Body Firmware Version", "机身固件版本", "ボディファームウェアバージョン"), format!("{:x}.{:03x}", v >> 16, v & 0xfff));
+            }
+        }
+        0x0201 => {
+            // 6 字节：制造商、未知、型号、子型号……与 exiftool 的 LensType 键一致
+            let b = e.u8s();
+            if b.len() >= 4 {
+                note.push(ID, "LensType", tr("Lens Type", "镜头类型", "レンズタイプ"), format!("{} {:02X} {:02X}", b[0], b[2], b[3]));
+            }
+        }
+        0x0202 => note.push(ID, "LensSerialNumber", tr("Lens Serial Number", "镜头序列号", "レンズシリアル番号"), e.text()),
+        0x0203 => note.push(ID, "LensModel", tr("Lens Model", "镜头型号", "レンズモデル"), e.text()),
+        0x0204 => {
+            if let Some(&v) = e.u32s().first() {
+                note.push(ID, "LensFirmwareVersion", tr("Lens Firmware Version", "镜头固件版本", "レンズファームウェアバージョン"), format!("{:x}.{:03x}", v >> 16, v & 0xfff));
+            }
+        }
+        0x0205 => {
+            if let Some(v) = first_u16() {
+                note.push(ID, "MaxApertureAtMinFocal", tr("Max Aperture at Min Focal", "最短焦距时最大光圈", "最短焦点距離での開放 F 値"), format_f_number(olympus_aperture(v)));
+            }
+        }
+        0x0206 => {
+            if let Some(v) = first_u16() {
+                note.push(ID, "MaxApertureAtMaxFocal", tr("Max Aperture at Max Focal", "最长焦距时最大光圈", "最長焦点距離での開放 F 値"), format_f_number(olympus_aperture(v)));
+            }
+        }
+        0x0207 => {
+            if let Some(v) = first_u16() {
+                note.push(ID, "MinFocalLength", tr("Min Focal Length", "最短焦距", "最短焦点距離"), format_mm(v as f64));
+            }
+        }
+        0x0208 => {
+            if let Some(v) = first_u16() {
+                note.push(ID, "MaxFocalLength", tr("Max Focal Length", "最长焦距", "最長焦点距離"), format_mm(v as f64));
+            }
+        }
+        0x1000 => {
+            if let Some(v) = e.first_int() {
+                note.push(ID, "FlashType", tr("Flash Type", "闪光灯类型", "フラッシュタイプ"), if v == 0 { tr("None", "无", "なし") } else { tr("External Flash", "外置闪光灯", "外付けフラッシュ") });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// CameraSettings 子 IFD：曝光、对焦、白平衡和画面风格
+fn decode_camera_settings(note: &mut MakerNote, e: &IfdEntry) {
+    const ID: u16 = 0x2020;
+    let Some(v) = e.first_int() else { return };
+    match e.tag {
+        0x0000 => note.push(ID, "CameraSettingsVersion", tr("Camera Settings Version", "CameraSettings 版本", "CameraSettings バージョン"), version(e)),
+        0x0200 => {
+            let value = match v {
+                1 => tr("Manual", "手动", "マニュアル"),
+                2 => tr("Program AE", "程序自动曝光", "プログラム AE"),
+                3 => tr("Aperture Priority", "光圈优先", "絞り優先"),
+                4 => tr("Shutter Priority", "快门优先", "シャッター優先"),
+                5 => tr("Program Shift", "程序偏移", "プログラムシフト"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(ID, "ExposureMode", tr("Exposure Mode", "曝光模式", "露出モード"), value);
+        }
+        0x0202 => {
+            let value = match v {
+                2 => tr("Center-weighted Average", "中央重点平均测光", "中央重点平均測光"),
+                3 => tr("Spot", "点测光", "スポット測光"),
+                5 => tr("ESP", "ESP 测光", "ESP 測光"),
+                261 => tr("Pattern + AF", "图案 + AF", "パターン + AF"),
+                515 => tr("Spot + Highlight Control", "点测光 + 高光控制", "スポット + ハイライトコントロール"),
+                1027 => tr("Spot + Shadow Control", "点测光 + 阴影控制", "スポット + シャドーコントロール"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(ID, "MeteringMode", tr("Metering Mode", "测光模式", "測光モード"), value);
+        }
+        0x0301 => {
+            let value = match v {
+                0 => tr("Single AF (S-AF)", "单次自动对焦 (S-AF)", "シングル AF (S-AF)"),
+                1 => tr("Sequential Shooting AF", "连拍自动对焦", "連写 AF"),
+                2 => tr("Continuous AF (C-AF)", "连续自动对焦 (C-AF)", "コンティニュアス AF (C-AF)"),
+                3 => tr("Multi-point AF", "多点自动对焦", "多点 AF"),
+                4 => tr("Face Detect", "人脸检测", "顔検出"),
+                10 => tr("Manual Focus (MF)", "手动对焦 (MF)", "マニュアルフォーカス (MF)"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(ID, "FocusMode", tr("Focus Mode", "对焦模式", "フォーカスモード"), value);
+        }
+        0x0500 => note.push(ID, "WhiteBalance2", tr("White Balance", "白平衡", "ホワイトバランス"), white_balance_name(v)),
+        0x0501 if v != 0 => note.push(ID, "WhiteBalanceTemperature", tr("White Balance Temperature", "白平衡色温", "ホワイトバランス色温度"), format!("{} K", v)),
+        0x0520 => {
+            let value = match v {
+                1 => tr("Vivid", "鲜艳", "ビビッド"),
+                2 => tr("Natural", "自然", "ナチュラル"),
+                3 => tr("Muted", "柔和", "フラット"),
+                4 => tr("Portrait", "人像", "ポートレート"),
+                5 => "i-Enhance",
+                6 => "e-Portrait",
+                7 => tr("Color Creator", "色彩创意", "カラークリエーター"),
+                9 => tr("Color Profile 1", "色彩配置文件 1", "カラープロファイル 1"),
+                10 => tr("Color Profile 2", "色彩配置文件 2", "カラープロファイル 2"),
+                11 => tr("Color Profile 3", "色彩配置文件 3", "カラープロファイル 3"),
+                12 => tr("Monochrome Profile 1", "单色配置文件 1", "モノクロプロファイル 1"),
+                13 => tr("Monochrome Profile 2", "单色配置文件 2", "モノクロプロファイル 2"),
+                14 => tr("Monochrome Profile 3", "单色配置文件 3", "モノクロプロファイル 3"),
+                256 => tr("Monochrome", "单色", "モノクロ"),
+                512 => tr("Sepia", "棕褐色", "セピア"),
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(ID, "PictureMode", tr("Picture Mode", "画面模式", "仕上がり"), value);
+        }
+        0x0603 => {
+            let value = match v {
+                1 => "SQ",
+                2 => "HQ",
+                3 => "SHQ",
+                4 => "RAW",
+                5 => "SQ (5)",
+                _ => tr("Unknown", "未知", "不明"),
+            };
+            note.push(ID, "ImageQuality2", tr("Quality", "画质", "画質"), value);
+        }
+        0x0604 => {
+            let value = match v {
+                0 => tr("Off", "关闭", "オフ").to_string(),
+                1..=4 => format!("{}{}{} {}", tr("On", "开启", "オン"), tr(", ", "，", "、"), tr("Mode", "模式", "モード"), v),
+                _ => tr("Unknown", "未知", "不明").to_string(),
+            };
+            note.push(ID, "ImageStabilization", tr("Image Stabilization", "防抖", "手ブレ補正"), value);
+        }
+        _ => {}
+    }
+}
+
+fn white_balance_name(v: i64) -> String {
+    match v {
+        0 => tr("Auto", "自动", "オート"),
+        1 => tr("Auto (Keep Warm Color Off)", "自动（保留暖色关闭）", "オート（電球色残し オフ）"),
+        16 => tr("7500K (Fine Weather with Shade)", "7500K（晴天阴影）", "7500K（晴天日陰）"),
+        17 => tr("6000K (Cloudy)", "6000K（阴天）", "6000K（曇天）"),
+        18 => tr("5300K (Fine Weather)", "5300K（晴天）", "5300K（晴天）"),
+        20 => tr("3000K (Tungsten light)", "3000K（钨丝灯）", "3000K（電球）"),
+        21 => tr("3600K (Tungsten light-like)", "3600K（类钨丝灯）", "3600K（電球類似）"),
+        22 => tr("Auto Setup", "自动设置", "オート設定"),
+        23 => tr("5500K (Flash)", "5500K（闪光灯）", "5500K（フラッシュ）"),
+        33 => tr("6600K (Daylight fluorescent)", "6600K（日光荧光灯）", "6600K（昼光色蛍光灯）"),
+        34 => tr("4500K (Neutral white fluorescent)", "4500K（中性白荧光灯）", "4500K（昼白色蛍光灯）"),
+        35 => tr("4000K (Cool white fluorescent)", "4000K（冷白荧光灯）", "4000K（白色蛍光灯）"),
+        36 => tr("White Fluorescent", "白色荧光灯", "白色蛍光灯"),
+        48 => tr("3600K (Tungsten light-like)", "3600K（类钨丝灯）", "3600K（電球類似）"),
+        67 => tr("Underwater", "水下", "水中"),
+        256..=259 => return format!("{} {}", tr("One Touch WB", "一键白平衡", "ワンタッチ WB"), v - 255),
+        512..=515 => return format!("{} {}", tr("Custom WB", "自定义白平衡", "カスタム WB"), v - 511),
+        _ => tr("Unknown", "未知", "不明"),
+    }
+    .to_string()
+}
+
+/// ImageProcessing 子 IFD：白平衡系数、画幅比例和人脸检测
+fn decode_image_processing(note: &mut MakerNote, e: &IfdEntry) {
+    const ID: u16 = 0x2040;
+    match e.tag {
+        0x0000 => note.push(ID, "ImageProcessingVersion", tr("Image Processing Version", "ImageProcessing 版本", "ImageProcessing バージョン"), version(e)),
+        0x0100 => {
+            let v = e.u16s();
+            if let [red, blue, ..] = v[..] {
+                note.push(ID, "WB_RBLevels", tr("WB Red/Blue Levels", "白平衡红 / 蓝系数", "ホワイトバランス R / B 係数"), format!("{} {}", red, blue));
+            }
+        }
+        0x1112 => {
+            if let Some(&v) = e.u8s().first() {
+                let value = match v {
+                    1 => "4:3",
+                    2 => "3:2",
+                    3 => "16:9",
+                    4 => "1:1",
+                    5 => "5:4",
+                    6 => "7:6",
+                    7 => "6:5",
+                    8 => "7:5",
+                    9 => "3:4",
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(ID, "AspectRatio", tr("Aspect Ratio", "画幅比例", "アスペクト比"), value);
+            }
+        }
+        0x1200 => {
+            // 三组人脸检测结果的人脸数，取最大值
+            if let Some(n) = e.u32s().iter().max() {
+                note.push(ID, "FacesDetected", tr("Faces Detected", "检测到的人脸数", "検出された顔の数"), n.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makernote::test_util::{build_ifd, u16s, u32s, value};
+    use little_exif::endian::Endian;
+
+    /// 头部之后是指向 Equipment 和 CameraSettings 子 IFD 的主 IFD
+    ///
+    /// # 参数
+    /// - `header`: MakerNote 头部，主 IFD 紧跟其后
+    /// - `base`: MakerNote 在偏移基准中的位置（新格式为 0，旧格式为 MakerNote 在 TIFF 中的偏移）
+    fn maker_note(header: &[u8], base: usize, endian: &Endian) -> Vec<u8> {
+        let equipment = [
+            (0x0000, 7, b"0100".to_vec()),
+            (0x0101, 2, b"BHX201234\0".to_vec()),
+            (0x0103, 5, u32s(&[2163, 100], endian)),
+            (0x0201, 1, vec![0, 0, 0x21, 0x10, 0, 0]),
+            (0x0203, 2, b"OM 12-40mm F2.8 PRO II\0".to_vec()),
+            // √2^(768/256) = f/2.8
+            (0x0205, 3, u16s(&[768], endian)),
+            (0x0207, 3, u16s(&[12], endian)),
+            (0x0208, 3, u16s(&[40], endian)),
+        ];
+        let settings = [
+            (0x0200, 3, u16s(&[3], endian)),
+            (0x0202, 3, u16s(&[5], endian)),
+            (0x0301, 3, u16s(&[2], endian)),
+            (0x0500, 3, u16s(&[257], endian)),
+            (0x0520, 3, u16s(&[256], endian)),
+            (0x0604, 4, u32s(&[2], endian)),
+        ];
+        let main_start = base + header.len();
+        let equipment_start = main_start + 2 + 2 * 12 + 4;
+        let equipment = build_ifd(&equipment, equipment_start, endian);
+        let settings_start = equipment_start + equipment.len();
+        let main = build_ifd(
+            &[(0x2010, 13, u32s(&[equipment_start as u32], endian)), (0x2020, 13, u32s(&[settings_start as u32], endian))],
+            main_start,
+            endian,
+        );
+        [header, &main, &equipment, &build_ifd(&settings, settings_start, endian)].concat()
+    }
+
+    fn ctx(tiff_offset: Option<u64>, endian: Endian) -> MakerNoteContext<'static> {
+        MakerNoteContext { make: Some("OM Digital Solutions"), model: Some("OM-1"), tiff_offset, endian }
+    }
+
+    fn assert_decoded(note: &MakerNote) {
+        assert_eq!(value(note, "EquipmentVersion"), "0100");
+        assert_eq!(value(note, "SerialNumber"), "BHX201234");
+        assert_eq!(value(note, "FocalPlaneDiagonal"), "21.6 mm");
+        assert_eq!(value(note, "LensType"), "0 21 10");
+        assert_eq!(value(note, "LensModel"), "OM 12-40mm F2.8 PRO II");
+        assert_eq!(value(note, "MaxApertureAtMinFocal"), "f/2.8");
+        assert_eq!(value(note, "MinFocalLength"), "12 mm");
+        assert_eq!(value(note, "MaxFocalLength"), "40 mm");
+        assert_eq!(value(note, "ExposureMode"), "Aperture Priority");
+        assert_eq!(value(note, "MeteringMode"), "ESP");
+        assert_eq!(value(note, "FocusMode"), "Continuous AF (C-AF)");
+        assert_eq!(value(note, "WhiteBalance2"), "One Touch WB 2");
+        assert_eq!(value(note, "PictureMode"), "Monochrome");
+        assert_eq!(value(note, "ImageStabilization"), "On, Mode 2");
+    }
+
+    #[test]
+    fn type2_offsets_relative_to_maker_note() {
+        let data = maker_note(b"OLYMPUS\0II\x03\0", 0, &Endian::Little);
+        // 外层字节序和位置不影响新格式
+        let note = decode_olympus_maker_note(&data, &ctx(Some(5000), Endian::Big)).unwrap();
+        assert_eq!(note.format, "Olympus Type II");
+        assert_decoded(&note);
+    }
+
+    #[test]
+    fn om_system_big_endian() {
+        let data = maker_note(b"OM SYSTEM\0\0\0MM\x03\0", 0, &Endian::Big);
+        let note = decode_olympus_maker_note(&data, &ctx(None, Endian::Little)).unwrap();
+        assert_eq!(note.format, "OM System");
+        assert_decoded(&note);
+    }
+
+    #[test]
+    fn type1_offsets_relative_to_tiff() {
+        let data = maker_note(b"OLYMP\0\x01\0", 800, &Endian::Big);
+        let note = decode_olympus_maker_note(&data, &ctx(Some(800), Endian::Big)).unwrap();
+        assert_eq!(note.format, "Olympus Type I");
+        assert_decoded(&note);
+    }
+
+    #[test]
+    fn unknown_header() {
+        let data = maker_note(b"OLYMPUX\0II\x03\0", 0, &Endian::Little);
+        assert!(decode_olympus_maker_note(&data, &ctx(None, Endian::Little)).is_none());
+    }
+}
@@ -0,0 +1,1226 @@
+use std::{
+    fs::File,
+    path::Path,
+    io::{Read, Cursor},
+};
+use flate2::read::ZlibDecoder;
+use little_exif::{endian::Endian, exif_tag::ExifTag};
+
+use crate::{error::Error, locale::tr};
+
+/// PNG 数据块中找到的元数据
+#[derive(Debug, Clone, Default)]
+pub struct PngMetadataChunks {
+    /// EXIF 数据（TIFF 结构）
+    pub exif: Option<Vec<u8>>,
+    /// XMP 数据包（已解压）
+    pub xmp: Option<Vec<u8>>,
+    /// IPTC-IIM 数据（来自 Raw profile type iptc）
+    pub iptc: Option<Vec<u8>>,
+    /// 其他文本数据块（Title、Author、Comment 等）
+    pub text: Vec<PngTextEntry>,
+    /// 其他辅助数据块（pHYs、tIME、gAMA、iCCP、acTL 等）
+    pub ancillary: PngAncillary,
+    /// 因 CRC 错误或无法解码而跳过的数据块（不含携带元数据的数据块，它们损坏时直接返回错误）
+    pub issues: Vec<PngIssue>,
+}
+
+/// PNG 文本数据块（tEXt / zTXt / iTXt）中的一条关键字和文本
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngTextEntry {
+    /// 来源数据块类型："tEXt"、"zTXt" 或 "iTXt"
+    pub chunk: &'static str,
+    /// 关键字（如 "Title"、"Creation Time"）
+    pub keyword: String,
+    /// iTXt 的语言标签
+    pub language: Option<String>,
+    /// 已解压、已解码的文本
+    pub text: String,
+}
+
+/// 携带元数据的数据块，读取时 CRC 错误会中止读取
+const METADATA_CHUNKS: [&[u8]; 4] = [b"eXIf", b"tEXt", b"zTXt", b"iTXt"];
+
+/// APP1 段中 EXIF 数据的前缀
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+/// APP1 段中 XMP 数据的前缀
+const XMP_APP1_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// 从 PNG 文件中提取 EXIF 和 XMP 数据，只读取文件，不产生任何写入
+/// 
+/// # 参数
+/// - `path`: PNG 文件路径
+/// 
+/// # 返回值
+/// - 找到的 EXIF / XMP 数据（都可能为空）；其他数据块的 CRC 错误只记录在 issues 中，该数据块被跳过
+/// - 文件无法读取、签名错误、数据块截断、元数据块（eXIf / tEXt / zTXt / iTXt）CRC 错误或解压失败时返回 Error
+pub fn extract_metadata_from_png(path: &Path) -> Result<PngMetadataChunks, Error> {
+    let mut f = File::open(path)?; // 打开文件
+    let mut buf = Vec::new(); // 创建缓冲区
+    f.read_to_end(&mut buf)?; // 读取整个文件内容
+    extract_metadata_from_png_bytes(&buf)
+}
+
+/// 从内存中的 PNG 文件提取元数据，参见 [`extract_metadata_from_png`]
+///
+/// # 参数
+/// - `buf`: 完整的 PNG 文件内容
+///
+/// # 返回值
+/// - 找到的元数据；签名错误、数据块截断、元数据块 CRC 错误或解压失败时返回 Error
+pub fn extract_metadata_from_png_bytes(buf: &[u8]) -> Result<PngMetadataChunks, Error> {
+    // PNG 文件签名
+    const PNG_SIG: &[u8; 8] = b"\x89PNG\r\n\x1a\n"; // PNG 文件有固定的8字节签名头
+
+    // 验证 PNG 文件签名
+    if buf.len() < 8 || &buf[..8] != PNG_SIG { // 如果文件长度小于8字节或前8字节不是 PNG 签名，则返回错误
+        return Err(Error::invalid_signature("png"));
+    }
+
+    let mut found = PngMetadataChunks::default();
+
+    // 遍历 PNG 数据块
+    let mut pos = 8usize; // 跳过 PNG 签名头
+    while pos < buf.len() {
+        let chunk_start = pos; // 数据块起始偏移
+        if pos + 8 > buf.len() { // 数据块头部不完整
+            return Err(Error::TruncatedChunk { chunk: String::new(), offset: chunk_start });
+        }
+        // 读取数据块长度
+        let len = {
+            let slice = &buf[pos..pos+4];
+            u32::from_be_bytes([slice[0], slice[1], slice[2], slice[3]]) as usize
+        }; // 数据块长度是4字节的大端整数
+        let typ = &buf[pos+4..pos+8]; // 数据块类型是接下来的4字节
+        pos += 8; // 移动到数据块数据部分
+        
+        let chunk_name = String::from_utf8_lossy(typ).to_string();
+        
+        // 检查数据边界
+        if pos + len + 4 > buf.len() { // 如果数据块长度超出文件边界，则返回截断错误
+            return Err(Error::TruncatedChunk { chunk: chunk_name, offset: chunk_start });
+        }
+        let data = &buf[pos..pos+len]; // 数据块数据部分
+        pos += len; // 移动到下一个数据块
+        let crc = u32::from_be_bytes([buf[pos], buf[pos+1], buf[pos+2], buf[pos+3]]); // CRC 校验码
+        pos += 4; // 移动到下一个数据块起始位置
+
+        // CRC 覆盖数据块类型和数据部分
+        let computed = crc32fast::hash(&buf[chunk_start+4..chunk_start+8+len]);
+        if computed != crc {
+            // 元数据块损坏时无法给出可信的结果，其他数据块跳过即可
+            if METADATA_CHUNKS.contains(&typ) {
+                return Err(Error::BadCrc { chunk: chunk_name, offset: chunk_start });
+            }
+            found.issues.push(PngIssue::BadCrc { chunk: chunk_name, offset: chunk_start, stored: crc, computed });
+            if typ == b"IEND" {
+                break;
+            }
+            continue;
+        }
+
+        // IEND 之后不再有数据块
+        if typ == b"IEND" {
+            break;
+        }
+
+        // 解析 pHYs、tIME、iCCP、acTL 等辅助数据块，无法解码的数据块只记录问题
+        if let Err(e) = found.ancillary.apply_chunk(typ, data) {
+            let message = match e {
+                Error::Decompression(message) => message,
+                e => e.to_string(),
+            };
+            found.issues.push(PngIssue::Undecodable { chunk: chunk_name, offset: chunk_start, message });
+            continue;
+        }
+
+        // 处理 eXIf 数据块（标准 EXIF 数据）
+        if typ == b"eXIf" && found.exif.is_none() { // 如果数据块类型是 eXIf，则将其数据部分作为 EXIF 数据
+            found.exif = Some(data.to_vec());
+        }
+
+        // 处理 tEXt 数据块：关键字 + 空字符 + Latin-1 文本
+        if typ == b"tEXt" {
+            if let Some(key_end) = data.iter().position(|&b| b == 0) {
+                add_text_chunk(&mut found, "tEXt", &data[..key_end], None, data[key_end + 1..].to_vec())?;
+            }
+        }
+
+        // 处理 zTXt 数据块：关键字 + 空字符 + 压缩方法（只有 0 = zlib）+ 压缩文本
+        if typ == b"zTXt" {
+            if let Some(key_end) = data.iter().position(|&b| b == 0) {
+                if data.len() > key_end + 1 {
+                    if data[key_end + 1] != 0 {
+                        return Err(Error::Decompression(format!("zTXt {} {}", unsupported_compression(), data[key_end + 1])));
+                    }
+                    let text = decompress_zlib(&data[key_end + 2..])?;
+                    add_text_chunk(&mut found, "zTXt", &data[..key_end], None, text)?;
+                }
+            }
+        }
+
+        // 处理 iTXt 数据块（包含 XMP 或 EXIF 文本数据）
+        // iTXt 数据块的格式结构如下：
+        //     Keyword (关键字): 一个以空字符 (\0) 结尾的 ASCII 字符串。
+        //     Null separator (空字符分隔符): 一个字节，值为 0。
+        //     Compression flag (压缩标志): 一个字节，0 表示未压缩，1 表示使用 zlib 压缩。
+        //     Compression method (压缩方法): 一个字节，通常为 0。
+        //     Language tag (语言标签)
+        //     Null separator
+        //     Translated keyword (翻译后的关键字)
+        //     Null separator
+        //     Text (文本内容)
+        if typ == b"iTXt" { // 如果数据块类型是 iTXt，则解析其内容
+            // Some 只有当key_end又值才会执行以下部分
+            if let Some(key_end) = data.iter().position(|&b| b == 0) { // 查找key_end关键字结束符"\0"
+                if data.len() > key_end + 2 {
+                    let compression_flag = data[key_end + 1]; // 获取压缩标志（0 表示未压缩，1 表示 zlib 压缩）
+                    let rest = &data[key_end + 3..]; // 跳过关键字、空字符和压缩标志
+                    if let Some(lang_end_rel) = rest.iter().position(|&b| b == 0) { // 查找语言标签结束位置
+                        let translated_start = key_end + 3 + lang_end_rel + 1; // 翻译后的关键字起始位置
+                        if translated_start <= data.len() { // 确保翻译后的关键字存在
+                            if let Some(trans_end_rel) = data[translated_start..].iter().position(|&b| b == 0) { // 查找翻译后的关键字结束位置
+                                let text_start = translated_start + trans_end_rel + 1; // 文本内容起始位置
+                                if text_start <= data.len() { // 确保文本内容存在
+
+                                    // 获取关键字
+                                    let key = &data[..key_end];
+                                    // key 为 ASCII码，转换字符串为 XML:com.adobe.xmp
+                                    // println!("{}", std::str::from_utf8(key).unwrap_or("无效的 UTF-8"));
+                                    
+                                    // println!("{}", std::str::from_utf8(data).unwrap_or("无效的 UTF-8"));
+                                    
+                                    let language = String::from_utf8_lossy(&rest[..lang_end_rel]).to_string();
+                                    let text_bytes = &data[text_start..];
+                                    let text = if compression_flag == 1 {
+                                        decompress_zlib(text_bytes)?
+                                    } else {
+                                        text_bytes.to_vec()
+                                    };
+                                    add_text_chunk(&mut found, "iTXt", key, Some(language).filter(|l| !l.is_empty()), text)?;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+/// 处理一个文本数据块：携带 EXIF / XMP / IPTC 的关键字解析为二进制数据，其余保留为文本
+///
+/// # 参数
+/// - `found`: 已找到的元数据
+/// - `chunk`: 数据块类型
+/// - `key`: 关键字（Latin-1）
+/// - `language`: iTXt 的语言标签
+/// - `text`: 已解压的文本内容（tEXt / zTXt 为 Latin-1，iTXt 为 UTF-8）
+fn add_text_chunk(
+    found: &mut PngMetadataChunks,
+    chunk: &'static str,
+    key: &[u8],
+    language: Option<String>,
+    text: Vec<u8>,
+) -> Result<(), Error> {
+    if is_metadata_keyword(key) {
+        return apply_text_entry(found, key, text);
+    }
+    let text = if chunk == "iTXt" {
+        String::from_utf8_lossy(&text).to_string()
+    } else {
+        latin1_to_string(&text)
+    };
+    found.text.push(PngTextEntry { chunk, keyword: latin1_to_string(key), language, text });
+    Ok(())
+}
+
+/// 将 Latin-1（ISO 8859-1）字节解码为字符串
+fn latin1_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// 将 PNG 文本关键字映射为对应的 EXIF 标签
+///
+/// | PNG 关键字 | EXIF 标签 |
+/// | --- | --- |
+/// | Title / Description | ImageDescription（Description 优先） |
+/// | Author | Artist |
+/// | Copyright | Copyright |
+/// | Creation Time | DateTimeOriginal（带时区时另加 OffsetTimeOriginal） |
+/// | Software | Software |
+/// | Source | Model |
+/// | Comment | UserComment |
+///
+/// # 参数
+/// - `entries`: 文本数据块
+/// - `endian`: 标签所属 ImageMetadata 的字节序（没有 eXIf 时为小端），UserComment 的 UCS-2 按此编码
+///
+/// # 返回值
+/// - 映射得到的 EXIF 标签，同一标签只取第一条
+pub fn png_text_to_exif_tags(entries: &[PngTextEntry], endian: &Endian) -> Vec<ExifTag> {
+    let find = |keyword: &str| {
+        entries
+            .iter()
+            .find(|e| e.keyword.eq_ignore_ascii_case(keyword))
+            .map(|e| e.text.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+
+    let mut out = Vec::new();
+    if let Some(t) = find("Description").or_else(|| find("Title")) {
+        out.push(ExifTag::ImageDescription(t));
+    }
+    if let Some(t) = find("Author") {
+        out.push(ExifTag::Artist(t));
+    }
+    if let Some(t) = find("Copyright") {
+        out.push(ExifTag::Copyright(t));
+    }
+    if let Some(t) = find("Creation Time") {
+        let (date, offset) = png_time_to_exif(&t);
+        out.push(ExifTag::DateTimeOriginal(date));
+        if let Some(offset) = offset {
+            out.push(ExifTag::OffsetTimeOriginal(offset));
+        }
+    }
+    if let Some(t) = find("Software") {
+        out.push(ExifTag::Software(t));
+    }
+    if let Some(t) = find("Source") {
+        out.push(ExifTag::Model(t));
+    }
+    if let Some(t) = find("Comment") {
+        // UserComment 前 8 字节是字符编码
+        let mut bytes = b"ASCII\0\0\0".to_vec();
+        if !t.is_ascii() {
+            bytes = b"UNICODE\0".to_vec();
+            bytes.extend(t.encode_utf16().flat_map(|u| match endian {
+                Endian::Big => u.to_be_bytes(),
+                Endian::Little => u.to_le_bytes(),
+            }));
+        } else {
+            bytes.extend_from_slice(t.as_bytes());
+        }
+        out.push(ExifTag::UserComment(bytes));
+    }
+    out
+}
+
+/// 将 PNG Creation Time 转换为 EXIF 日期格式 "YYYY:MM:DD HH:MM:SS"
+///
+/// PNG 规范推荐 RFC 1123 格式（"Tue, 14 May 2024 10:20:30 +0800"），实际文件中也常见 ISO 8601；
+/// 无法识别的格式原样返回
+///
+/// # 返回值
+/// - (EXIF 日期, 时区偏移)，偏移为 EXIF OffsetTime 的 "+08:00" 形式，文本中没有时区时为 None
+fn png_time_to_exif(text: &str) -> (String, Option<String>) {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    let t = text.trim();
+    let digits = |range: std::ops::Range<usize>| t.get(range).filter(|s| s.bytes().all(|c| c.is_ascii_digit()));
+    let sep = |i: usize, allowed: &[u8]| t.as_bytes().get(i).is_some_and(|c| allowed.contains(c));
+
+    // ISO 8601："2024-05-14T10:20:30" 或 "2024-05-14 10:20:30"，后面可以跟小数秒和时区
+    if let (Some(y), Some(mo), Some(d), Some(h), Some(mi), Some(s)) =
+        (digits(0..4), digits(5..7), digits(8..10), digits(11..13), digits(14..16), digits(17..19))
+    {
+        if sep(4, b"-") && sep(7, b"-") && sep(10, b"T ") && sep(13, b":") && sep(16, b":") {
+            let rest = t[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+            return (format!("{}:{}:{} {}:{}:{}", y, mo, d, h, mi, s), parse_utc_offset(rest));
+        }
+    }
+
+    // RFC 1123：可选的星期，日 月 年 时:分[:秒] [时区]
+    let parts: Vec<&str> = t.split(|c: char| c.is_whitespace() || c == ',').filter(|p| !p.is_empty()).collect();
+    let start = if parts.first().is_some_and(|p| p.parse::<u32>().is_err()) { 1 } else { 0 };
+    if let [day, month, year, time, zone @ ..] = &parts[start.min(parts.len())..] {
+        let month = MONTHS.iter().position(|m| month.to_lowercase().starts_with(m));
+        let time = parse_clock(time);
+        if let (Ok(day), Some(month), Ok(year), Some(time)) = (day.parse::<u32>(), month, year.parse::<u32>(), time) {
+            let offset = zone.first().and_then(|z| parse_utc_offset(z));
+            return (format!("{:04}:{:02}:{:02} {}", year, month + 1, day, time), offset);
+        }
+    }
+    (t.to_string(), None)
+}
+
+/// 解析 "HH:MM" 或 "HH:MM:SS"，每段都必须是两位数字
+///
+/// # 返回值
+/// - "HH:MM:SS"，没有秒时补 ":00"
+fn parse_clock(time: &str) -> Option<String> {
+    let fields: Vec<&str> = time.split(':').collect();
+    if !(2..=3).contains(&fields.len()) || !fields.iter().all(|f| f.len() == 2 && f.bytes().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    Some(format!("{}:{}:{}", fields[0], fields[1], fields.get(2).unwrap_or(&"00")))
+}
+
+/// 将 "+0800"、"+08:00"、"Z"、"GMT"、"UT" 等时区写法转换为 EXIF OffsetTime 的 "+08:00"
+fn parse_utc_offset(zone: &str) -> Option<String> {
+    let zone = zone.trim();
+    if ["Z", "UT", "UTC", "GMT"].iter().any(|z| zone.eq_ignore_ascii_case(z)) {
+        return Some("+00:00".to_string());
+    }
+    let sign = zone.chars().next().filter(|c| *c == '+' || *c == '-')?;
+    let body = &zone[1..];
+    let (hours, minutes) = match body.len() {
+        4 => (body.get(0..2)?, body.get(2..4)?),
+        5 if body.as_bytes()[2] == b':' => (body.get(0..2)?, body.get(3..5)?),
+        _ => return None,
+    };
+    if !(hours.bytes().chain(minutes.bytes())).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}{}:{}", sign, hours, minutes))
+}
+
+/// 判断文本数据块关键字是否携带 EXIF / XMP / IPTC 数据
+fn is_metadata_keyword(key: &[u8]) -> bool {
+    key.eq_ignore_ascii_case(b"XML:com.adobe.xmp")
+        || key.eq_ignore_ascii_case(b"exif")
+        || (key.len() > 17 && key[..17].eq_ignore_ascii_case(b"raw profile type "))
+}
+
+/// 处理文本数据块中的一条关键字/文本，按关键字识别 EXIF、XMP 和 IPTC 数据
+///
+/// # 参数
+/// - `found`: 已找到的元数据，每种数据只保留第一次出现的
+/// - `key`: 关键字（ASCII）
+/// - `text`: 已解压的文本内容
+fn apply_text_entry(found: &mut PngMetadataChunks, key: &[u8], text: Vec<u8>) -> Result<(), Error> {
+    // Adobe XMP 数据包原样保留
+    if key.eq_ignore_ascii_case(b"XML:com.adobe.xmp") {
+        if found.xmp.is_none() && !text.is_empty() {
+            found.xmp = Some(text);
+        }
+        return Ok(());
+    }
+
+    // ImageMagick / GIMP 写入的 "Raw profile type xxx" 是十六进制文本，需要先解码
+    let (profile, payload) = if key.len() > 17 && key[..17].eq_ignore_ascii_case(b"raw profile type ") {
+        let profile = String::from_utf8_lossy(&key[17..]).to_lowercase();
+        (profile, decode_raw_profile(&text)?)
+    } else if key.eq_ignore_ascii_case(b"exif") {
+        ("exif".to_string(), text)
+    } else {
+        return Ok(());
+    };
+
+    match profile.as_str() {
+        "exif" if found.exif.is_none() => {
+            found.exif = Some(strip_prefix_bytes(payload, EXIF_HEADER));
+        }
+        "xmp" if found.xmp.is_none() && !payload.is_empty() => {
+            found.xmp = Some(payload);
+        }
+        "iptc" if found.iptc.is_none() => {
+            found.iptc = Some(payload);
+        }
+        // APP1 既可能是 EXIF 也可能是 XMP，按前缀区分
+        "app1" => {
+            if payload.starts_with(EXIF_HEADER) {
+                if found.exif.is_none() {
+                    found.exif = Some(strip_prefix_bytes(payload, EXIF_HEADER));
+                }
+            } else if payload.starts_with(XMP_APP1_HEADER) && found.xmp.is_none() {
+                found.xmp = Some(payload[XMP_APP1_HEADER.len()..].to_vec());
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 去掉字节序列的前缀（如果存在）
+fn strip_prefix_bytes(data: Vec<u8>, prefix: &[u8]) -> Vec<u8> {
+    match data.strip_prefix(prefix) {
+        Some(rest) => rest.to_vec(),
+        None => data,
+    }
+}
+
+/// 解码 ImageMagick 的 "Raw profile" 文本格式
+///
+/// 格式为：换行、配置文件类型、换行、十进制长度、换行，之后是每行 72 个字符的十六进制数据
+///
+/// ```text
+/// \nexif\n    1234\n457869660000...
+/// ```
+///
+/// # 参数
+/// - `text`: 数据块文本
+///
+/// # 返回值
+/// - 解码后的二进制数据；格式错误或十六进制数据不足时返回 Error::Decompression
+pub fn decode_raw_profile(text: &[u8]) -> Result<Vec<u8>, Error> {
+    let bad = |msg: &str| Error::Decompression(format!("Raw profile {}", msg));
+    let mut fields = text.split(|b| b.is_ascii_whitespace()).filter(|f| !f.is_empty());
+
+    // 第一个字段是配置文件类型，第二个是数据长度
+    fields.next().ok_or_else(|| bad(tr("missing type", "缺少类型", "タイプがありません")))?;
+    let length: usize = fields
+        .next()
+        .and_then(|f| std::str::from_utf8(f).ok())
+        .and_then(|f| f.parse().ok())
+        .ok_or_else(|| bad(tr("invalid length", "长度无效", "長さが不正")))?;
+
+    let mut out = Vec::with_capacity(length);
+    let mut high: Option<u8> = None;
+    for &b in fields.flatten() {
+        if out.len() == length {
+            break;
+        }
+        let nibble = (b as char).to_digit(16).ok_or_else(|| bad(tr("contains non-hex characters", "包含非十六进制字符", "16 進数以外の文字を含みます")))? as u8;
+        match high.take() {
+            Some(h) => out.push(h << 4 | nibble),
+            None => high = Some(nibble),
+        }
+    }
+    if out.len() < length {
+        return Err(bad(&format!(
+            "{} ({} {}, {} {})",
+            tr("not enough data", "数据不足", "データ不足"),
+            tr("expected", "需要", "必要"),
+            length,
+            tr("got", "实际", "実際"),
+            out.len()
+        )));
+    }
+    Ok(out)
+}
+
+/// 解压缩 Zlib 数据
+/// 接受一个字节数切片 compressed 作为输入参数
+/// - 成功时返回解压后的 Vec<u8> 字节向量，失败时返回 Error::Decompression 错误
+pub(crate) fn decompress_zlib(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut decoder = ZlibDecoder::new(Cursor::new(compressed));
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out)
+        .map_err(|e| Error::Decompression(e.to_string()))?;
+    Ok(out)
+}
+
+/// PNG 完整性检查中发现的问题
+#[derive(Debug, Clone, PartialEq)]
+pub enum PngIssue {
+    /// 数据块 CRC 与内容不符
+    BadCrc { chunk: String, offset: usize, stored: u32, computed: u32 },
+    /// 数据块头部或内容超出文件结尾
+    Truncated { chunk: String, offset: usize },
+    /// 第一个数据块不是 IHDR
+    IhdrNotFirst { chunk: String },
+    /// 没有 IEND 数据块，offset 为最后一个数据块之后的位置
+    MissingIend { offset: usize },
+    /// IEND 之后还有多余数据
+    DataAfterIend { offset: usize, length: usize },
+    /// 数据块 CRC 正确但内容无法解码（如 iCCP 压缩方法未知或 zlib 数据损坏），只在读取元数据时出现
+    Undecodable { chunk: String, offset: usize, message: String },
+}
+
+impl PngIssue {
+    /// 转换为对应的错误类型，用于决定退出码
+    pub fn to_error(&self) -> Error {
+        match self {
+            PngIssue::BadCrc { chunk, offset, .. } => Error::BadCrc { chunk: chunk.clone(), offset: *offset },
+            PngIssue::Truncated { chunk, offset } => Error::TruncatedChunk { chunk: chunk.clone(), offset: *offset },
+            PngIssue::MissingIend { offset } => Error::TruncatedChunk { chunk: "IEND".to_string(), offset: *offset },
+            PngIssue::Undecodable { message, .. } => Error::Decompression(message.clone()),
+            issue => Error::Integrity(issue.to_string()),
+        }
+    }
+
+    /// 问题类型的机器可读名称
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PngIssue::BadCrc { .. } => "bad_crc",
+            PngIssue::Truncated { .. } => "truncated",
+            PngIssue::IhdrNotFirst { .. } => "ihdr_not_first",
+            PngIssue::MissingIend { .. } => "missing_iend",
+            PngIssue::Undecodable { .. } => "undecodable",
+            PngIssue::DataAfterIend { .. } => "data_after_iend",
+        }
+    }
+}
+
+impl std::fmt::Display for PngIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngIssue::BadCrc { chunk, offset, stored, computed } => write!(
+                f,
+                "{} ({} {:08X}, {} {:08X})",
+                Error::BadCrc { chunk: chunk.clone(), offset: *offset },
+                tr("stored", "记录值", "記録値"),
+                stored,
+                tr("computed", "实际值", "実際の値"),
+                computed
+            ),
+            PngIssue::Truncated { chunk, offset } if chunk.is_empty() => write!(
+                f,
+                "{} ({} {})",
+                tr("chunk header truncated", "数据块头部被截断", "チャンクヘッダーが途切れています"),
+                tr("offset", "偏移", "オフセット"),
+                offset
+            ),
+            PngIssue::Truncated { chunk, offset } => {
+                write!(f, "{}", Error::TruncatedChunk { chunk: chunk.clone(), offset: *offset })
+            }
+            PngIssue::IhdrNotFirst { chunk } => write!(
+                f,
+                "{}: {}",
+                tr("first chunk should be IHDR, found", "第一个数据块应为 IHDR，实际为", "最初のチャンクは IHDR であるべきところ、実際は"),
+                chunk
+            ),
+            PngIssue::MissingIend { offset } => write!(
+                f,
+                "{} ({} {})",
+                tr("missing IEND chunk", "缺少 IEND 数据块", "IEND チャンクがありません"),
+                tr("file ends at offset", "文件结束于偏移", "ファイル終端のオフセット"),
+                offset
+            ),
+            PngIssue::DataAfterIend { offset, length } => write!(
+                f,
+                "{} ({} {}, {} {})",
+                tr("data after IEND", "IEND 之后还有数据", "IEND の後にデータがあります"),
+                tr("offset", "偏移", "オフセット"),
+                offset,
+                length,
+                tr("bytes", "字节", "バイト")
+            ),
+            PngIssue::Undecodable { chunk, offset, message } => write!(
+                f,
+                "{} {} ({} {}) {}: {}",
+                tr("chunk", "数据块", "チャンク"),
+                chunk,
+                tr("offset", "偏移", "オフセット"),
+                offset,
+                tr("could not be decoded and was skipped", "无法解码，已跳过", "を解読できないためスキップしました"),
+                message
+            ),
+        }
+    }
+}
+
+/// PNG 数据块概要
+#[derive(Debug, Clone)]
+pub struct PngChunkInfo {
+    /// 数据块类型
+    pub chunk_type: String,
+    /// 数据块在文件中的起始偏移（长度字段处）
+    pub offset: usize,
+    /// 数据部分长度
+    pub length: usize,
+    /// 文件中记录的 CRC
+    pub crc: u32,
+    /// CRC 是否正确
+    pub crc_ok: bool,
+}
+
+/// PNG 完整性检查报告
+#[derive(Debug, Clone, Default)]
+pub struct PngIntegrityReport {
+    /// 按顺序列出的全部数据块
+    pub chunks: Vec<PngChunkInfo>,
+    /// 发现的问题，为空表示文件完整
+    pub issues: Vec<PngIssue>,
+}
+
+impl PngIntegrityReport {
+    /// 文件是否完整
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// 检查 PNG 文件的完整性：逐个验证数据块 CRC，检测截断、缺少 IEND、IEND 之后的多余数据
+///
+/// 与 [`extract_metadata_from_png`] 不同，元数据块的 CRC 错误也不会停止检查，而是继续检查后续数据块
+///
+/// # 参数
+/// - `path`: PNG 文件路径
+///
+/// # 返回值
+/// - 完整性报告；文件无法读取或签名不是 PNG 时返回 Error
+pub fn verify_png(path: &Path) -> Result<PngIntegrityReport, Error> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    verify_png_bytes(&buf)
+}
+
+/// 检查内存中 PNG 文件的完整性，参见 [`verify_png`]
+///
+/// # 参数
+/// - `buf`: 完整的 PNG 文件内容
+///
+/// # 返回值
+/// - 完整性报告；签名不是 PNG 时返回 Error
+pub fn verify_png_bytes(buf: &[u8]) -> Result<PngIntegrityReport, Error> {
+    if buf.len() < 8 || &buf[..8] != b"\x89PNG\r\n\x1a\n" {
+        return Err(Error::invalid_signature("png"));
+    }
+
+    let mut report = PngIntegrityReport::default();
+    let mut pos = 8usize;
+    let mut seen_iend = false;
+    while pos < buf.len() {
+        let chunk_start = pos;
+        if pos + 8 > buf.len() {
+            report.issues.push(PngIssue::Truncated { chunk: String::new(), offset: chunk_start });
+            break;
+        }
+        let len = u32::from_be_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]]) as usize;
+        let chunk_type = String::from_utf8_lossy(&buf[pos + 4..pos + 8]).to_string();
+        if pos + 12 + len > buf.len() {
+            report.issues.push(PngIssue::Truncated { chunk: chunk_type, offset: chunk_start });
+            break;
+        }
+
+        let crc_pos = pos + 8 + len;
+        let stored = u32::from_be_bytes([buf[crc_pos], buf[crc_pos + 1], buf[crc_pos + 2], buf[crc_pos + 3]]);
+        let computed = crc32fast::hash(&buf[pos + 4..crc_pos]);
+        if stored != computed {
+            report.issues.push(PngIssue::BadCrc { chunk: chunk_type.clone(), offset: chunk_start, stored, computed });
+        }
+        if report.chunks.is_empty() && chunk_type != "IHDR" {
+            report.issues.push(PngIssue::IhdrNotFirst { chunk: chunk_type.clone() });
+        }
+        report.chunks.push(PngChunkInfo { chunk_type: chunk_type.clone(), offset: chunk_start, length: len, crc: stored, crc_ok: stored == computed });
+        pos = crc_pos + 4;
+
+        if chunk_type == "IEND" {
+            seen_iend = true;
+            break;
+        }
+    }
+
+    if seen_iend {
+        if pos < buf.len() {
+            report.issues.push(PngIssue::DataAfterIend { offset: pos, length: buf.len() - pos });
+        }
+    } else if !report.issues.iter().any(|i| matches!(i, PngIssue::Truncated { .. })) {
+        // 截断时 IEND 必然缺失，不再重复报告
+        report.issues.push(PngIssue::MissingIend { offset: pos });
+    }
+    Ok(report)
+}
+
+/// pHYs 数据块：像素物理尺寸
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngPhysicalSize {
+    /// 每单位的水平像素数
+    pub pixels_per_unit_x: u32,
+    /// 每单位的垂直像素数
+    pub pixels_per_unit_y: u32,
+    /// 单位：0 = 未知（只表示宽高比），1 = 米
+    pub unit: u8,
+}
+
+impl PngPhysicalSize {
+    /// 换算为 DPI（水平, 垂直），单位未知时返回 None
+    pub fn dpi(&self) -> Option<(f64, f64)> {
+        (self.unit == 1)
+            .then_some((self.pixels_per_unit_x as f64 * 0.0254, self.pixels_per_unit_y as f64 * 0.0254))
+    }
+}
+
+/// tIME 数据块：最后修改时间（UTC）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl std::fmt::Display for PngTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// cHRM 数据块：白点和三原色的色度坐标（已除以 100000）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngChromaticities {
+    pub white_x: f64,
+    pub white_y: f64,
+    pub red_x: f64,
+    pub red_y: f64,
+    pub green_x: f64,
+    pub green_y: f64,
+    pub blue_x: f64,
+    pub blue_y: f64,
+}
+
+/// iCCP 数据块：嵌入的 ICC 配置文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngIccProfile {
+    /// 配置文件名称
+    pub name: String,
+    /// 已解压的 ICC 配置文件
+    pub profile: Vec<u8>,
+}
+
+/// bKGD 数据块：建议的背景色，形式取决于颜色类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PngBackground {
+    /// 索引颜色图片的调色板下标
+    PaletteIndex(u8),
+    /// 灰度值
+    Gray(u16),
+    /// RGB 值
+    Rgb(u16, u16, u16),
+}
+
+/// fcTL 数据块：APNG 单帧的位置与时长
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PngFrameControl {
+    pub sequence_number: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x_offset: u32,
+    pub y_offset: u32,
+    pub delay_num: u16,
+    pub delay_den: u16,
+    /// 帧结束后的处理方式：0 = 保留，1 = 清为透明，2 = 恢复为上一帧
+    pub dispose_op: u8,
+    /// 混合方式：0 = 覆盖，1 = Alpha 混合
+    pub blend_op: u8,
+}
+
+impl PngFrameControl {
+    /// 帧时长（秒），分母为 0 时按 1/100 秒计算
+    pub fn delay_seconds(&self) -> f64 {
+        let den = if self.delay_den == 0 { 100 } else { self.delay_den };
+        self.delay_num as f64 / den as f64
+    }
+}
+
+/// acTL / fcTL 数据块：APNG 动画信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PngAnimation {
+    /// acTL 声明的帧数
+    pub num_frames: u32,
+    /// 播放次数，0 表示无限循环
+    pub num_plays: u32,
+    /// 各帧的 fcTL 信息
+    pub frames: Vec<PngFrameControl>,
+}
+
+impl PngAnimation {
+    /// 一次播放的总时长（秒）
+    pub fn total_duration(&self) -> f64 {
+        self.frames.iter().map(|f| f.delay_seconds()).sum()
+    }
+}
+
+/// PNG 辅助数据块中解析出的信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PngAncillary {
+    /// pHYs：像素物理尺寸
+    pub physical_size: Option<PngPhysicalSize>,
+    /// tIME：最后修改时间
+    pub modified: Option<PngTime>,
+    /// gAMA：图像伽马值（已除以 100000）
+    pub gamma: Option<f64>,
+    /// cHRM：色度坐标
+    pub chromaticities: Option<PngChromaticities>,
+    /// sRGB：渲染意图
+    pub srgb_intent: Option<u8>,
+    /// iCCP：ICC 配置文件
+    pub icc_profile: Option<PngIccProfile>,
+    /// sBIT：各通道的有效位数
+    pub significant_bits: Option<Vec<u8>>,
+    /// bKGD：背景色
+    pub background: Option<PngBackground>,
+    /// tRNS：透明度数据的字节数，None 表示没有 tRNS
+    pub transparency: Option<usize>,
+    /// acTL / fcTL：APNG 动画信息
+    pub animation: Option<PngAnimation>,
+}
+
+impl PngAncillary {
+    /// 是否没有任何辅助数据块
+    pub fn is_empty(&self) -> bool {
+        *self == PngAncillary::default()
+    }
+
+    /// 解析一个辅助数据块，长度不符的数据块忽略
+    ///
+    /// # 返回值
+    /// - iCCP 配置文件解压失败时返回 Error
+    fn apply_chunk(&mut self, typ: &[u8], data: &[u8]) -> Result<(), Error> {
+        let be32 = |i: usize| u32::from_be_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let be16 = |i: usize| u16::from_be_bytes([data[i], data[i + 1]]);
+        match typ {
+            b"pHYs" if data.len() == 9 => {
+                self.physical_size = Some(PngPhysicalSize {
+                    pixels_per_unit_x: be32(0),
+                    pixels_per_unit_y: be32(4),
+                    unit: data[8],
+                });
+            }
+            b"tIME" if data.len() == 7 => {
+                self.modified = Some(PngTime {
+                    year: be16(0),
+                    month: data[2],
+                    day: data[3],
+                    hour: data[4],
+                    minute: data[5],
+                    second: data[6],
+                });
+            }
+            b"gAMA" if data.len() == 4 => self.gamma = Some(be32(0) as f64 / 100000.0),
+            b"cHRM" if data.len() == 32 => {
+                let v = |i: usize| be32(i * 4) as f64 / 100000.0;
+                self.chromaticities = Some(PngChromaticities {
+                    white_x: v(0),
+                    white_y: v(1),
+                    red_x: v(2),
+                    red_y: v(3),
+                    green_x: v(4),
+                    green_y: v(5),
+                    blue_x: v(6),
+                    blue_y: v(7),
+                });
+            }
+            b"sRGB" if data.len() == 1 => self.srgb_intent = Some(data[0]),
+            // iCCP：名称 + 空字符 + 压缩方法（只有 0 = zlib）+ 压缩的配置文件
+            b"iCCP" => {
+                if let Some(name_end) = data.iter().position(|&b| b == 0) {
+                    if data.len() > name_end + 1 {
+                        if data[name_end + 1] != 0 {
+                            return Err(Error::Decompression(format!("iCCP {} {}", unsupported_compression(), data[name_end + 1])));
+                        }
+                        self.icc_profile = Some(PngIccProfile {
+                            name: latin1_to_string(&data[..name_end]),
+                            profile: decompress_zlib(&data[name_end + 2..])?,
+                        });
+                    }
+                }
+            }
+            b"sBIT" if !data.is_empty() && data.len() <= 4 => self.significant_bits = Some(data.to_vec()),
+            b"bKGD" => {
+                self.background = match data.len() {
+                    1 => Some(PngBackground::PaletteIndex(data[0])),
+                    2 => Some(PngBackground::Gray(be16(0))),
+                    6 => Some(PngBackground::Rgb(be16(0), be16(2), be16(4))),
+                    _ => None,
+                };
+            }
+            b"tRNS" => self.transparency = Some(data.len()),
+            b"acTL" if data.len() == 8 => {
+                let animation = self.animation.get_or_insert_with(PngAnimation::default);
+                animation.num_frames = be32(0);
+                animation.num_plays = be32(4);
+            }
+            b"fcTL" if data.len() == 26 => {
+                self.animation.get_or_insert_with(PngAnimation::default).frames.push(PngFrameControl {
+                    sequence_number: be32(0),
+                    width: be32(4),
+                    height: be32(8),
+                    x_offset: be32(12),
+                    y_offset: be32(16),
+                    delay_num: be16(20),
+                    delay_den: be16(22),
+                    dispose_op: data[24],
+                    blend_op: data[25],
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// zTXt、iCCP 中不受支持的压缩方法的说明
+fn unsupported_compression() -> &'static str {
+    tr("unsupported compression method", "不受支持的压缩方法", "未対応の圧縮方式")
+}
+
+/// 获取 sRGB 渲染意图在当前语言下的名称
+pub fn png_srgb_intent_name(intent: u8) -> &'static str {
+    match intent {
+        0 => tr("Perceptual", "感知", "知覚的"),
+        1 => tr("Relative Colorimetric", "相对色度", "相対的な色域を維持"),
+        2 => tr("Saturation", "饱和度", "彩度"),
+        3 => tr("Absolute Colorimetric", "绝对色度", "絶対的な色域を維持"),
+        _ => tr("Unknown", "未知", "不明"),
+    }
+}
+
+/// PNG IHDR 数据块信息
+#[derive(Debug, Clone)]
+pub struct PngIhdr {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: u8,
+    pub compression_method: u8,
+    pub filter_method: u8,
+    pub interlace_method: u8,
+}
+
+/// 读取 PNG 文件的 IHDR 数据块
+///
+/// # 参数
+/// - `path`: PNG 文件路径
+///
+/// # 返回值
+/// - 如果文件是合法的 PNG 且 IHDR 完整则返回 Some(PngIhdr)，否则返回 None
+pub fn read_png_ihdr(path: &Path) -> Option<PngIhdr> {
+    // 签名(8) + 长度(4) + 类型(4) + IHDR 数据(13)
+    let mut head = [0u8; 29];
+    File::open(path).ok()?.read_exact(&mut head).ok()?;
+    if &head[..8] != b"\x89PNG\r\n\x1a\n" || &head[12..16] != b"IHDR" {
+        return None;
+    }
+    let data = &head[16..];
+    Some(PngIhdr {
+        width: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+        height: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+        bit_depth: data[8],
+        color_type: data[9],
+        compression_method: data[10],
+        filter_method: data[11],
+        interlace_method: data[12],
+    })
+}
+
+/// 获取 PNG 颜色类型在当前语言下的名称
+pub fn png_color_type_name(color_type: u8) -> &'static str {
+    match color_type {
+        0 => tr("Grayscale", "灰度", "グレースケール"),
+        2 => tr("RGB", "真彩色", "トゥルーカラー"),
+        3 => tr("Palette", "索引颜色", "インデックスカラー"),
+        4 => tr("Grayscale with Alpha", "灰度+Alpha", "グレースケール+アルファ"),
+        6 => tr("RGB with Alpha", "真彩色+Alpha", "トゥルーカラー+アルファ"),
+        _ => tr("Unknown", "未知", "不明"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creation_time_iso8601() {
+        assert_eq!(png_time_to_exif("2024-05-14T10:20:30"), ("2024:05:14 10:20:30".to_string(), None));
+        assert_eq!(
+            png_time_to_exif("2024-05-14 10:20:30.25+08:00"),
+            ("2024:05:14 10:20:30".to_string(), Some("+08:00".to_string()))
+        );
+        assert_eq!(png_time_to_exif("2024-05-14T10:20:30Z").1.as_deref(), Some("+00:00"));
+    }
+
+    #[test]
+    fn creation_time_rfc1123() {
+        assert_eq!(
+            png_time_to_exif("Tue, 14 May 2024 10:20:30 +0800"),
+            ("2024:05:14 10:20:30".to_string(), Some("+08:00".to_string()))
+        );
+        assert_eq!(png_time_to_exif("14 May 2024 10:20 GMT"), ("2024:05:14 10:20:00".to_string(), Some("+00:00".to_string())));
+        assert_eq!(png_time_to_exif("14 May 2024 10:20:30"), ("2024:05:14 10:20:30".to_string(), None));
+    }
+
+    #[test]
+    fn creation_time_malformed_is_returned_unchanged() {
+        // 第 19 字节落在多字节字符中间，不能按字节切片
+        assert_eq!(png_time_to_exif("2024-05-14T10:20:3é").0, "2024-05-14T10:20:3é");
+        assert_eq!(png_time_to_exif("2024-05-14T1a:20:30").0, "2024-05-14T1a:20:30");
+        assert_eq!(png_time_to_exif("14 May 2024 1:2:3").0, "14 May 2024 1:2:3");
+        assert_eq!(png_time_to_exif("14 May 2024 ab:cd:ef").0, "14 May 2024 ab:cd:ef");
+        assert_eq!(png_time_to_exif("14 May 2024 10:20:30 +8").1, None);
+    }
+
+    #[test]
+    fn comment_round_trip_uses_metadata_endian() {
+        let entries = [PngTextEntry { chunk: "iTXt", keyword: "Comment".to_string(), language: None, text: "中文注释 ok".to_string() }];
+        for endian in [Endian::Little, Endian::Big] {
+            let tags = png_text_to_exif_tags(&entries, &endian);
+            let comment = tags.iter().find(|t| matches!(t, ExifTag::UserComment(_))).unwrap();
+            assert_eq!(crate::tags::get_tag_value_string(comment, &endian), "中文注释 ok");
+        }
+        // 没有 eXIf 时 ImageMetadata 为小端
+        let tags = png_text_to_exif_tags(&entries, &Endian::Little);
+        assert!(matches!(&tags[0], ExifTag::UserComment(v) if v[8..10] == [0x2d, 0x4e]));
+
+        let ascii = [PngTextEntry { chunk: "tEXt", keyword: "comment".to_string(), language: None, text: " plain ".to_string() }];
+        assert!(matches!(&png_text_to_exif_tags(&ascii, &Endian::Big)[0], ExifTag::UserComment(v) if v == b"ASCII\0\0\0plain"));
+    }
+
+    #[test]
+    fn raw_profile_hex_decoding() {
+        // ImageMagick 的格式：类型、十进制长度，随后是每行 72 个字符的十六进制数据
+        let text = b"\nexif\n       6\n45786966\n0000\n";
+        assert_eq!(decode_raw_profile(text).unwrap(), b"Exif\0\0");
+        // 大小写混合，多余的数据被忽略
+        assert_eq!(decode_raw_profile(b"iptc 2 aBcD ef").unwrap(), [0xab, 0xcd]);
+        assert_eq!(decode_raw_profile(b"xmp 0").unwrap(), b"");
+    }
+
+    #[test]
+    fn raw_profile_malformed() {
+        assert!(decode_raw_profile(b"").is_err());
+        assert!(decode_raw_profile(b"exif").is_err());
+        assert!(decode_raw_profile(b"exif -1 00").is_err());
+        assert!(decode_raw_profile(b"exif six 00").is_err());
+        assert!(decode_raw_profile(b"exif 2 0g00").is_err());
+        // 数据不足，包括只剩半个字节的情况
+        assert!(decode_raw_profile(b"exif 3 0000").is_err());
+        assert!(decode_raw_profile(b"exif 2 000").is_err());
+    }
+
+    /// 构造一个数据块：长度、类型、数据和 CRC
+    fn chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut out = (data.len() as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(chunk_type);
+        out.extend_from_slice(data);
+        out.extend_from_slice(&crc32fast::hash(&out[4..]).to_be_bytes());
+        out
+    }
+
+    /// 最小的 1×1 灰度 PNG，各数据块依次为 IHDR、IDAT、IEND
+    fn minimal_png() -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0, 0, 0]));
+        png.extend(chunk(b"IDAT", &[0x78, 0x9c, 0x63, 0x60, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01]));
+        png.extend(chunk(b"IEND", &[]));
+        png
+    }
+
+    #[test]
+    fn verify_intact_png() {
+        let report = verify_png_bytes(&minimal_png()).unwrap();
+        assert!(report.is_ok(), "{:?}", report.issues);
+        let types: Vec<_> = report.chunks.iter().map(|c| c.chunk_type.as_str()).collect();
+        assert_eq!(types, ["IHDR", "IDAT", "IEND"]);
+        assert_eq!(report.chunks[1].offset, 8 + 25);
+    }
+
+    #[test]
+    fn verify_bad_crc_continues() {
+        let mut png = minimal_png();
+        // IHDR 数据部分的第一个字节
+        png[16] ^= 0xff;
+        let report = verify_png_bytes(&png).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::BadCrc { chunk, offset: 8, stored, computed }]
+            if chunk == "IHDR" && stored != computed));
+        assert!(!report.chunks[0].crc_ok);
+        // CRC 错误之后仍然检查后续数据块
+        assert_eq!(report.chunks.len(), 3);
+        assert!(report.chunks[1].crc_ok && report.chunks[2].crc_ok);
+    }
+
+    #[test]
+    fn verify_missing_iend() {
+        let mut png = minimal_png();
+        png.truncate(png.len() - 12);
+        let len = png.len();
+        let report = verify_png_bytes(&png).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::MissingIend { offset }] if *offset == len));
+        assert_eq!(report.chunks.len(), 2);
+    }
+
+    #[test]
+    fn verify_data_after_iend() {
+        let mut png = minimal_png();
+        let len = png.len();
+        png.extend_from_slice(b"trailer");
+        let report = verify_png_bytes(&png).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::DataAfterIend { offset, length: 7 }] if *offset == len));
+    }
+
+    #[test]
+    fn verify_truncation() {
+        let png = minimal_png();
+        // 截断在 IDAT 数据中间
+        let report = verify_png_bytes(&png[..8 + 25 + 12]).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::Truncated { chunk, offset: 33 }] if chunk == "IDAT"));
+        // 截断在数据块头部中间，不再重复报告缺少 IEND
+        let report = verify_png_bytes(&png[..8 + 25 + 4]).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::Truncated { chunk, offset: 33 }] if chunk.is_empty()));
+        assert_eq!(report.chunks.len(), 1);
+    }
+
+    #[test]
+    fn verify_ihdr_not_first_and_bad_signature() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(chunk(b"IEND", &[]));
+        let report = verify_png_bytes(&png).unwrap();
+        assert!(matches!(&report.issues[..], [PngIssue::IhdrNotFirst { chunk }] if chunk == "IEND"));
+
+        assert!(verify_png_bytes(b"GIF89a").is_err());
+    }
+
+    /// 在 IEND 之前插入数据块，并按需破坏它的 CRC
+    fn png_with(chunks: &[(&[u8; 4], &[u8], bool)]) -> Vec<u8> {
+        let mut png = minimal_png();
+        let iend = png.split_off(png.len() - 12);
+        for &(chunk_type, data, corrupt) in chunks {
+            let mut c = chunk(chunk_type, data);
+            if corrupt {
+                *c.last_mut().unwrap() ^= 0xff;
+            }
+            png.extend(c);
+        }
+        png.extend(iend);
+        png
+    }
+
+
+    #[test]
+    fn read_skips_bad_crc_in_other_chunks() {
+        let png = png_with(&[
+            (b"gAMA", &45455u32.to_be_bytes(), true),
+            (b"tEXt", b"Title\0Harbour", false),
+            (b"eXIf", b"II\x2a\0\x08\0\0\0\0\0\0\0\0\0", false),
+        ]);
+        let found = extract_metadata_from_png_bytes(&png).unwrap();
+        // 损坏的 gAMA 被跳过，后面的数据块照常读取
+        assert_eq!(found.ancillary.gamma, None);
+        assert!(matches!(&found.issues[..], [PngIssue::BadCrc { chunk, offset: 55, .. }] if chunk == "gAMA"));
+        assert_eq!(found.text.len(), 1);
+        assert_eq!(found.text[0].text, "Harbour");
+        assert!(found.exif.is_some());
+
+        // IEND 的 CRC 错误同样只记录
+        let mut png = minimal_png();
+        *png.last_mut().unwrap() ^= 0xff;
+        let found = extract_metadata_from_png_bytes(&png).unwrap();
+        assert!(matches!(&found.issues[..], [PngIssue::BadCrc { chunk, .. }] if chunk == "IEND"));
+    }
+
+    #[test]
+    fn read_skips_undecodable_iccp() {
+        // 未知的压缩方法、损坏的 zlib 数据
+        for iccp in [&b"sRGB\0\x01\x78\x9c"[..], &b"sRGB\0\0\x78\x9c\xff\xff"[..]] {
+            let png = png_with(&[(b"iCCP", iccp, false), (b"tEXt", b"Author\0Jo", false)]);
+            let found = extract_metadata_from_png_bytes(&png).unwrap();
+            assert!(found.ancillary.icc_profile.is_none());
+            assert!(matches!(&found.issues[..], [PngIssue::Undecodable { chunk, offset: 55, .. }] if chunk == "iCCP"));
+            assert_eq!(found.text[0].keyword, "Author");
+            assert_eq!(found.text[0].text, "Jo");
+        }
+    }
+
+    #[test]
+    fn read_fails_on_bad_crc_in_metadata_chunks() {
+        for chunk_type in [b"eXIf", b"tEXt", b"zTXt", b"iTXt"] {
+            let png = png_with(&[(chunk_type, b"Comment\0\0\0\0\0", true)]);
+            let result = extract_metadata_from_png_bytes(&png);
+            assert!(
+                matches!(&result, Err(Error::BadCrc { chunk, offset: 55 }) if chunk.as_bytes() == chunk_type),
+                "{:?}",
+                result
+            );
+        }
+    }
+}
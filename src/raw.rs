@@ -0,0 +1,384 @@
+use std::{
+    fs::File,
+    path::Path,
+    io::Read,
+};
+
+use little_exif::{
+    endian::Endian,
+    exif_tag::ExifTag,
+    ifd::ExifTagGroup,
+};
+
+use crate::{
+    error::Error,
+    heif::parse_boxes,
+    makernote::maker_note_vendor,
+    tiff::{self, TiffEntry, TiffInfo},
+};
+
+/// Olympus ORF 的魔数（"IIRO" / "MMOR"）
+pub const ORF_MAGIC: u16 = 0x4f52;
+/// 部分 Olympus 机型 ORF 的魔数（"IIRS"）
+pub const ORF_MAGIC_RS: u16 = 0x5352;
+/// Panasonic RW2 的魔数（"IIU\0"）
+pub const RW2_MAGIC: u16 = 0x0055;
+
+/// MakerNote 标签
+pub const TAG_MAKER_NOTE: u16 = 0x927c;
+
+/// Fujifilm RAF 文件签名
+const RAF_SIG: &[u8] = b"FUJIFILMCCD-RAW ";
+/// CR3 moov 中存放 CMT1–CMT4、THMB 的 uuid 盒子
+const CR3_META_UUID: [u8; 16] = [
+    0x85, 0xc0, 0xb6, 0x87, 0x82, 0x0f, 0x11, 0xe0, 0x81, 0x11, 0xf4, 0xce, 0x46, 0x2b, 0x6a, 0x48,
+];
+/// CR3 顶层存放 PRVW 预览图的 uuid 盒子
+const CR3_PREVIEW_UUID: [u8; 16] = [
+    0xea, 0xf4, 0x2b, 0x5e, 0x1c, 0x98, 0x4b, 0x88, 0xb9, 0xfb, 0xb7, 0xdc, 0x40, 0x6e, 0x4d, 0x16,
+];
+/// CR3 顶层存放 XMP 数据包的 uuid 盒子
+const CR3_XMP_UUID: [u8; 16] = [
+    0xbe, 0x7a, 0xcf, 0xcb, 0x97, 0xa9, 0x42, 0xe8, 0x9c, 0x71, 0x99, 0x94, 0x91, 0xe3, 0xaf, 0xac,
+];
+/// APP1 段中 XMP 数据包的前缀
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// MakerNote 数据块的位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct MakerNoteBlock {
+    /// 根据头部签名或 Make 标签判断出的厂商，如 "Nikon"、"Canon"
+    pub vendor: Option<&'static str>,
+    /// 在文件中的偏移
+    pub file_offset: u64,
+    /// 在所属 TIFF 数据中的偏移（Canon 等 MakerNote 内部的偏移相对于 TIFF 头）
+    pub tiff_offset: u64,
+    /// MakerNote 原始数据
+    pub data: Vec<u8>,
+}
+
+/// RAW 文件中内嵌的一张预览图
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawPreview {
+    /// 预览图所在位置："IFD0"、"SubIFD0"、"JpgFromRaw"、"THMB"、"PRVW"、"JPEG" 等
+    pub source: String,
+    /// 在文件中的偏移
+    pub offset: u64,
+    /// 字节数
+    pub length: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// 相机 RAW 文件信息
+#[derive(Debug, Clone)]
+pub struct RawInfo {
+    /// 格式名称："NEF"、"CR2"、"CR3"、"ARW"、"ORF"、"RW2"、"PEF"、"RAF" 等
+    pub format: String,
+    /// RAF 头部中的相机型号
+    pub camera: Option<String>,
+    /// EXIF 所在 TIFF 结构在文件中的偏移
+    pub tiff_offset: u64,
+    /// 解析后的 TIFF 结构；CR3 由 CMT1、CMT2、CMT4 合并而成，各 IFD 的偏移相对于各自的 CMT 盒子
+    pub tiff: Option<TiffInfo>,
+    /// MakerNote 数据块
+    pub maker_note: Option<MakerNoteBlock>,
+    /// 内嵌的预览图
+    pub previews: Vec<RawPreview>,
+}
+
+/// RAW 文件中找到的元数据
+#[derive(Debug, Clone)]
+pub struct RawMetadataChunks {
+    /// XMP 数据包
+    pub xmp: Option<Vec<u8>>,
+    /// 文件信息
+    pub info: RawInfo,
+}
+
+/// 读取相机 RAW 文件，提取 EXIF、MakerNote 数据块和预览图位置，只读取文件
+///
+/// 基于 TIFF 的格式（NEF、NRW、CR2、ARW、SR2、ORF、RW2、PEF）直接遍历 IFD，
+/// RAF 读取其内嵌 JPEG 中的 EXIF，CR3 读取 ISOBMFF 容器中的 CMT 盒子
+///
+/// # 参数
+/// - `path`: RAW 文件路径
+/// - `extension`: 小写的文件扩展名
+///
+/// # 返回值
+/// - 找到的元数据；RAF 内嵌 JPEG 中没有 EXIF 时 tiff 为 None
+/// - 文件无法读取、签名错误或结构损坏时返回 Error
+pub fn extract_metadata_from_raw(path: &Path, extension: &str) -> Result<RawMetadataChunks, Error> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+
+    let mut found = RawMetadataChunks {
+        xmp: None,
+        info: RawInfo {
+            format: extension.to_uppercase(),
+            camera: None,
+            tiff_offset: 0,
+            tiff: None,
+            maker_note: None,
+            previews: Vec::new(),
+        },
+    };
+    match extension {
+        "raf" => read_raf(&mut found, &buf)?,
+        "cr3" => read_cr3(&mut found, &buf)?,
+        _ => {
+            let info = tiff::parse_tiff_with_magic(&buf, &[ORF_MAGIC, ORF_MAGIC_RS, RW2_MAGIC])?;
+            collect_from_tiff(&mut found.info, &buf, 0, &info);
+            found.xmp = info.xmp_packet();
+            found.info.tiff = Some(info);
+        }
+    }
+    Ok(found)
+}
+
+/// 读取条目的第一个整数值
+fn entry_u64(data: &[u8], endian: &Endian, entry: &TiffEntry) -> Option<u64> {
+    let pos = entry.value_offset;
+    let size = match entry.type_code {
+        3 => 2,
+        4 | 13 => 4,
+        16 => 8,
+        _ => return None,
+    };
+    let b = data.get(pos..pos.checked_add(size)?)?;
+    let mut bytes = [0u8; 8];
+    match endian {
+        Endian::Little => bytes[..size].copy_from_slice(b),
+        Endian::Big => {
+            bytes[..size].copy_from_slice(b);
+            bytes[..size].reverse();
+        }
+    }
+    Some(u64::from_le_bytes(bytes))
+}
+
+/// 从 TIFF 结构中找出 MakerNote 和预览图
+///
+/// # 参数
+/// - `raw`: 要填充的 RAW 信息
+/// - `data`: TIFF 数据
+/// - `base`: TIFF 数据在文件中的偏移
+/// - `info`: 解析后的 TIFF 结构
+fn collect_from_tiff(raw: &mut RawInfo, data: &[u8], base: u64, info: &TiffInfo) {
+    raw.tiff_offset = base;
+    let endian = &info.endian;
+
+    let make = info.ifd("IFD0").and_then(|ifd| ifd.find(0x010f)).and_then(|e| match &e.tag {
+        ExifTag::Make(s) => Some(s.trim_end_matches('\0').to_string()),
+        _ => None,
+    });
+    if let Some(entry) = info.ifd("ExifIFD").and_then(|ifd| ifd.find(TAG_MAKER_NOTE)) {
+        let start = entry.value_offset;
+        if let Some(note) = usize::try_from(entry.count).ok().and_then(|n| data.get(start..start.checked_add(n)?)) {
+            raw.maker_note = Some(MakerNoteBlock {
+                vendor: maker_note_vendor(note, make.as_deref()),
+                file_offset: base + start as u64,
+                tiff_offset: start as u64,
+                data: note.to_vec(),
+            });
+        }
+    }
+
+    for ifd in &info.ifds {
+        let value = |tag_id: u16| ifd.find(tag_id).and_then(|e| entry_u64(data, endian, e));
+        let mut push = |source: &str, offset: u64, length: u64, width: Option<u64>, height: Option<u64>| {
+            // 超出文件范围的预览图不记录
+            if length > 0 && offset.checked_add(length).is_some_and(|end| end <= data.len() as u64) {
+                raw.previews.push(RawPreview {
+                    source: source.to_string(),
+                    offset: base + offset,
+                    length,
+                    width: width.map(|w| w as u32),
+                    height: height.map(|h| h as u32),
+                });
+            }
+        };
+
+        // JPEGInterchangeFormat / JPEGInterchangeFormatLength（IFD1 缩略图、NEF / ARW 预览图）
+        if let (Some(offset), Some(length)) = (value(0x0201), value(0x0202)) {
+            push(&ifd.name, offset, length, None, None);
+        }
+        // 单条带 JPEG 压缩的图像（CR2 IFD0 预览图）；CFA 数据和 CR2 的无损 JPEG 原始数据（带 CR2Slice 标签）除外
+        let compression = value(0x0103);
+        let photometric = value(0x0106);
+        let strip = ifd.find(0x0111).zip(ifd.find(0x0117)).filter(|(o, c)| o.count == 1 && c.count == 1);
+        if matches!(compression, Some(6) | Some(7)) && photometric != Some(32803) && ifd.find(0xc640).is_none() {
+            if let Some((offsets, counts)) = strip {
+                if let (Some(offset), Some(length)) = (entry_u64(data, endian, offsets), entry_u64(data, endian, counts)) {
+                    push(&ifd.name, offset, length, value(0x0100), value(0x0101));
+                }
+            }
+        }
+        // RW2 的 JpgFromRaw（0x002E）直接存放完整的 JPEG
+        if info.magic == RW2_MAGIC && ifd.name == "IFD0" {
+            if let Some(entry) = ifd.find(0x002e) {
+                push("JpgFromRaw", entry.value_offset as u64, entry.count, None, None);
+            }
+        }
+    }
+}
+
+/// 在 JPEG 的 APP1 段中查找 EXIF 和 XMP
+///
+/// # 返回值
+/// - (TIFF 结构在 JPEG 中的偏移和数据, XMP 数据包)
+#[allow(clippy::type_complexity)]
+pub(crate) fn find_jpeg_app1(jpeg: &[u8]) -> (Option<(usize, &[u8])>, Option<&[u8]>) {
+    let (mut exif, mut xmp) = (None, None);
+    if !jpeg.starts_with(&[0xff, 0xd8]) {
+        return (exif, xmp);
+    }
+    let mut pos = 2usize;
+    while pos + 4 <= jpeg.len() && jpeg[pos] == 0xff {
+        let marker = jpeg[pos + 1];
+        // 图像数据开始或文件结束，后面不再有元数据段
+        if marker == 0xda || marker == 0xd9 {
+            break;
+        }
+        let len = u16::from_be_bytes([jpeg[pos + 2], jpeg[pos + 3]]) as usize;
+        let segment = match jpeg.get(pos + 4..pos + 2 + len.max(2)) {
+            Some(s) => s,
+            None => break,
+        };
+        if marker == 0xe1 {
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0".as_slice()) {
+                exif = exif.or(Some((pos + 10, tiff)));
+            } else if let Some(packet) = segment.strip_prefix(XMP_HEADER) {
+                xmp = xmp.or(Some(packet));
+            }
+        }
+        pos += 2 + len;
+    }
+    (exif, xmp)
+}
+
+/// 读取 Fujifilm RAF：头部中的相机型号和内嵌 JPEG，EXIF 在 JPEG 的 APP1 段中
+fn read_raf(found: &mut RawMetadataChunks, buf: &[u8]) -> Result<(), Error> {
+    if !buf.starts_with(RAF_SIG) {
+        return Err(Error::invalid_signature("raf"));
+    }
+    if buf.len() < 92 {
+        return Err(Error::TruncatedChunk { chunk: "RAF header".to_string(), offset: 0 });
+    }
+    let camera = &buf[0x1c..0x3c];
+    let camera = &camera[..camera.iter().position(|&b| b == 0).unwrap_or(camera.len())];
+    found.info.camera = Some(String::from_utf8_lossy(camera).trim().to_string());
+
+    // 偏移 84：内嵌 JPEG 的偏移和长度（大端）
+    let jpeg_offset = u32::from_be_bytes([buf[84], buf[85], buf[86], buf[87]]) as usize;
+    let jpeg_length = u32::from_be_bytes([buf[88], buf[89], buf[90], buf[91]]) as usize;
+    let jpeg = buf
+        .get(jpeg_offset..jpeg_offset.saturating_add(jpeg_length))
+        .ok_or(Error::TruncatedChunk { chunk: "JPEG".to_string(), offset: jpeg_offset })?;
+    found.info.previews.push(RawPreview {
+        source: "JPEG".to_string(),
+        offset: jpeg_offset as u64,
+        length: jpeg_length as u64,
+        width: None,
+        height: None,
+    });
+
+    let (exif, xmp) = find_jpeg_app1(jpeg);
+    found.xmp = xmp.map(|x| x.to_vec());
+    if let Some((pos, data)) = exif {
+        let info = tiff::parse_tiff(data)?;
+        collect_from_tiff(&mut found.info, data, (jpeg_offset + pos) as u64, &info);
+        found.info.tiff = Some(info);
+    }
+    Ok(())
+}
+
+/// 读取 Canon CR3：moov 中 uuid 盒子里的 CMT1（IFD0）、CMT2（Exif）、CMT3（MakerNote）、
+/// CMT4（GPS）和 THMB 缩略图，以及顶层 uuid 盒子里的 PRVW 预览图和 XMP
+fn read_cr3(found: &mut RawMetadataChunks, buf: &[u8]) -> Result<(), Error> {
+    if buf.len() < 12 || &buf[4..8] != b"ftyp" || &buf[8..12] != b"crx " {
+        return Err(Error::invalid_signature("cr3"));
+    }
+
+    let mut merged: Option<TiffInfo> = None;
+    let mut extra_ifds = Vec::new();
+    for b in parse_boxes(buf, 0)? {
+        match &b.box_type {
+            b"moov" => {
+                for c in parse_boxes(b.body, b.body_offset)? {
+                    if &c.box_type != b"uuid" || !c.body.starts_with(&CR3_META_UUID) {
+                        continue;
+                    }
+                    for d in parse_boxes(&c.body[16..], c.body_offset + 16)? {
+                        match &d.box_type {
+                            b"CMT1" => {
+                                found.info.tiff_offset = d.body_offset as u64;
+                                merged = Some(tiff::parse_tiff(d.body)?);
+                            }
+                            b"CMT2" => extra_ifds.extend(tiff::parse_tiff_as(d.body, "ExifIFD", ExifTagGroup::EXIF)?.ifds),
+                            b"CMT4" => extra_ifds.extend(tiff::parse_tiff_as(d.body, "GPS", ExifTagGroup::GPS)?.ifds),
+                            // CMT3 本身就是一个完整的 TIFF 结构
+                            b"CMT3" => {
+                                found.info.maker_note = Some(MakerNoteBlock {
+                                    vendor: Some("Canon"),
+                                    file_offset: d.body_offset as u64,
+                                    tiff_offset: 0,
+                                    data: d.body.to_vec(),
+                                });
+                            }
+                            // THMB：4 字节版本和标志 + 宽 + 高 + JPEG 长度 + 4 字节未知 + JPEG
+                            b"THMB" => {
+                                let preview = read_cr3_preview("THMB", d.body, d.body_offset, 4, 8)
+                                    .ok_or_else(|| d.truncated())?;
+                                found.info.previews.push(preview);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            b"uuid" if b.body.starts_with(&CR3_PREVIEW_UUID) => {
+                // uuid 之后是 8 字节未知数据，然后是 PRVW 盒子
+                let inner = b.body.get(24..).ok_or_else(|| b.truncated())?;
+                for d in parse_boxes(inner, b.body_offset + 24)? {
+                    if &d.box_type == b"PRVW" {
+                        // PRVW：4 字节未知 + 2 字节未知 + 宽 + 高 + 2 字节未知 + JPEG 长度 + JPEG
+                        let preview = read_cr3_preview("PRVW", d.body, d.body_offset, 6, 12)
+                            .ok_or_else(|| d.truncated())?;
+                        found.info.previews.push(preview);
+                    }
+                }
+            }
+            b"uuid" if b.body.starts_with(&CR3_XMP_UUID) && found.xmp.is_none() => {
+                found.xmp = Some(b.body[16..].to_vec());
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(mut info) = merged {
+        info.ifds.extend(extra_ifds);
+        found.info.tiff = Some(info);
+    }
+    Ok(())
+}
+
+/// 读取 CR3 的 THMB / PRVW 预览图
+///
+/// # 参数
+/// - `size_pos`: 宽度和高度（大端 16 位）所在位置
+/// - `length_pos`: JPEG 长度（大端 32 位）所在位置，JPEG 紧跟在其后 4 字节对齐的位置（16）
+fn read_cr3_preview(source: &str, body: &[u8], body_offset: usize, size_pos: usize, length_pos: usize) -> Option<RawPreview> {
+    let be16 = |p: usize| body.get(p..p + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+    let width = be16(size_pos)?;
+    let height = be16(size_pos + 2)?;
+    let length = body.get(length_pos..length_pos + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))?;
+    body.get(16..16 + length as usize)?;
+    Some(RawPreview {
+        source: source.to_string(),
+        offset: (body_offset + 16) as u64,
+        length: length as u64,
+        width: Some(width as u32),
+        height: Some(height as u32),
+    })
+}
@@ -0,0 +1,88 @@
+use crate::{
+    locale::tr,
+    makernote::{format_exposure_time, format_f_number, guess_offset_base, read_ifd, IfdEntry, MakerNote, MakerNoteContext},
+};
+
+/// 解码 Samsung Type 2 MakerNote
+///
+/// 不带头部的 IFD；不同机型的偏移有的相对于 MakerNote 开头、有的相对于外层 TIFF 头，
+/// 因此总是根据值的位置推测偏移基准
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: 外层字节序
+///
+/// # 返回值
+/// - 解码出的标签；IFD 损坏时返回 None
+pub fn decode_samsung_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    let endian = ctx.endian.clone();
+    let base = guess_offset_base(data, 0, &endian).unwrap_or(0);
+    let entries = read_ifd(data, 0, &endian, base)?;
+
+    let mut note = MakerNote::new("Samsung", "Samsung Type 2");
+    for e in &entries {
+        decode_entry(&mut note, e);
+    }
+    Some(note)
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry) {
+    let id = e.tag;
+    let ratio = |(n, d): (u32, u32)| (d != 0).then(|| n as f64 / d as f64);
+    match id {
+        0x0001 => note.push(id, "MakerNoteVersion", tr("MakerNote Version", "MakerNote 版本", "MakerNote バージョン"), e.text()),
+        0x0002 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0x1000 => tr("Compact Digital Camera", "小型数码相机", "コンパクトデジタルカメラ"),
+                    0x2000 => tr("High-end NX Camera", "NX 系列相机", "NX シリーズカメラ"),
+                    0x3000 => tr("HXM Video Camera", "HXM 摄像机", "HXM ビデオカメラ"),
+                    0x12000 => tr("Cell Phone", "手机", "携帯電話"),
+                    0x300000 => tr("SMX Video Camera", "SMX 摄像机", "SMX ビデオカメラ"),
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(id, "DeviceType", tr("Device Type", "设备类型", "デバイスタイプ"), value);
+            }
+        }
+        0x0003 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "SamsungModelID", tr("Model ID", "机型 ID", "機種 ID"), format!("0x{:08X}", v));
+            }
+        }
+        0x0043 => {
+            if let Some(t) = e.srationals().first().and_then(|&(n, d)| (d != 0).then(|| n as f64 / d as f64)) {
+                note.push(id, "CameraTemperature", tr("Camera Temperature", "机身温度", "カメラ温度"), format!("{:.1} °C", t));
+            }
+        }
+        0xa001 => note.push(id, "FirmwareName", tr("Firmware Name", "固件名称", "ファームウェア名"), e.text()),
+        0xa002 => note.push(id, "SerialNumber", tr("Serial Number", "序列号", "シリアル番号"), e.text()),
+        0xa013 => {
+            if let Some(ev) = e.srationals().first().and_then(|&(n, d)| (d != 0).then(|| n as f64 / d as f64)) {
+                note.push(id, "ExposureCompensation", tr("Exposure Compensation", "曝光补偿", "露出補正"), format!("{:+.1} EV", ev));
+            }
+        }
+        0xa014 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "ISO", tr("ISO", "ISO 感光度", "ISO 感度"), v.to_string());
+            }
+        }
+        0xa018 => {
+            if let Some(t) = e.rationals().first().copied().and_then(ratio) {
+                note.push(id, "ExposureTime", tr("Exposure Time", "曝光时间", "露出時間"), format_exposure_time(t));
+            }
+        }
+        0xa019 => {
+            if let Some(f) = e.rationals().first().copied().and_then(ratio) {
+                note.push(id, "FNumber", tr("Aperture", "光圈", "絞り値"), format_f_number(f));
+            }
+        }
+        0xa01a => {
+            // 以 0.1 mm 为单位
+            if let Some(v) = e.first_int().filter(|&v| v > 0) {
+                note.push(id, "FocalLengthIn35mmFormat", tr("Focal Length In 35mm Format", "35mm 等效焦距", "35mm 換算焦点距離"), format!("{} mm", v / 10));
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,372 @@
+use little_exif::endian::Endian;
+
+use crate::{
+    locale::tr,
+    makernote::{
+        format_exposure_time, format_f_number, guess_offset_base, read_ifd, u16_at, u32_at, IfdEntry, MakerNote,
+        MakerNoteContext,
+    },
+};
+
+/// 带头部的 Sony MakerNote："SONY DSC " / "SONY CAM " / "SONY MOBILE" 之后补零到 12 字节
+const HEADERS: &[&[u8]] = &[b"SONY DSC \0\0\0", b"SONY CAM \0\0\0", b"SONY MOBILE\0"];
+
+/// 使用 Tag9050a 布局的机型（2015 年以前的 SLT / NEX / ILCA / ILCE），其余机型使用 Tag9050b 布局
+const TAG9050A_PREFIXES: &[&str] = &["SLT-", "NEX-", "ILCA-"];
+const TAG9050A_MODELS: &[&str] = &[
+    "ILCE-7", "ILCE-7R", "ILCE-7S", "ILCE-7M2", "ILCE-3000", "ILCE-3500", "ILCE-5000", "ILCE-5100", "ILCE-6000",
+    "ILCE-QX1",
+];
+/// 前缀匹配 Tag9050a 但实际使用 Tag9050b 布局的机型
+const TAG9050B_EXCEPTIONS: &[&str] = &["ILCA-99M2"];
+
+/// 解码 Sony MakerNote
+///
+/// MakerNote 是一个 IFD（可能带 12 字节头部），值的偏移相对于外层 TIFF 头；
+/// 0x9050 和 0x2010 两个数据块经过替换加密，解密后可读出快门次数和镜头信息
+///
+/// # 参数
+/// - `data`: MakerNote 原始数据
+/// - `ctx`: MakerNote 在 TIFF 中的偏移、外层字节序和相机型号
+///
+/// # 返回值
+/// - 解码出的标签；IFD 损坏时返回 None
+pub fn decode_sony_maker_note(data: &[u8], ctx: &MakerNoteContext) -> Option<MakerNote> {
+    let ifd_offset = if HEADERS.iter().any(|h| data.starts_with(h)) { 12 } else { 0 };
+    let endian = ctx.endian.clone();
+    let base = match ctx.tiff_offset {
+        Some(offset) => offset as i64,
+        None => guess_offset_base(data, ifd_offset, &endian).unwrap_or(0),
+    };
+    let entries = read_ifd(data, ifd_offset, &endian, base)?;
+
+    let model = ctx.model.map(str::trim).unwrap_or_default();
+    let tag9050a = uses_tag9050a(model);
+
+    let mut note = MakerNote::new("Sony", "Sony");
+    for e in &entries {
+        decode_entry(&mut note, e, &endian, tag9050a);
+    }
+    Some(note)
+}
+
+/// 判断机型的 Tag9050 是否使用 Tag9050a 布局
+fn uses_tag9050a(model: &str) -> bool {
+    if TAG9050B_EXCEPTIONS.contains(&model) {
+        return false;
+    }
+    TAG9050A_PREFIXES.iter().any(|p| model.starts_with(p)) || TAG9050A_MODELS.contains(&model)
+}
+
+/// 解密 0x9050 / 0x2010 等数据块：加密时每个小于 249 的字节 b 被替换为 b³ mod 249，
+/// 解密即求 c⁵⁵ mod 249（3 × 55 ≡ 1 mod λ(249)），249–255 保持不变
+fn decipher(data: &[u8]) -> Vec<u8> {
+    data.iter()
+        .map(|&c| {
+            if c >= 249 {
+                return c;
+            }
+            let mut result = 1u32;
+            for _ in 0..55 {
+                result = result * c as u32 % 249;
+            }
+            result as u8
+        })
+        .collect()
+}
+
+/// 解码一个 MakerNote 条目，写入 note
+fn decode_entry(note: &mut MakerNote, e: &IfdEntry, endian: &Endian, tag9050a: bool) {
+    let id = e.tag;
+    match id {
+        0x0102 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0 => "RAW",
+                    1 => tr("Super Fine", "超精细", "スーパーファイン"),
+                    2 => tr("Fine", "精细", "ファイン"),
+                    3 => tr("Standard", "标准", "標準"),
+                    4 => tr("Economy", "经济", "エコノミー"),
+                    5 => tr("Extra Fine", "特精细", "エクストラファイン"),
+                    6 => "RAW + JPEG",
+                    7 => tr("Compressed RAW", "压缩 RAW", "圧縮 RAW"),
+                    8 => tr("Compressed RAW + JPEG", "压缩 RAW + JPEG", "圧縮 RAW + JPEG"),
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(id, "Quality", tr("Quality", "画质", "画質"), value);
+            }
+        }
+        0x0115 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "WhiteBalance", tr("White Balance", "白平衡", "ホワイトバランス"), white_balance_name(v));
+            }
+        }
+        0x2010 => decode_tag2010(note, &decipher(e.u8s()), endian),
+        0x2031 => note.push(id, "SerialNumber", tr("Serial Number", "机身序列号", "シリアル番号"), e.text()),
+        0x9050 => decode_tag9050(note, &decipher(e.u8s()), endian, tag9050a),
+        0xb001 => {
+            if let Some(v) = e.first_int() {
+                note.push(id, "SonyModelID", tr("Model ID", "机型 ID", "機種 ID"), v.to_string());
+            }
+        }
+        0xb020 => note.push(id, "CreativeStyle", tr("Creative Style", "创意风格", "クリエイティブスタイル"), e.text()),
+        0xb025 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0 => tr("Off", "关闭", "オフ"),
+                    1 => tr("Standard", "标准", "標準"),
+                    2 => tr("Advanced Auto", "高级自动", "アドバンスオート"),
+                    3 => tr("Auto", "自动", "オート"),
+                    8 => tr("Advanced Lv1", "高级 Lv1", "アドバンス Lv1"),
+                    9 => tr("Advanced Lv2", "高级 Lv2", "アドバンス Lv2"),
+                    10 => tr("Advanced Lv3", "高级 Lv3", "アドバンス Lv3"),
+                    11 => tr("Advanced Lv4", "高级 Lv4", "アドバンス Lv4"),
+                    12 => tr("Advanced Lv5", "高级 Lv5", "アドバンス Lv5"),
+                    16 => "Lv1",
+                    17 => "Lv2",
+                    18 => "Lv3",
+                    19 => "Lv4",
+                    20 => "Lv5",
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(id, "DynamicRangeOptimizer", tr("Dynamic Range Optimizer", "动态范围优化", "Dレンジオプティマイザー"), value);
+            }
+        }
+        0xb026 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0 => tr("Off", "关闭", "オフ"),
+                    1 => tr("On", "开启", "オン"),
+                    _ => tr("n/a", "不适用", "該当なし"),
+                };
+                note.push(id, "ImageStabilization", tr("Image Stabilization", "防抖", "手ブレ補正"), value);
+            }
+        }
+        0xb027 => {
+            // 65535 表示 E 卡口镜头，具体型号见 Tag9050 中的 LensType2
+            if let Some(v) = e.first_int() {
+                let value = if v == 65535 { tr("E-mount lens (or no lens)", "E 卡口镜头（或无镜头）", "E マウントレンズ（またはレンズなし）").to_string() } else { v.to_string() };
+                note.push(id, "LensType", tr("Lens Type", "镜头类型", "レンズタイプ"), value);
+            }
+        }
+        0xb02a => {
+            if let Some(spec) = lens_spec(e.u8s()) {
+                note.push(id, "LensSpec", tr("Lens Spec", "镜头规格", "レンズ仕様"), spec);
+            }
+        }
+        0xb041 => {
+            if let Some(v) = e.first_int() {
+                let value = match v {
+                    0 => tr("Program AE", "程序自动曝光", "プログラム AE"),
+                    1 => tr("Portrait", "人像", "ポートレート"),
+                    2 => tr("Landscape", "风景", "風景"),
+                    3 => tr("Macro", "微距", "マクロ"),
+                    4 => tr("Sports", "运动", "スポーツ"),
+                    5 => tr("Sunset", "日落", "夕景"),
+                    6 => tr("Night Scene", "夜景", "夜景"),
+                    7 => tr("Night Portrait", "夜景人像", "夜景ポートレート"),
+                    8 => tr("Intelligent Auto", "智能自动", "おまかせオート"),
+                    9 => tr("Shutter Priority", "快门优先", "シャッター優先"),
+                    10 => tr("Aperture Priority", "光圈优先", "絞り優先"),
+                    11 => tr("Manual", "手动曝光", "マニュアル露出"),
+                    18 => tr("Sweep Panorama", "全景扫描", "スイングパノラマ"),
+                    _ => tr("Unknown", "未知", "不明"),
+                };
+                note.push(id, "ExposureMode", tr("Exposure Mode", "曝光模式", "露出モード"), value);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn white_balance_name(v: i64) -> &'static str {
+    match v {
+        0x00 => tr("Auto", "自动", "オート"),
+        0x01 => tr("Color Temperature / Color Filter", "色温 / 滤色片", "色温度 / カラーフィルター"),
+        0x10 => tr("Daylight", "日光", "晴天"),
+        0x20 => tr("Cloudy", "阴天", "曇天"),
+        0x30 => tr("Shade", "阴影", "日陰"),
+        0x40 => tr("Tungsten", "钨丝灯", "電球"),
+        0x50 => tr("Flash", "闪光灯", "フラッシュ"),
+        0x60 => tr("Fluorescent", "荧光灯", "蛍光灯"),
+        0x70 => tr("Custom", "自定义", "カスタム"),
+        0x80 => tr("Underwater", "水下", "水中"),
+        _ => tr("Unknown", "未知", "不明"),
+    }
+}
+
+/// LensSpec：8 字节，第 1–4 字节为 BCD 编码的最短、最长焦距，第 5–6 字节为两端最大光圈（× 10）
+fn lens_spec(data: &[u8]) -> Option<String> {
+    let b = data.get(..8)?;
+    let bcd = |v: u8| ((v >> 4) * 10 + (v & 0x0f)) as u32;
+    let short = bcd(b[1]) * 100 + bcd(b[2]);
+    let long = bcd(b[3]) * 100 + bcd(b[4]);
+    if short == 0 {
+        return None;
+    }
+    let focal = if long > short { format!("{}-{} mm", short, long) } else { format!("{} mm", short) };
+    let wide = bcd(b[5]) as f64 / 10.0;
+    let tele = bcd(b[6]) as f64 / 10.0;
+    let aperture = if tele > wide {
+        format!("{}-{}", format_f_number(wide), format_f_number(tele).trim_start_matches("f/"))
+    } else {
+        format_f_number(wide)
+    };
+    Some(format!("{} {}", focal, aperture))
+}
+
+/// Tag9050 中各字段的位置
+struct Tag9050Layout {
+    shutter_count: usize,
+    exposure_time: usize,
+    f_number: usize,
+    serial: (usize, usize),
+}
+
+/// Tag9050：解密后含快门次数、实际曝光参数、内部序列号和镜头卡口与类型
+fn decode_tag9050(note: &mut MakerNote, data: &[u8], endian: &Endian, tag9050a: bool) {
+    const ID: u16 = 0x9050;
+    let layout = if tag9050a {
+        Tag9050Layout { shutter_count: 0x0032, exposure_time: 0x003a, f_number: 0x003c, serial: (0x007c, 4) }
+    } else {
+        Tag9050Layout { shutter_count: 0x003a, exposure_time: 0x0046, f_number: 0x0048, serial: (0x0088, 6) }
+    };
+    // 高字节在部分机型上有其他含义，只取低 24 位
+    if let Some(count) = u32_at(data, layout.shutter_count, endian).map(|v| v & 0x00ff_ffff).filter(|&v| v > 0) {
+        note.push(ID, "ShutterCount", tr("Shutter Count", "快门次数", "シャッター回数"), count.to_string());
+    }
+    // 曝光时间为 2^(16 - v/256) 秒，光圈为 2^((v/256 - 16) / 2)
+    if let Some(v) = u16_at(data, layout.exposure_time, endian).filter(|&v| v > 0) {
+        let t = 2f64.powf(16.0 - v as f64 / 256.0);
+        note.push(ID, "SonyExposureTime", tr("Sony Exposure Time", "实际曝光时间", "実露出時間"), format_exposure_time(t));
+    }
+    if let Some(v) = u16_at(data, layout.f_number, endian).filter(|&v| v > 0) {
+        let f = 2f64.powf((v as f64 / 256.0 - 16.0) / 2.0);
+        note.push(ID, "SonyFNumber", tr("Sony F Number", "实际光圈", "実絞り値"), format_f_number(f));
+    }
+    let (pos, len) = layout.serial;
+    if let Some(serial) = data.get(pos..pos + len) {
+        let hex: String = serial.iter().map(|b| format!("{:02x}", b)).collect();
+        note.push(ID, "InternalSerialNumber", tr("Internal Serial Number", "内部序列号", "内部シリアル番号"), hex);
+    }
+    if let Some(&v) = data.get(0x0105) {
+        let value = match v {
+            1 => tr("A-mount", "A 卡口", "A マウント"),
+            2 => tr("E-mount", "E 卡口", "E マウント"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "LensMount", tr("Lens Mount", "镜头卡口", "レンズマウント"), value);
+    }
+    if let Some(&v) = data.get(0x0106) {
+        let value = match v {
+            1 => "APS-C",
+            2 => tr("Full-frame", "全画幅", "フルサイズ"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "LensFormat", tr("Lens Format", "镜头画幅", "レンズフォーマット"), value);
+    }
+    if let Some(v) = u16_at(data, 0x0107, endian).filter(|&v| v != 0) {
+        note.push(ID, "LensType2", tr("E-mount Lens Type", "E 卡口镜头类型", "E マウントレンズタイプ"), v.to_string());
+    }
+    if let Some(v) = u16_at(data, 0x0109, endian).filter(|&v| v != 0 && v != 0xffff) {
+        note.push(ID, "LensType", tr("A-mount Lens Type", "A 卡口镜头类型", "A マウントレンズタイプ"), v.to_string());
+    }
+}
+
+/// Tag2010：各版本开头都是连拍中的图像序号、文件序号和驱动模式
+fn decode_tag2010(note: &mut MakerNote, data: &[u8], endian: &Endian) {
+    const ID: u16 = 0x2010;
+    // 序号从 0 开始计数
+    if let Some(v) = u32_at(data, 0x0000, endian) {
+        note.push(ID, "SequenceImageNumber", tr("Sequence Image Number", "连拍图像序号", "連続撮影画像番号"), (v as u64 + 1).to_string());
+    }
+    if let Some(v) = u32_at(data, 0x0004, endian) {
+        note.push(ID, "SequenceFileNumber", tr("Sequence File Number", "连拍文件序号", "連続撮影ファイル番号"), (v as u64 + 1).to_string());
+    }
+    if let Some(&v) = data.get(0x0008) {
+        let value = match v {
+            0 => tr("Single", "单拍", "1 コマ撮影"),
+            1 => tr("Continuous", "连拍", "連続撮影"),
+            2 => tr("Continuous Bracketing", "连续包围", "連続ブラケット"),
+            3 => tr("Single-frame Bracketing", "单张包围", "1 枚ブラケット"),
+            4 => tr("Continuous (speed priority)", "连拍（速度优先）", "連続撮影（速度優先）"),
+            5 => tr("White Balance Bracketing", "白平衡包围", "ホワイトバランスブラケティング"),
+            6 => tr("DRO Bracketing", "DRO 包围", "DRO ブラケット"),
+            8 => tr("Self-timer", "自拍", "セルフタイマー"),
+            _ => tr("Unknown", "未知", "不明"),
+        };
+        note.push(ID, "ReleaseMode2", tr("Drive Mode", "驱动模式", "ドライブモード"), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makernote::test_util::value;
+
+    /// 加密：小于 249 的字节替换为 b³ mod 249
+    fn encipher(data: &[u8]) -> Vec<u8> {
+        data.iter().map(|&b| if b >= 249 { b } else { ((b as u32).pow(3) % 249) as u8 }).collect()
+    }
+
+    /// 按给定布局构造 Tag9050 明文
+    fn tag9050(shutter_count: usize, exposure_time: usize, f_number: usize, serial: usize) -> Vec<u8> {
+        let mut data = vec![0u8; 0x120];
+        // 高字节不属于快门次数
+        data[shutter_count..shutter_count + 4].copy_from_slice(&0xaa00_3039u32.to_le_bytes());
+        // 2^(16 - 6144/256) = 1/256 秒，2^((5120/256 - 16) / 2) = f/4
+        data[exposure_time..exposure_time + 2].copy_from_slice(&6144u16.to_le_bytes());
+        data[f_number..f_number + 2].copy_from_slice(&5120u16.to_le_bytes());
+        data[serial..serial + 6].copy_from_slice(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab]);
+        data[0x0105] = 2;
+        data[0x0106] = 2;
+        data[0x0107..0x0109].copy_from_slice(&32790u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn decipher_inverts_cube() {
+        let plain: Vec<u8> = (0..=255).collect();
+        assert_eq!(decipher(&encipher(&plain)), plain);
+        // 10³ = 1000 ≡ 4 (mod 249)
+        assert_eq!(encipher(&[10]), [4]);
+        assert_eq!(decipher(&[4]), [10]);
+        assert_eq!(decipher(&[249, 250, 255]), [249, 250, 255]);
+    }
+
+    #[test]
+    fn tag9050_layout_by_model() {
+        for model in ["SLT-A99V", "NEX-7", "ILCA-77M2", "ILCA-68", "ILCE-7", "ILCE-7M2", "ILCE-6000", "ILCE-QX1"] {
+            assert!(uses_tag9050a(model), "{}", model);
+        }
+        for model in ["ILCA-99M2", "ILCE-7RM2", "ILCE-7SM2", "ILCE-6300", "ILCE-6500", "ILCE-7M3", "ILCE-1", "DSC-RX100M7", ""] {
+            assert!(!uses_tag9050a(model), "{}", model);
+        }
+    }
+
+    #[test]
+    fn tag9050a_offsets() {
+        let data = encipher(&tag9050(0x0032, 0x003a, 0x003c, 0x007c));
+        let mut note = MakerNote::new("Sony", "Sony");
+        decode_tag9050(&mut note, &decipher(&data), &Endian::Little, true);
+        assert_eq!(value(&note, "ShutterCount"), "12345");
+        assert_eq!(value(&note, "SonyExposureTime"), "1/256 s");
+        assert_eq!(value(&note, "SonyFNumber"), "f/4");
+        // 9050a 的内部序列号只有 4 字节
+        assert_eq!(value(&note, "InternalSerialNumber"), "01234567");
+        assert_eq!(value(&note, "LensMount"), "E-mount");
+        assert_eq!(value(&note, "LensFormat"), "Full-frame");
+        assert_eq!(value(&note, "LensType2"), "32790");
+    }
+
+    #[test]
+    fn tag9050b_offsets() {
+        let data = encipher(&tag9050(0x003a, 0x0046, 0x0048, 0x0088));
+        let mut note = MakerNote::new("Sony", "Sony");
+        decode_tag9050(&mut note, &decipher(&data), &Endian::Little, false);
+        assert_eq!(value(&note, "ShutterCount"), "12345");
+        assert_eq!(value(&note, "SonyExposureTime"), "1/256 s");
+        assert_eq!(value(&note, "SonyFNumber"), "f/4");
+        assert_eq!(value(&note, "InternalSerialNumber"), "0123456789ab");
+    }
+}
@@ -0,0 +1,1697 @@
+use little_exif::{
+    exif_tag::ExifTag,
+    exif_tag_format::ExifTagFormat,
+    ifd::ExifTagGroup,
+    endian::Endian,
+};
+use std::convert::TryInto;
+
+use crate::locale::{current_locale, tr, Locale};
+
+/// 获取人类可读的标签名称 For PNG
+/// # 参数
+/// - `tag`: EXIF 标签
+/// # 返回值
+/// - 当前语言（见 [`crate::locale`]）下的标签名称
+pub fn get_human_readable_tag_name(tag: &ExifTag) -> String {
+    let name = match tag { // 列举 build_tag_enum! 中所有的 EXIF 标签
+        ExifTag::Make(_) => tr("Make", "制造商", "メーカー"),
+        ExifTag::Model(_) => tr("Camera Model Name", "相机型号", "カメラ機種名"),
+        ExifTag::Software(_) => tr("Software", "使用软件", "ソフトウェア"),
+        ExifTag::Artist(_) => tr("Artist", "作者", "撮影者"),
+        ExifTag::Copyright(_) => tr("Copyright", "版权信息", "著作権"),
+        ExifTag::ISO(_) => tr("ISO", "ISO 感光度", "ISO 感度"),
+        ExifTag::FNumber(_) => tr("F Number", "光圈值", "F 値"),
+        ExifTag::FocalLength(_) => tr("Focal Length", "焦距", "焦点距離"),
+        ExifTag::ExposureTime(_) => tr("Exposure Time", "曝光时间", "露出時間"),
+        ExifTag::ImageWidth(_) => tr("Image Width", "图片宽度", "画像の幅"),
+        ExifTag::ImageHeight(_) => tr("Image Height", "图片高度", "画像の高さ"),
+        ExifTag::XResolution(_) => tr("X Resolution", "水平分辨率", "水平解像度"),
+        ExifTag::YResolution(_) => tr("Y Resolution", "垂直分辨率", "垂直解像度"),
+        ExifTag::BitsPerSample(_) => tr("Bits Per Sample", "每样本位数", "ビット深度"),
+        ExifTag::Compression(_) => tr("Compression", "压缩", "圧縮方式"),
+        ExifTag::PhotometricInterpretation(_) => tr("Photometric Interpretation", "光度解释", "画素構成"),
+        ExifTag::CellWidth(_) => tr("Cell Width", "单元宽度", "セルの幅"),
+        ExifTag::CellHeight(_) => tr("Cell Height", "单元高度", "セルの高さ"),
+        ExifTag::ImageDescription(_) => tr("Image Description", "图片描述", "画像タイトル"),
+        ExifTag::Orientation(_) => tr("Orientation", "图片方向", "画像方向"),
+        ExifTag::SamplesPerPixel(_) => tr("Samples Per Pixel", "每像素样本数", "コンポーネント数"),
+        ExifTag::RowsPerStrip(_) => tr("Rows Per Strip", "每带行数", "ストリップ当たりの行数"),
+        ExifTag::PlanarConfiguration(_) => tr("Planar Configuration", "平面配置", "画像データの並び"),
+        ExifTag::ResolutionUnit(_) => tr("Resolution Unit", "分辨率单位", "解像度の単位"),
+        ExifTag::TransferFunction(_) => tr("Transfer Function", "传递函数", "再生階調カーブ特性"),
+        ExifTag::ModifyDate(_) => tr("Modify Date", "修改时间", "変更日時"),
+        ExifTag::WhitePoint(_) => tr("White Point", "白点", "白色点色度"),
+        ExifTag::PrimaryChromaticities(_) => tr("Primary Chromaticities", "主要色度", "原色の色度"),
+        ExifTag::ColorMap(_) => tr("Color Map", "颜色映射表", "カラーマップ"),
+        ExifTag::YCbCrCoefficients(_) => tr("YCbCr Coefficients", "YCbCr 系数", "色変換マトリクス係数"),
+        ExifTag::YCbCrSubSampling(_) => tr("YCbCr Sub Sampling", "YCbCr 子采样", "YCbCr サブサンプリング"),
+        ExifTag::YCbCrPositioning(_) => tr("YCbCr Positioning", "YCbCr 定位", "YCbCr の位置"),
+        ExifTag::ReferenceBlackWhite(_) => tr("Reference Black White", "参考黑白", "参照黒色点値と参照白色点値"),
+        ExifTag::StripOffsets(..) => tr("Strip Offsets", "图像带偏移", "ストリップのオフセット"),
+        ExifTag::StripByteCounts(_) => tr("Strip Byte Counts", "图像带字节数", "ストリップのバイト数"),
+        ExifTag::ThumbnailOffset(..) => tr("Thumbnail Offset", "缩略图偏移", "サムネイルのオフセット"),
+        ExifTag::ThumbnailLength(_) => tr("Thumbnail Length", "缩略图长度", "サムネイルのバイト数"),
+        ExifTag::ExifOffset(_) => tr("Exif Offset", "EXIF IFD 偏移", "Exif IFD へのポインタ"),
+        ExifTag::GPSInfo(_) => tr("GPS Info", "GPS IFD 偏移", "GPS IFD へのポインタ"),
+        ExifTag::GPSVersionID(_) => tr("GPS Version ID", "GPS 版本", "GPS タグのバージョン"),
+        ExifTag::GPSLatitude(_) => tr("GPS Latitude", "GPS 纬度", "GPS 緯度"),
+        ExifTag::GPSLatitudeRef(_) => tr("GPS Latitude Ref", "纬度方向", "GPS 北緯/南緯"),
+        ExifTag::GPSLongitude(_) => tr("GPS Longitude", "GPS 经度", "GPS 経度"),
+        ExifTag::GPSLongitudeRef(_) => tr("GPS Longitude Ref", "经度方向", "GPS 東経/西経"),
+        ExifTag::GPSAltitude(_) => tr("GPS Altitude", "GPS 海拔", "GPS 高度"),
+        ExifTag::GPSAltitudeRef(_) => tr("GPS Altitude Ref", "海拔方向", "GPS 高度の基準"),
+        ExifTag::GPSTimeStamp(_) => tr("GPS Time Stamp", "GPS 时间戳", "GPS 時刻"),
+        ExifTag::GPSSatellites(_) => tr("GPS Satellites", "GPS 卫星", "GPS 測位衛星"),
+        ExifTag::GPSStatus(_) => tr("GPS Status", "GPS 状态", "GPS 受信機の状態"),
+        ExifTag::GPSMeasureMode(_) => tr("GPS Measure Mode", "GPS 测量模式", "GPS 測位方式"),
+        ExifTag::GPSDOP(_) => tr("GPS Dilution Of Precision", "GPS 精度", "GPS 測位精度"),
+        ExifTag::GPSSpeedRef(_) => tr("GPS Speed Ref", "GPS 速度参考", "GPS 速度の単位"),
+        ExifTag::GPSSpeed(_) => tr("GPS Speed", "GPS 速度", "GPS 速度"),
+        ExifTag::GPSTrackRef(_) => tr("GPS Track Ref", "GPS 方向参考", "GPS 進行方向の基準"),
+        ExifTag::GPSTrack(_) => tr("GPS Track", "GPS 方向", "GPS 進行方向"),
+        ExifTag::GPSImgDirectionRef(_) => tr("GPS Img Direction Ref", "GPS 图像方向参考", "GPS 撮影方向の基準"),
+        ExifTag::GPSImgDirection(_) => tr("GPS Img Direction", "GPS 图像方向", "GPS 撮影方向"),
+        ExifTag::GPSMapDatum(_) => tr("GPS Map Datum", "GPS 地图基准", "GPS 測地系"),
+        ExifTag::GPSDestLatitudeRef(_) => tr("GPS Dest Latitude Ref", "GPS 目的地纬度参考", "GPS 目的地の北緯/南緯"),
+        ExifTag::GPSDestLatitude(_) => tr("GPS Dest Latitude", "GPS 目的地纬度", "GPS 目的地の緯度"),
+        ExifTag::GPSDestLongitudeRef(_) => tr("GPS Dest Longitude Ref", "GPS 目的地经度参考", "GPS 目的地の東経/西経"),
+        ExifTag::GPSDestLongitude(_) => tr("GPS Dest Longitude", "GPS 目的地经度", "GPS 目的地の経度"),
+        ExifTag::GPSDestBearingRef(_) => tr("GPS Dest Bearing Ref", "GPS 目的地方位参考", "GPS 目的地の方角の基準"),
+        ExifTag::GPSDestBearing(_) => tr("GPS Dest Bearing", "GPS 目的地方位", "GPS 目的地の方角"),
+        ExifTag::GPSDestDistanceRef(_) => tr("GPS Dest Distance Ref", "GPS 目的地距离参考", "GPS 目的地までの距離の単位"),
+        ExifTag::GPSDestDistance(_) => tr("GPS Dest Distance", "GPS 目的地距离", "GPS 目的地までの距離"),
+        ExifTag::GPSProcessingMethod(_) => tr("GPS Processing Method", "GPS 处理方法", "GPS 測位方式の名称"),
+        ExifTag::GPSAreaInformation(_) => tr("GPS Area Information", "GPS 区域信息", "GPS 測位地点の名称"),
+        ExifTag::GPSDateStamp(_) => tr("GPS Date Stamp", "GPS 日期标记", "GPS 日付"),
+        ExifTag::GPSDifferential(_) => tr("GPS Differential", "GPS 差分", "GPS ディファレンシャル補正"),
+        ExifTag::GPSHPositioningError(_) => tr("GPS Horizontal Positioning Error", "GPS 水平定位误差", "GPS 水平測位誤差"),
+        ExifTag::InteroperabilityIndex(_) => tr("Interoperability Index", "互操作性索引", "互換性インデックス"),
+        ExifTag::InteroperabilityVersion(_) => tr("Interoperability Version", "互操作性版本", "互換性バージョン"),
+        ExifTag::CreateDate(_) => tr("Create Date", "文件创建时间", "デジタルデータ作成日時"),
+        ExifTag::DateTimeOriginal(_) => tr("Date/Time Original", "拍摄时间", "撮影日時"),
+        ExifTag::UserComment(_) => tr("User Comment", "用户注释", "ユーザーコメント"),
+        ExifTag::ExposureProgram(_) => tr("Exposure Program", "曝光程序", "露出プログラム"),
+        ExifTag::SpectralSensitivity(_) => tr("Spectral Sensitivity", "光谱灵敏度", "スペクトル感度"),
+        ExifTag::OECF(_) => tr("Opto-Electric Conversion Factor", "光电转换函数", "光電変換関数"),
+        ExifTag::SensitivityType(_) => tr("Sensitivity Type", "灵敏度类型", "感度種別"),
+        ExifTag::StandardOutputSensitivity(_) => tr("Standard Output Sensitivity", "标准输出灵敏度", "標準出力感度"),
+        ExifTag::RecommendedExposureIndex(_) => tr("Recommended Exposure Index", "推荐曝光指数", "推奨露光指数"),
+        ExifTag::ISOSpeed(_) => tr("ISO Speed", "ISO 速度", "ISO スピード"),
+        ExifTag::ISOSpeedLatitudeyyy(_) => tr("ISO Speed Latitude yyy", "ISO 速度纬度 yyy", "ISO スピードラティチュード yyy"),
+        ExifTag::ISOSpeedLatitudezzz(_) => tr("ISO Speed Latitude zzz", "ISO 速度纬度 zzz", "ISO スピードラティチュード zzz"),
+        ExifTag::ExifVersion(_) => tr("Exif Version", "EXIF 版本", "Exif バージョン"),
+        ExifTag::OffsetTime(_) => tr("Offset Time", "偏移时间", "時差"),
+        ExifTag::OffsetTimeOriginal(_) => tr("Offset Time Original", "原始偏移时间", "撮影日時の時差"),
+        ExifTag::OffsetTimeDigitized(_) => tr("Offset Time Digitized", "数字化偏移时间", "デジタル化日時の時差"),
+        ExifTag::ComponentsConfiguration(_) => tr("Components Configuration", "组件配置", "各コンポーネントの意味"),
+        ExifTag::CompressedBitsPerPixel(_) => tr("Compressed Bits Per Pixel", "每像素压缩位数", "画像圧縮モード"),
+        ExifTag::ShutterSpeedValue(_) => tr("Shutter Speed Value", "快门速度值", "シャッタースピード (APEX)"),
+        ExifTag::ApertureValue(_) => tr("Aperture Value", "光圈值", "絞り値 (APEX)"),
+        ExifTag::BrightnessValue(_) => tr("Brightness Value", "亮度值", "輝度値"),
+        ExifTag::ExposureCompensation(_) => tr("Exposure Compensation", "曝光补偿", "露出補正値"),
+        ExifTag::MaxApertureValue(_) => tr("Max Aperture Value", "最大光圈值", "レンズ最小 F 値"),
+        ExifTag::SubjectDistance(_) => tr("Subject Distance", "主体距离", "被写体距離"),
+        ExifTag::MeteringMode(_) => tr("Metering Mode", "测光模式", "測光方式"),
+        ExifTag::LightSource(_) => tr("Light Source", "光源", "光源"),
+        ExifTag::Flash(_) => tr("Flash", "闪光灯", "フラッシュ"),
+        ExifTag::SubjectArea(_) => tr("Subject Area", "主体区域", "被写体領域"),
+        ExifTag::MakerNote(_) => tr("Maker Note", "制造商注释", "メーカーノート"),
+        ExifTag::SubSecTime(_) => tr("Sub Sec Time", "亚秒时间", "変更日時のサブセック"),
+        ExifTag::SubSecTimeOriginal(_) => tr("Sub Sec Time Original", "原始亚秒时间", "撮影日時のサブセック"),
+        ExifTag::SubSecTimeDigitized(_) => tr("Sub Sec Time Digitized", "数字化亚秒时间", "デジタル化日時のサブセック"),
+        ExifTag::AmbientTemperature(_) => tr("Ambient Temperature", "环境温度", "温度"),
+        ExifTag::Humidity(_) => tr("Humidity", "湿度", "湿度"),
+        ExifTag::Pressure(_) => tr("Pressure", "压力", "気圧"),
+        ExifTag::WaterDepth(_) => tr("Water Depth", "水深", "水深"),
+        ExifTag::Acceleration(_) => tr("Acceleration", "加速度", "加速度"),
+        ExifTag::CameraElevationAngle(_) => tr("Camera Elevation Angle", "相机仰角", "カメラの仰角"),
+        ExifTag::FlashpixVersion(_) => tr("Flashpix Version", "Flashpix 版本", "Flashpix バージョン"),
+        ExifTag::ColorSpace(_) => tr("Color Space", "色彩空间", "色空間"),
+        ExifTag::ExifImageWidth(_) => tr("Exif Image Width", "EXIF 图像宽度", "実効画像幅"),
+        ExifTag::ExifImageHeight(_) => tr("Exif Image Height", "EXIF 图像高度", "実効画像高さ"),
+        ExifTag::RelatedSoundFile(_) => tr("Related Sound File", "相关音频文件", "関連音声ファイル"),
+        ExifTag::InteropOffset(_) => tr("Interop Offset", "互操作性 IFD 偏移", "互換性 IFD へのポインタ"),
+        ExifTag::FlashEnergy(_) => tr("Flash Energy", "闪光灯能量", "フラッシュ強度"),
+        ExifTag::SpatialFrequencyResponse(_) => tr("Spatial Frequency Response", "空间频率响应", "空間周波数応答"),
+        ExifTag::FocalPlaneXResolution(_) => tr("Focal Plane X Resolution", "焦平面 X 分辨率", "焦点面の水平解像度"),
+        ExifTag::FocalPlaneYResolution(_) => tr("Focal Plane Y Resolution", "焦平面 Y 分辨率", "焦点面の垂直解像度"),
+        ExifTag::FocalPlaneResolutionUnit(_) => tr("Focal Plane Resolution Unit", "焦平面分辨率单位", "焦点面解像度の単位"),
+        ExifTag::SubjectLocation(_) => tr("Subject Location", "主体位置", "被写体位置"),
+        ExifTag::ExposureIndex(_) => tr("Exposure Index", "曝光指数", "露出インデックス"),
+        ExifTag::SensingMethod(_) => tr("Sensing Method", "感应方法", "センサー方式"),
+        ExifTag::FileSource(_) => tr("File Source", "文件来源", "ファイルソース"),
+        ExifTag::SceneType(_) => tr("Scene Type", "场景类型", "シーンタイプ"),
+        ExifTag::CFAPattern(_) => tr("CFA Pattern", "CFA 模式", "CFA パターン"),
+        ExifTag::CustomRendered(_) => tr("Custom Rendered", "自定义渲染", "個別画像処理"),
+        ExifTag::ExposureMode(_) => tr("Exposure Mode", "曝光模式", "露出モード"),
+        ExifTag::WhiteBalance(_) => tr("White Balance", "白平衡", "ホワイトバランス"),
+        ExifTag::DigitalZoomRatio(_) => tr("Digital Zoom Ratio", "数字变焦比率", "デジタルズーム倍率"),
+        ExifTag::FocalLengthIn35mmFormat(_) => tr("Focal Length In 35mm Format", "35mm 等效焦距", "35mm 換算焦点距離"),
+        ExifTag::SceneCaptureType(_) => tr("Scene Capture Type", "场景捕捉类型", "撮影シーンタイプ"),
+        ExifTag::GainControl(_) => tr("Gain Control", "增益控制", "ゲイン制御"),
+        ExifTag::Contrast(_) => tr("Contrast", "对比度", "コントラスト"),
+        ExifTag::Saturation(_) => tr("Saturation", "饱和度", "彩度"),
+        ExifTag::Sharpness(_) => tr("Sharpness", "锐度", "シャープネス"),
+        ExifTag::DeviceSettingDescription(_) => tr("Device Setting Description", "设备设置描述", "撮影条件記述情報"),
+        ExifTag::SubjectDistanceRange(_) => tr("Subject Distance Range", "主体距离范围", "被写体距離レンジ"),
+        ExifTag::ImageUniqueID(_) => tr("Image Unique ID", "图像唯一 ID", "画像固有 ID"),
+        ExifTag::OwnerName(_) => tr("Owner Name", "所有者名称", "カメラ所有者名"),
+        ExifTag::SerialNumber(_) => tr("Serial Number", "序列号", "カメラシリアル番号"),
+        ExifTag::LensInfo(_) => tr("Lens Info", "镜头信息", "レンズの仕様情報"),
+        ExifTag::LensMake(_) => tr("Lens Make", "镜头制造商", "レンズメーカー"),
+        ExifTag::LensModel(_) => tr("Lens Model", "镜头型号", "レンズモデル"),
+        ExifTag::LensSerialNumber(_) => tr("Lens Serial Number", "镜头序列号", "レンズシリアル番号"),
+        ExifTag::CompositeImage(_) => tr("Composite Image", "合成图像", "合成画像"),
+        ExifTag::CompositeImageCount(_) => tr("Composite Image Count", "合成图像数量", "合成画像の枚数"),
+        ExifTag::CompositeImageExposureTimes(_) => tr("Composite Image Exposure Times", "合成图像曝光时间", "合成画像の露出時間"),
+        ExifTag::Gamma(_) => tr("Gamma", "伽马", "ガンマ"),
+        _ => match extra_tag_name(tag) {
+            Some((_, name)) => name,
+            None => return format!("{} 0x{:04X}", tr("Unknown Tag", "未知标签", "不明なタグ"), tag.as_u16()),
+        },
+    };
+    name.to_string()
+}
+
+/// 获取标签的简短说明
+///
+/// # 参数
+/// - `tag`: EXIF 标签
+/// # 返回值
+/// - 当前语言下的一句话说明；little_exif 不认识的标签给出其所属规范
+pub fn get_tag_description(tag: &ExifTag) -> String {
+    let description = match tag {
+        // IFD0：TIFF 6.0 基本标签
+        ExifTag::ImageWidth(_) => tr("Number of columns in the image", "图像的列数（像素宽度）", "画像の列数（幅）"),
+        ExifTag::ImageHeight(_) => tr("Number of rows in the image", "图像的行数（像素高度）", "画像の行数（高さ）"),
+        ExifTag::BitsPerSample(_) => tr("Number of bits per color component", "每个颜色分量的位数", "色成分ごとのビット数"),
+        ExifTag::Compression(_) => tr("Compression scheme of the image data", "图像数据的压缩方式", "画像データの圧縮方式"),
+        ExifTag::PhotometricInterpretation(_) => tr("Color space of the image data", "图像数据的色彩空间", "画像データの色空間"),
+        ExifTag::CellWidth(_) => tr("Width of the dithering or halftoning matrix", "抖动或半色调矩阵的宽度", "ディザ・ハーフトーン行列の幅"),
+        ExifTag::CellHeight(_) => tr("Height of the dithering or halftoning matrix", "抖动或半色调矩阵的高度", "ディザ・ハーフトーン行列の高さ"),
+        ExifTag::ImageDescription(_) => tr("Title or caption of the image", "图像的标题或说明", "画像のタイトルまたは説明"),
+        ExifTag::Make(_) => tr("Manufacturer of the recording equipment", "拍摄设备的制造商", "撮影機器のメーカー"),
+        ExifTag::Model(_) => tr("Model name of the recording equipment", "拍摄设备的型号", "撮影機器の機種名"),
+        ExifTag::StripOffsets(..) => tr("Byte offset of each strip of image data", "每个图像数据带的字节偏移", "各ストリップの画像データへのオフセット"),
+        ExifTag::Orientation(_) => tr("How the image must be rotated or flipped for display", "显示时图像需要的旋转或翻转", "表示時に必要な回転・反転"),
+        ExifTag::SamplesPerPixel(_) => tr("Number of components per pixel", "每个像素的分量数", "画素あたりのコンポーネント数"),
+        ExifTag::RowsPerStrip(_) => tr("Number of rows in each strip", "每个图像数据带包含的行数", "各ストリップの行数"),
+        ExifTag::StripByteCounts(_) => tr("Size in bytes of each strip", "每个图像数据带的字节数", "各ストリップのバイト数"),
+        ExifTag::XResolution(_) => tr("Pixels per resolution unit in the width direction", "宽度方向每分辨率单位的像素数", "幅方向の単位あたりの画素数"),
+        ExifTag::YResolution(_) => tr("Pixels per resolution unit in the height direction", "高度方向每分辨率单位的像素数", "高さ方向の単位あたりの画素数"),
+        ExifTag::PlanarConfiguration(_) => tr("Whether components are stored interleaved or in separate planes", "各分量交错存储还是分平面存储", "コンポーネントを点順次で記録するか面順次で記録するか"),
+        ExifTag::ResolutionUnit(_) => tr("Unit of X and Y resolution", "水平和垂直分辨率的单位", "水平・垂直解像度の単位"),
+        ExifTag::TransferFunction(_) => tr("Transfer function as a lookup table", "以查找表表示的传递函数", "参照テーブルで表した再生階調カーブ"),
+        ExifTag::Software(_) => tr("Software or firmware that created the image", "生成图像的软件或固件", "画像を作成したソフトウェアまたはファームウェア"),
+        ExifTag::ModifyDate(_) => tr("Date and time the file was last changed", "文件最后修改的日期和时间", "ファイルの最終変更日時"),
+        ExifTag::Artist(_) => tr("Person who created the image", "图像的创作者", "画像の作成者"),
+        ExifTag::WhitePoint(_) => tr("Chromaticity of the white point", "白点的色度坐标", "白色点の色度座標"),
+        ExifTag::PrimaryChromaticities(_) => tr("Chromaticities of the three primary colors", "三原色的色度坐标", "三原色の色度座標"),
+        ExifTag::ColorMap(_) => tr("Palette for palette-color images", "调色板图像的颜色表", "パレットカラー画像のカラーマップ"),
+        ExifTag::ThumbnailOffset(..) => tr("Byte offset of the JPEG thumbnail in IFD1", "IFD1 中 JPEG 缩略图的字节偏移", "IFD1 の JPEG サムネイルへのオフセット"),
+        ExifTag::ThumbnailLength(_) => tr("Size in bytes of the JPEG thumbnail", "JPEG 缩略图的字节数", "JPEG サムネイルのバイト数"),
+        ExifTag::YCbCrCoefficients(_) => tr("Coefficients for converting RGB to YCbCr", "RGB 转换为 YCbCr 的系数", "RGB から YCbCr への変換係数"),
+        ExifTag::YCbCrSubSampling(_) => tr("Chroma subsampling factors", "色度子采样系数", "色差信号のサブサンプリング比率"),
+        ExifTag::YCbCrPositioning(_) => tr("Position of chroma samples relative to luma samples", "色度样本相对亮度样本的位置", "輝度サンプルに対する色差サンプルの位置"),
+        ExifTag::ReferenceBlackWhite(_) => tr("Reference black and white values for each component", "各分量的参考黑点和白点值", "各コンポーネントの参照黒色点値と参照白色点値"),
+        ExifTag::Copyright(_) => tr("Copyright notice", "版权声明", "著作権表示"),
+        ExifTag::ExifOffset(_) => tr("Offset of the Exif IFD", "EXIF IFD 的偏移", "Exif IFD へのオフセット"),
+        ExifTag::GPSInfo(_) => tr("Offset of the GPS IFD", "GPS IFD 的偏移", "GPS IFD へのオフセット"),
+
+        // ExifIFD：拍摄条件
+        ExifTag::ExposureTime(_) => tr("Exposure time in seconds", "曝光时间（秒）", "露出時間（秒）"),
+        ExifTag::FNumber(_) => tr("F-number of the lens", "镜头的光圈 F 值", "レンズの F 値"),
+        ExifTag::ExposureProgram(_) => tr("Program the camera used to set exposure", "相机设定曝光所用的程序", "露出を設定したプログラム"),
+        ExifTag::SpectralSensitivity(_) => tr("Spectral sensitivity of each channel", "各通道的光谱灵敏度", "各チャンネルのスペクトル感度"),
+        ExifTag::ISO(_) => tr("ISO sensitivity or speed of the camera", "相机的 ISO 感光度", "カメラの ISO 感度"),
+        ExifTag::OECF(_) => tr("Opto-electric conversion function as defined in ISO 14524", "ISO 14524 定义的光电转换函数", "ISO 14524 で定義された光電変換関数"),
+        ExifTag::SensitivityType(_) => tr("Which ISO 12232 parameter the ISO tag records", "ISO 标签记录的是 ISO 12232 中的哪种参数", "ISO タグが記録する ISO 12232 のパラメーター"),
+        ExifTag::StandardOutputSensitivity(_) => tr("Standard output sensitivity (SOS) per ISO 12232", "ISO 12232 定义的标准输出灵敏度", "ISO 12232 の標準出力感度"),
+        ExifTag::RecommendedExposureIndex(_) => tr("Recommended exposure index (REI) per ISO 12232", "ISO 12232 定义的推荐曝光指数", "ISO 12232 の推奨露光指数"),
+        ExifTag::ISOSpeed(_) => tr("ISO speed per ISO 12232", "ISO 12232 定义的 ISO 速度", "ISO 12232 の ISO スピード"),
+        ExifTag::ISOSpeedLatitudeyyy(_) => tr("ISO speed latitude yyy per ISO 12232", "ISO 12232 定义的 ISO 速度宽容度 yyy", "ISO 12232 の ISO スピードラティチュード yyy"),
+        ExifTag::ISOSpeedLatitudezzz(_) => tr("ISO speed latitude zzz per ISO 12232", "ISO 12232 定义的 ISO 速度宽容度 zzz", "ISO 12232 の ISO スピードラティチュード zzz"),
+        ExifTag::ExifVersion(_) => tr("Version of the Exif standard", "所遵循的 EXIF 标准版本", "準拠する Exif 規格のバージョン"),
+        ExifTag::DateTimeOriginal(_) => tr("Date and time the image was captured", "拍摄图像的日期和时间", "撮影した日時"),
+        ExifTag::CreateDate(_) => tr("Date and time the image was stored as digital data", "图像保存为数字数据的日期和时间", "デジタルデータとして記録した日時"),
+        ExifTag::OffsetTime(_) => tr("UTC offset of Modify Date", "修改时间的 UTC 时差", "変更日時の UTC からの時差"),
+        ExifTag::OffsetTimeOriginal(_) => tr("UTC offset of Date/Time Original", "拍摄时间的 UTC 时差", "撮影日時の UTC からの時差"),
+        ExifTag::OffsetTimeDigitized(_) => tr("UTC offset of Create Date", "数字化时间的 UTC 时差", "デジタル化日時の UTC からの時差"),
+        ExifTag::ComponentsConfiguration(_) => tr("Order of the components in compressed data", "压缩数据中各分量的顺序", "圧縮データ中のコンポーネントの並び"),
+        ExifTag::CompressedBitsPerPixel(_) => tr("Average compressed bits per pixel", "压缩后每像素的平均位数", "圧縮後の画素あたり平均ビット数"),
+        ExifTag::ShutterSpeedValue(_) => tr("Shutter speed in APEX units", "以 APEX 单位表示的快门速度", "APEX 値で表したシャッタースピード"),
+        ExifTag::ApertureValue(_) => tr("Lens aperture in APEX units", "以 APEX 单位表示的光圈", "APEX 値で表した絞り値"),
+        ExifTag::BrightnessValue(_) => tr("Scene brightness in APEX units", "以 APEX 单位表示的场景亮度", "APEX 値で表した被写体の輝度"),
+        ExifTag::ExposureCompensation(_) => tr("Exposure bias in EV", "曝光补偿量（EV）", "露出補正量（EV）"),
+        ExifTag::MaxApertureValue(_) => tr("Smallest F-number of the lens in APEX units", "以 APEX 单位表示的镜头最大光圈", "APEX 値で表したレンズの開放 F 値"),
+        ExifTag::SubjectDistance(_) => tr("Distance to the subject in meters", "到拍摄主体的距离（米）", "被写体までの距離（メートル）"),
+        ExifTag::MeteringMode(_) => tr("Metering mode", "测光方式", "測光方式"),
+        ExifTag::LightSource(_) => tr("Kind of light source", "光源类型", "光源の種類"),
+        ExifTag::Flash(_) => tr("Flash status and mode", "闪光灯的状态和模式", "フラッシュの状態とモード"),
+        ExifTag::FocalLength(_) => tr("Actual focal length of the lens in mm", "镜头的实际焦距（毫米）", "レンズの実焦点距離（mm）"),
+        ExifTag::SubjectArea(_) => tr("Location and area of the main subject", "主体的位置和范围", "主要被写体の位置と範囲"),
+        ExifTag::MakerNote(_) => tr("Manufacturer-specific data", "制造商私有数据", "メーカー独自のデータ"),
+        ExifTag::UserComment(_) => tr("Comments written by the user", "用户写入的注释", "ユーザーが記入したコメント"),
+        ExifTag::SubSecTime(_) => tr("Fractional seconds of Modify Date", "修改时间的亚秒部分", "変更日時の秒未満の値"),
+        ExifTag::SubSecTimeOriginal(_) => tr("Fractional seconds of Date/Time Original", "拍摄时间的亚秒部分", "撮影日時の秒未満の値"),
+        ExifTag::SubSecTimeDigitized(_) => tr("Fractional seconds of Create Date", "数字化时间的亚秒部分", "デジタル化日時の秒未満の値"),
+        ExifTag::AmbientTemperature(_) => tr("Ambient temperature in °C when the image was captured", "拍摄时的环境温度（°C）", "撮影時の温度（°C）"),
+        ExifTag::Humidity(_) => tr("Ambient relative humidity in percent", "拍摄时的相对湿度（%）", "撮影時の相対湿度（%）"),
+        ExifTag::Pressure(_) => tr("Ambient air pressure in hPa", "拍摄时的气压（hPa）", "撮影時の気圧（hPa）"),
+        ExifTag::WaterDepth(_) => tr("Water depth in meters, negative when above water", "水深（米），在水面以上时为负", "水深（メートル）、水面より上では負"),
+        ExifTag::Acceleration(_) => tr("Acceleration of the camera in mGal", "相机的加速度（mGal）", "カメラの加速度（mGal）"),
+        ExifTag::CameraElevationAngle(_) => tr("Elevation angle of the camera in degrees", "相机的仰角（度）", "カメラの仰角（度）"),
+        ExifTag::FlashpixVersion(_) => tr("Version of the Flashpix format supported", "支持的 Flashpix 格式版本", "対応する Flashpix フォーマットのバージョン"),
+        ExifTag::ColorSpace(_) => tr("Color space of the image", "图像的色彩空间", "画像の色空間"),
+        ExifTag::ExifImageWidth(_) => tr("Width of the meaningful image data", "有效图像数据的宽度", "有効な画像データの幅"),
+        ExifTag::ExifImageHeight(_) => tr("Height of the meaningful image data", "有效图像数据的高度", "有効な画像データの高さ"),
+        ExifTag::RelatedSoundFile(_) => tr("Name of an audio file related to the image", "与图像关联的音频文件名", "画像に関連する音声ファイル名"),
+        ExifTag::InteropOffset(_) => tr("Offset of the Interoperability IFD", "互操作性 IFD 的偏移", "互換性 IFD へのオフセット"),
+        ExifTag::FlashEnergy(_) => tr("Strobe energy in BCPS", "闪光灯能量（BCPS）", "ストロボの強度（BCPS）"),
+        ExifTag::SpatialFrequencyResponse(_) => tr("Spatial frequency table and SFR values per ISO 12233", "ISO 12233 定义的空间频率响应表", "ISO 12233 の空間周波数応答テーブル"),
+        ExifTag::FocalPlaneXResolution(_) => tr("Pixels per unit in the width direction on the focal plane", "焦平面上宽度方向每单位的像素数", "焦点面上の幅方向の単位あたり画素数"),
+        ExifTag::FocalPlaneYResolution(_) => tr("Pixels per unit in the height direction on the focal plane", "焦平面上高度方向每单位的像素数", "焦点面上の高さ方向の単位あたり画素数"),
+        ExifTag::FocalPlaneResolutionUnit(_) => tr("Unit of the focal plane resolutions", "焦平面分辨率的单位", "焦点面解像度の単位"),
+        ExifTag::SubjectLocation(_) => tr("Pixel location of the main subject", "主体所在的像素位置", "主要被写体の画素位置"),
+        ExifTag::ExposureIndex(_) => tr("Exposure index selected on the camera", "相机上选择的曝光指数", "カメラで選択した露出インデックス"),
+        ExifTag::SensingMethod(_) => tr("Type of image sensor", "图像传感器的类型", "イメージセンサーの種類"),
+        ExifTag::FileSource(_) => tr("Source of the image, such as a digital camera or scanner", "图像来源，如数码相机或扫描仪", "画像の入力機器（デジタルカメラ、スキャナーなど）"),
+        ExifTag::SceneType(_) => tr("Whether the image was directly photographed", "图像是否为直接拍摄", "直接撮影された画像かどうか"),
+        ExifTag::CFAPattern(_) => tr("Color filter array geometry of the sensor", "传感器彩色滤镜阵列的排列", "センサーのカラーフィルター配列"),
+        ExifTag::CustomRendered(_) => tr("Whether special processing was applied to the image", "是否对图像做了特殊处理", "特殊な画像処理を行ったかどうか"),
+        ExifTag::ExposureMode(_) => tr("Exposure mode set when the image was shot", "拍摄时设定的曝光模式", "撮影時の露出モード"),
+        ExifTag::WhiteBalance(_) => tr("White balance mode set when the image was shot", "拍摄时设定的白平衡模式", "撮影時のホワイトバランスモード"),
+        ExifTag::DigitalZoomRatio(_) => tr("Digital zoom ratio, 0 when not used", "数字变焦倍率，未使用时为 0", "デジタルズーム倍率、未使用時は 0"),
+        ExifTag::FocalLengthIn35mmFormat(_) => tr("Equivalent focal length on 35 mm film", "换算到 35mm 胶片的等效焦距", "35mm フィルム換算の焦点距離"),
+        ExifTag::SceneCaptureType(_) => tr("Type of scene that was shot", "拍摄的场景类型", "撮影したシーンの種類"),
+        ExifTag::GainControl(_) => tr("Degree of overall image gain adjustment", "图像整体增益的调整程度", "画像全体のゲイン調整の度合い"),
+        ExifTag::Contrast(_) => tr("Contrast processing applied by the camera", "相机应用的对比度处理", "カメラが適用したコントラスト処理"),
+        ExifTag::Saturation(_) => tr("Saturation processing applied by the camera", "相机应用的饱和度处理", "カメラが適用した彩度処理"),
+        ExifTag::Sharpness(_) => tr("Sharpness processing applied by the camera", "相机应用的锐化处理", "カメラが適用したシャープネス処理"),
+        ExifTag::DeviceSettingDescription(_) => tr("Picture-taking conditions of a particular camera model", "特定相机型号的拍摄条件", "特定の機種の撮影条件"),
+        ExifTag::SubjectDistanceRange(_) => tr("Range of distance to the subject", "到主体的距离范围", "被写体までの距離の範囲"),
+        ExifTag::ImageUniqueID(_) => tr("Identifier assigned uniquely to each image", "为每幅图像分配的唯一标识", "画像ごとに固有の識別子"),
+        ExifTag::OwnerName(_) => tr("Name of the camera owner", "相机所有者的名称", "カメラ所有者の名前"),
+        ExifTag::SerialNumber(_) => tr("Serial number of the camera body", "相机机身的序列号", "カメラ本体のシリアル番号"),
+        ExifTag::LensInfo(_) => tr("Minimum and maximum focal length and F-number of the lens", "镜头的最短、最长焦距和对应的最大光圈", "レンズの最短・最長焦点距離とそれぞれの開放 F 値"),
+        ExifTag::LensMake(_) => tr("Manufacturer of the lens", "镜头的制造商", "レンズのメーカー"),
+        ExifTag::LensModel(_) => tr("Model name of the lens", "镜头的型号", "レンズのモデル名"),
+        ExifTag::LensSerialNumber(_) => tr("Serial number of the lens", "镜头的序列号", "レンズのシリアル番号"),
+        ExifTag::CompositeImage(_) => tr("Whether the image is a composite of several captures", "图像是否由多次拍摄合成", "複数の撮影画像を合成した画像かどうか"),
+        ExifTag::CompositeImageCount(_) => tr("Number of source images and images used for the composite", "合成所用的源图像数和实际使用的图像数", "合成に使用した元画像の総数と使用枚数"),
+        ExifTag::CompositeImageExposureTimes(_) => tr("Exposure times of the source images of a composite", "合成图像各源图像的曝光时间", "合成画像の元画像の露出時間"),
+        ExifTag::Gamma(_) => tr("Gamma coefficient of the image", "图像的伽马系数", "画像のガンマ係数"),
+
+        // InteropIFD
+        ExifTag::InteroperabilityIndex(_) => tr("Interoperability rule the file follows, such as R98", "文件遵循的互操作性规则，如 R98", "ファイルが準拠する互換性規則（R98 など）"),
+        ExifTag::InteroperabilityVersion(_) => tr("Version of the interoperability rule", "互操作性规则的版本", "互換性規則のバージョン"),
+
+        // GPS IFD
+        ExifTag::GPSVersionID(_) => tr("Version of the GPS IFD", "GPS IFD 的版本", "GPS IFD のバージョン"),
+        ExifTag::GPSLatitudeRef(_) => tr("North or south latitude", "北纬或南纬", "北緯または南緯"),
+        ExifTag::GPSLatitude(_) => tr("Latitude as degrees, minutes and seconds", "以度、分、秒表示的纬度", "度・分・秒で表した緯度"),
+        ExifTag::GPSLongitudeRef(_) => tr("East or west longitude", "东经或西经", "東経または西経"),
+        ExifTag::GPSLongitude(_) => tr("Longitude as degrees, minutes and seconds", "以度、分、秒表示的经度", "度・分・秒で表した経度"),
+        ExifTag::GPSAltitudeRef(_) => tr("Whether the altitude is above or below sea level", "海拔在海平面以上还是以下", "高度が海面より上か下か"),
+        ExifTag::GPSAltitude(_) => tr("Altitude in meters", "海拔高度（米）", "高度（メートル）"),
+        ExifTag::GPSTimeStamp(_) => tr("UTC time of the GPS fix", "GPS 定位的 UTC 时间", "GPS 測位時の UTC 時刻"),
+        ExifTag::GPSSatellites(_) => tr("Satellites used for the measurement", "用于定位的卫星", "測位に使用した衛星"),
+        ExifTag::GPSStatus(_) => tr("Status of the GPS receiver", "GPS 接收器的状态", "GPS 受信機の状態"),
+        ExifTag::GPSMeasureMode(_) => tr("Two- or three-dimensional measurement", "二维或三维定位", "2 次元測位か 3 次元測位か"),
+        ExifTag::GPSDOP(_) => tr("Dilution of precision of the measurement", "定位的精度因子", "測位の精度低下率"),
+        ExifTag::GPSSpeedRef(_) => tr("Unit of GPS speed", "GPS 速度的单位", "GPS 速度の単位"),
+        ExifTag::GPSSpeed(_) => tr("Speed of the GPS receiver", "GPS 接收器的移动速度", "GPS 受信機の移動速度"),
+        ExifTag::GPSTrackRef(_) => tr("Reference for the direction of movement", "移动方向的参考基准", "進行方向の基準"),
+        ExifTag::GPSTrack(_) => tr("Direction of movement in degrees", "移动方向（度）", "進行方向（度）"),
+        ExifTag::GPSImgDirectionRef(_) => tr("Reference for the image direction", "拍摄方向的参考基准", "撮影方向の基準"),
+        ExifTag::GPSImgDirection(_) => tr("Direction the camera was facing in degrees", "拍摄时相机朝向（度）", "撮影方向（度）"),
+        ExifTag::GPSMapDatum(_) => tr("Geodetic survey data used", "所用的大地测量基准", "使用した測地系"),
+        ExifTag::GPSDestLatitudeRef(_) => tr("North or south latitude of the destination", "目的地的北纬或南纬", "目的地の北緯または南緯"),
+        ExifTag::GPSDestLatitude(_) => tr("Latitude of the destination", "目的地的纬度", "目的地の緯度"),
+        ExifTag::GPSDestLongitudeRef(_) => tr("East or west longitude of the destination", "目的地的东经或西经", "目的地の東経または西経"),
+        ExifTag::GPSDestLongitude(_) => tr("Longitude of the destination", "目的地的经度", "目的地の経度"),
+        ExifTag::GPSDestBearingRef(_) => tr("Reference for the bearing to the destination", "目的地方位的参考基准", "目的地の方角の基準"),
+        ExifTag::GPSDestBearing(_) => tr("Bearing to the destination in degrees", "目的地的方位（度）", "目的地の方角（度）"),
+        ExifTag::GPSDestDistanceRef(_) => tr("Unit of the distance to the destination", "目的地距离的单位", "目的地までの距離の単位"),
+        ExifTag::GPSDestDistance(_) => tr("Distance to the destination", "到目的地的距离", "目的地までの距離"),
+        ExifTag::GPSProcessingMethod(_) => tr("Name of the method used for location finding", "定位所用方法的名称", "測位に使用した方式の名称"),
+        ExifTag::GPSAreaInformation(_) => tr("Name of the GPS area", "GPS 区域的名称", "測位地点の名称"),
+        ExifTag::GPSDateStamp(_) => tr("UTC date of the GPS fix", "GPS 定位的 UTC 日期", "GPS 測位時の UTC 日付"),
+        ExifTag::GPSDifferential(_) => tr("Whether differential correction was applied", "是否应用了差分校正", "ディファレンシャル補正を行ったかどうか"),
+        ExifTag::GPSHPositioningError(_) => tr("Horizontal positioning error in meters", "水平定位误差（米）", "水平方向の測位誤差（メートル）"),
+
+        _ => match find_extra_tag(tag) {
+            Some((TagSpec::Tiff, _)) => tr("Tag defined by TIFF 6.0 or its supplements", "TIFF 6.0 及其补充规范定义的标签", "TIFF 6.0 とその補足仕様で定義されたタグ"),
+            Some((TagSpec::TiffEp, _)) => tr("Tag defined by TIFF/EP (ISO 12234-2)", "TIFF/EP（ISO 12234-2）定义的标签", "TIFF/EP（ISO 12234-2）で定義されたタグ"),
+            Some((TagSpec::Exif, _)) => tr("Tag defined by Exif 3.0 or DCF", "EXIF 3.0 或 DCF 定义的标签", "Exif 3.0 または DCF で定義されたタグ"),
+            Some((TagSpec::Dng, _)) => tr("Tag defined by the DNG specification", "DNG 规范定义的标签", "DNG 仕様で定義されたタグ"),
+            Some((TagSpec::CinemaDng, _)) => tr("Tag defined by the CinemaDNG specification", "CinemaDNG 规范定义的标签", "CinemaDNG 仕様で定義されたタグ"),
+            Some((TagSpec::Private, _)) => tr("Private tag written by common applications", "常见软件写入的私有标签", "一般的なソフトウェアが書き込む独自タグ"),
+            None => tr("Tag not defined by TIFF, Exif or DNG", "TIFF、EXIF 和 DNG 均未定义的标签", "TIFF・Exif・DNG のいずれでも定義されていないタグ"),
+        },
+    };
+    description.to_string()
+}
+
+/// little_exif 不认识的标签：(编号, 规范名称, 英文名, 中文名, 日文名)
+type ExtraTag = (u16, &'static str, &'static str, &'static str, &'static str);
+
+/// 标签出自的规范，决定未知标签的说明文字
+#[derive(Clone, Copy)]
+enum TagSpec {
+    Tiff,
+    TiffEp,
+    Exif,
+    Dng,
+    CinemaDng,
+    /// Windows、Photoshop 等软件写入的私有标签
+    Private,
+}
+
+/// TIFF 6.0 及其补充规范中的标签
+const TIFF_TAGS: &[ExtraTag] = &[
+    (0x00fe, "NewSubfileType", "New Subfile Type", "子文件类型", "サブファイルタイプ"),
+    (0x00ff, "SubfileType", "Subfile Type", "旧式子文件类型", "旧式サブファイルタイプ"),
+    (0x0107, "Thresholding", "Thresholding", "阈值处理", "二値化方式"),
+    (0x010a, "FillOrder", "Fill Order", "位填充顺序", "ビット順序"),
+    (0x010d, "DocumentName", "Document Name", "文档名称", "文書名"),
+    (0x0118, "MinSampleValue", "Min Sample Value", "最小样本值", "最小サンプル値"),
+    (0x0119, "MaxSampleValue", "Max Sample Value", "最大样本值", "最大サンプル値"),
+    (0x011d, "PageName", "Page Name", "页面名称", "ページ名"),
+    (0x011e, "XPosition", "X Position", "水平位置", "水平位置"),
+    (0x011f, "YPosition", "Y Position", "垂直位置", "垂直位置"),
+    (0x0120, "FreeOffsets", "Free Offsets", "空闲区偏移", "空き領域のオフセット"),
+    (0x0121, "FreeByteCounts", "Free Byte Counts", "空闲区字节数", "空き領域のバイト数"),
+    (0x0122, "GrayResponseUnit", "Gray Response Unit", "灰度响应单位", "グレー応答の単位"),
+    (0x0123, "GrayResponseCurve", "Gray Response Curve", "灰度响应曲线", "グレー応答カーブ"),
+    (0x0124, "T4Options", "T4 Options", "T4 选项", "T4 オプション"),
+    (0x0125, "T6Options", "T6 Options", "T6 选项", "T6 オプション"),
+    (0x0129, "PageNumber", "Page Number", "页码", "ページ番号"),
+    (0x013c, "HostComputer", "Host Computer", "主机", "ホストコンピューター"),
+    (0x013d, "Predictor", "Predictor", "预测器", "予測子"),
+    (0x0142, "TileWidth", "Tile Width", "图块宽度", "タイルの幅"),
+    (0x0143, "TileLength", "Tile Length", "图块高度", "タイルの高さ"),
+    (0x0144, "TileOffsets", "Tile Offsets", "图块偏移", "タイルのオフセット"),
+    (0x0145, "TileByteCounts", "Tile Byte Counts", "图块字节数", "タイルのバイト数"),
+    (0x014a, "SubIFDs", "Sub IFDs", "子 IFD", "サブ IFD"),
+    (0x014c, "InkSet", "Ink Set", "油墨组", "インクセット"),
+    (0x014d, "InkNames", "Ink Names", "油墨名称", "インク名"),
+    (0x014e, "NumberOfInks", "Number Of Inks", "油墨数量", "インク数"),
+    (0x0150, "DotRange", "Dot Range", "网点范围", "ドット範囲"),
+    (0x0151, "TargetPrinter", "Target Printer", "目标打印机", "対象プリンター"),
+    (0x0152, "ExtraSamples", "Extra Samples", "附加样本", "追加サンプル"),
+    (0x0153, "SampleFormat", "Sample Format", "样本格式", "サンプル形式"),
+    (0x0154, "SMinSampleValue", "S Min Sample Value", "最小样本值（按格式）", "最小サンプル値（形式別）"),
+    (0x0155, "SMaxSampleValue", "S Max Sample Value", "最大样本值（按格式）", "最大サンプル値（形式別）"),
+    (0x0156, "TransferRange", "Transfer Range", "传递范围", "転送範囲"),
+    (0x0200, "JPEGProc", "JPEG Proc", "JPEG 处理方式", "JPEG 処理方式"),
+    (0x0203, "JPEGRestartInterval", "JPEG Restart Interval", "JPEG 重启间隔", "JPEG リスタート間隔"),
+    (0x0205, "JPEGLosslessPredictors", "JPEG Lossless Predictors", "JPEG 无损预测器", "JPEG 可逆予測子"),
+    (0x0206, "JPEGPointTransforms", "JPEG Point Transforms", "JPEG 点变换", "JPEG 点変換"),
+    (0x0207, "JPEGQTables", "JPEG Q Tables", "JPEG 量化表", "JPEG 量子化テーブル"),
+    (0x0208, "JPEGDCTables", "JPEG DC Tables", "JPEG DC 表", "JPEG DC テーブル"),
+    (0x0209, "JPEGACTables", "JPEG AC Tables", "JPEG AC 表", "JPEG AC テーブル"),
+    (0x02bc, "ApplicationNotes", "Application Notes (XMP)", "XMP 数据", "XMP データ"),
+    (0x83bb, "IPTC-NAA", "IPTC-NAA", "IPTC 数据", "IPTC データ"),
+    (0x8773, "ICC_Profile", "ICC Profile", "ICC 配置文件", "ICC プロファイル"),
+];
+
+/// TIFF/EP（ISO 12234-2）中的标签，多数与 EXIF 标签同义但编号不同
+const TIFF_EP_TAGS: &[ExtraTag] = &[
+    (0x828d, "CFARepeatPatternDim", "CFA Repeat Pattern Dim", "CFA 重复图案尺寸", "CFA 繰り返しパターンのサイズ"),
+    (0x828e, "CFAPattern", "CFA Pattern", "CFA 图案", "CFA パターン"),
+    (0x828f, "BatteryLevel", "Battery Level", "电池电量", "バッテリー残量"),
+    (0x920b, "FlashEnergy", "Flash Energy", "闪光灯能量", "フラッシュ強度"),
+    (0x920c, "SpatialFrequencyResponse", "Spatial Frequency Response", "空间频率响应", "空間周波数応答"),
+    (0x920d, "Noise", "Noise", "噪声", "ノイズ"),
+    (0x920e, "FocalPlaneXResolution", "Focal Plane X Resolution", "焦平面 X 分辨率", "焦点面の水平解像度"),
+    (0x920f, "FocalPlaneYResolution", "Focal Plane Y Resolution", "焦平面 Y 分辨率", "焦点面の垂直解像度"),
+    (0x9210, "FocalPlaneResolutionUnit", "Focal Plane Resolution Unit", "焦平面分辨率单位", "焦点面解像度の単位"),
+    (0x9211, "ImageNumber", "Image Number", "图像编号", "画像番号"),
+    (0x9212, "SecurityClassification", "Security Classification", "安全等级", "セキュリティ分類"),
+    (0x9213, "ImageHistory", "Image History", "图像历史", "画像履歴"),
+    (0x9215, "ExposureIndex", "Exposure Index", "曝光指数", "露出インデックス"),
+    (0x9216, "TIFF-EPStandardID", "TIFF-EP Standard ID", "TIFF-EP 标准版本", "TIFF-EP 規格バージョン"),
+    (0x9217, "SensingMethod", "Sensing Method", "感应方法", "センサー方式"),
+];
+
+/// EXIF 3.0 新增的标签
+const EXIF_TAGS: &[ExtraTag] = &[
+    (0xa436, "ImageTitle", "Image Title", "图像标题", "画像タイトル"),
+    (0xa437, "Photographer", "Photographer", "摄影师", "撮影者名"),
+    (0xa438, "ImageEditor", "Image Editor", "图像编辑者", "画像編集者名"),
+    (0xa439, "CameraFirmware", "Camera Firmware", "相机固件", "カメラファームウェア"),
+    (0xa43a, "RAWDevelopingSoftware", "RAW Developing Software", "RAW 显像软件", "RAW 現像ソフトウェア"),
+    (0xa43b, "ImageEditingSoftware", "Image Editing Software", "图像编辑软件", "画像編集ソフトウェア"),
+    (0xa43c, "MetadataEditingSoftware", "Metadata Editing Software", "元数据编辑软件", "メタデータ編集ソフトウェア"),
+];
+
+/// InteropIFD 中 DCF 规定的标签
+const INTEROP_TAGS: &[ExtraTag] = &[
+    (0x1000, "RelatedImageFileFormat", "Related Image File Format", "相关图像文件格式", "関連画像ファイル形式"),
+    (0x1001, "RelatedImageWidth", "Related Image Width", "相关图像宽度", "関連画像の幅"),
+    (0x1002, "RelatedImageHeight", "Related Image Height", "相关图像高度", "関連画像の高さ"),
+];
+
+/// DNG 1.0 – 1.7 中的标签
+const DNG_TAGS: &[ExtraTag] = &[
+    (0xc612, "DNGVersion", "DNG Version", "DNG 版本", "DNG バージョン"),
+    (0xc613, "DNGBackwardVersion", "DNG Backward Version", "DNG 兼容版本", "DNG 互換バージョン"),
+    (0xc614, "UniqueCameraModel", "Unique Camera Model", "唯一相机型号", "固有カメラモデル"),
+    (0xc615, "LocalizedCameraModel", "Localized Camera Model", "本地化相机型号", "ローカライズされたカメラモデル"),
+    (0xc616, "CFAPlaneColor", "CFA Plane Color", "CFA 平面颜色", "CFA プレーンの色"),
+    (0xc617, "CFALayout", "CFA Layout", "CFA 布局", "CFA レイアウト"),
+    (0xc618, "LinearizationTable", "Linearization Table", "线性化表", "リニアライズテーブル"),
+    (0xc619, "BlackLevelRepeatDim", "Black Level Repeat Dim", "黑电平重复尺寸", "黒レベル繰り返しサイズ"),
+    (0xc61a, "BlackLevel", "Black Level", "黑电平", "黒レベル"),
+    (0xc61b, "BlackLevelDeltaH", "Black Level Delta H", "黑电平水平增量", "黒レベル水平差分"),
+    (0xc61c, "BlackLevelDeltaV", "Black Level Delta V", "黑电平垂直增量", "黒レベル垂直差分"),
+    (0xc61d, "WhiteLevel", "White Level", "白电平", "白レベル"),
+    (0xc61e, "DefaultScale", "Default Scale", "默认缩放", "デフォルトスケール"),
+    (0xc61f, "DefaultCropOrigin", "Default Crop Origin", "默认裁剪原点", "デフォルトクロップ原点"),
+    (0xc620, "DefaultCropSize", "Default Crop Size", "默认裁剪尺寸", "デフォルトクロップサイズ"),
+    (0xc621, "ColorMatrix1", "Color Matrix 1", "色彩矩阵 1", "カラーマトリクス 1"),
+    (0xc622, "ColorMatrix2", "Color Matrix 2", "色彩矩阵 2", "カラーマトリクス 2"),
+    (0xc623, "CameraCalibration1", "Camera Calibration 1", "相机校准 1", "カメラキャリブレーション 1"),
+    (0xc624, "CameraCalibration2", "Camera Calibration 2", "相机校准 2", "カメラキャリブレーション 2"),
+    (0xc625, "ReductionMatrix1", "Reduction Matrix 1", "降维矩阵 1", "次元削減マトリクス 1"),
+    (0xc626, "ReductionMatrix2", "Reduction Matrix 2", "降维矩阵 2", "次元削減マトリクス 2"),
+    (0xc627, "AnalogBalance", "Analog Balance", "模拟白平衡", "アナログバランス"),
+    (0xc628, "AsShotNeutral", "As Shot Neutral", "拍摄时中性色", "撮影時ニュートラル"),
+    (0xc629, "AsShotWhiteXY", "As Shot White XY", "拍摄时白点", "撮影時白色点"),
+    (0xc62a, "BaselineExposure", "Baseline Exposure", "基准曝光", "基準露出"),
+    (0xc62b, "BaselineNoise", "Baseline Noise", "基准噪点", "基準ノイズ"),
+    (0xc62c, "BaselineSharpness", "Baseline Sharpness", "基准锐度", "基準シャープネス"),
+    (0xc62d, "BayerGreenSplit", "Bayer Green Split", "拜耳绿色分离", "ベイヤー緑分離"),
+    (0xc62e, "LinearResponseLimit", "Linear Response Limit", "线性响应上限", "線形応答の上限"),
+    (0xc62f, "CameraSerialNumber", "Camera Serial Number", "相机序列号", "カメラシリアル番号"),
+    (0xc630, "DNGLensInfo", "DNG Lens Info", "DNG 镜头信息", "DNG レンズ情報"),
+    (0xc631, "ChromaBlurRadius", "Chroma Blur Radius", "色度模糊半径", "色ぼかし半径"),
+    (0xc632, "AntiAliasStrength", "Anti Alias Strength", "抗锯齿强度", "アンチエイリアス強度"),
+    (0xc633, "ShadowScale", "Shadow Scale", "阴影缩放", "シャドウスケール"),
+    (0xc634, "DNGPrivateData", "DNG Private Data", "DNG 私有数据", "DNG プライベートデータ"),
+    (0xc635, "MakerNoteSafety", "Maker Note Safety", "制造商注释安全性", "メーカーノートの安全性"),
+    (0xc65a, "CalibrationIlluminant1", "Calibration Illuminant 1", "校准光源 1", "キャリブレーション光源 1"),
+    (0xc65b, "CalibrationIlluminant2", "Calibration Illuminant 2", "校准光源 2", "キャリブレーション光源 2"),
+    (0xc65c, "BestQualityScale", "Best Quality Scale", "最佳质量缩放", "最高画質スケール"),
+    (0xc65d, "RawDataUniqueID", "Raw Data Unique ID", "RAW 数据唯一 ID", "RAW データ固有 ID"),
+    (0xc68b, "OriginalRawFileName", "Original Raw File Name", "原始 RAW 文件名", "元の RAW ファイル名"),
+    (0xc68c, "OriginalRawFileData", "Original Raw File Data", "原始 RAW 文件数据", "元の RAW ファイルデータ"),
+    (0xc68d, "ActiveArea", "Active Area", "有效区域", "有効領域"),
+    (0xc68e, "MaskedAreas", "Masked Areas", "遮蔽区域", "マスク領域"),
+    (0xc68f, "AsShotICCProfile", "As Shot ICC Profile", "拍摄时 ICC 配置文件", "撮影時 ICC プロファイル"),
+    (0xc690, "AsShotPreProfileMatrix", "As Shot Pre Profile Matrix", "拍摄时预配置矩阵", "撮影時プリプロファイルマトリクス"),
+    (0xc691, "CurrentICCProfile", "Current ICC Profile", "当前 ICC 配置文件", "現在の ICC プロファイル"),
+    (0xc692, "CurrentPreProfileMatrix", "Current Pre Profile Matrix", "当前预配置矩阵", "現在のプリプロファイルマトリクス"),
+    (0xc6bf, "ColorimetricReference", "Colorimetric Reference", "色度参考", "測色基準"),
+    (0xc6f3, "CameraCalibrationSignature", "Camera Calibration Signature", "相机校准签名", "カメラキャリブレーション署名"),
+    (0xc6f4, "ProfileCalibrationSignature", "Profile Calibration Signature", "配置文件校准签名", "プロファイルキャリブレーション署名"),
+    (0xc6f5, "ExtraCameraProfiles", "Extra Camera Profiles", "附加相机配置文件", "追加カメラプロファイル"),
+    (0xc6f6, "AsShotProfileName", "As Shot Profile Name", "拍摄时配置文件名称", "撮影時プロファイル名"),
+    (0xc6f7, "NoiseReductionApplied", "Noise Reduction Applied", "已应用降噪", "適用済みノイズ低減"),
+    (0xc6f8, "ProfileName", "Profile Name", "配置文件名称", "プロファイル名"),
+    (0xc6f9, "ProfileHueSatMapDims", "Profile Hue Sat Map Dims", "色相饱和度映射尺寸", "色相彩度マップのサイズ"),
+    (0xc6fa, "ProfileHueSatMapData1", "Profile Hue Sat Map Data 1", "色相饱和度映射 1", "色相彩度マップ 1"),
+    (0xc6fb, "ProfileHueSatMapData2", "Profile Hue Sat Map Data 2", "色相饱和度映射 2", "色相彩度マップ 2"),
+    (0xc6fc, "ProfileToneCurve", "Profile Tone Curve", "配置文件色调曲线", "プロファイルトーンカーブ"),
+    (0xc6fd, "ProfileEmbedPolicy", "Profile Embed Policy", "配置文件嵌入策略", "プロファイル埋め込みポリシー"),
+    (0xc6fe, "ProfileCopyright", "Profile Copyright", "配置文件版权", "プロファイル著作権"),
+    (0xc714, "ForwardMatrix1", "Forward Matrix 1", "正向矩阵 1", "フォワードマトリクス 1"),
+    (0xc715, "ForwardMatrix2", "Forward Matrix 2", "正向矩阵 2", "フォワードマトリクス 2"),
+    (0xc716, "PreviewApplicationName", "Preview Application Name", "预览生成程序", "プレビュー作成アプリケーション"),
+    (0xc717, "PreviewApplicationVersion", "Preview Application Version", "预览生成程序版本", "プレビュー作成アプリケーションのバージョン"),
+    (0xc718, "PreviewSettingsName", "Preview Settings Name", "预览设置名称", "プレビュー設定名"),
+    (0xc719, "PreviewSettingsDigest", "Preview Settings Digest", "预览设置摘要", "プレビュー設定ダイジェスト"),
+    (0xc71a, "PreviewColorSpace", "Preview Color Space", "预览色彩空间", "プレビュー色空間"),
+    (0xc71b, "PreviewDateTime", "Preview Date/Time", "预览生成时间", "プレビュー作成日時"),
+    (0xc71c, "RawImageDigest", "Raw Image Digest", "RAW 图像摘要（旧式）", "RAW 画像ダイジェスト（旧式）"),
+    (0xc71d, "OriginalRawFileDigest", "Original Raw File Digest", "原始 RAW 文件摘要", "元の RAW ファイルダイジェスト"),
+    (0xc71e, "SubTileBlockSize", "Sub Tile Block Size", "子图块尺寸", "サブタイルブロックサイズ"),
+    (0xc71f, "RowInterleaveFactor", "Row Interleave Factor", "行交错系数", "行インターリーブ係数"),
+    (0xc725, "ProfileLookTableDims", "Profile Look Table Dims", "外观表尺寸", "ルックテーブルのサイズ"),
+    (0xc726, "ProfileLookTableData", "Profile Look Table Data", "外观表数据", "ルックテーブルデータ"),
+    (0xc740, "OpcodeList1", "Opcode List 1", "操作码列表 1", "オペコードリスト 1"),
+    (0xc741, "OpcodeList2", "Opcode List 2", "操作码列表 2", "オペコードリスト 2"),
+    (0xc74e, "OpcodeList3", "Opcode List 3", "操作码列表 3", "オペコードリスト 3"),
+    (0xc761, "NoiseProfile", "Noise Profile", "噪点配置", "ノイズプロファイル"),
+    (0xc791, "OriginalDefaultFinalSize", "Original Default Final Size", "原始默认最终尺寸", "元のデフォルト最終サイズ"),
+    (0xc792, "OriginalBestQualitySize", "Original Best Quality Size", "原始最佳质量尺寸", "元の最高画質サイズ"),
+    (0xc793, "OriginalDefaultCropSize", "Original Default Crop Size", "原始默认裁剪尺寸", "元のデフォルトクロップサイズ"),
+    (0xc7a3, "ProfileHueSatMapEncoding", "Profile Hue Sat Map Encoding", "色相饱和度映射编码", "色相彩度マップのエンコード"),
+    (0xc7a4, "ProfileLookTableEncoding", "Profile Look Table Encoding", "外观表编码", "ルックテーブルのエンコード"),
+    (0xc7a5, "BaselineExposureOffset", "Baseline Exposure Offset", "基准曝光偏移", "基準露出オフセット"),
+    (0xc7a6, "DefaultBlackRender", "Default Black Render", "默认黑色渲染", "デフォルト黒レンダリング"),
+    (0xc7a7, "NewRawImageDigest", "New Raw Image Digest", "RAW 图像摘要", "RAW 画像ダイジェスト"),
+    (0xc7a8, "RawToPreviewGain", "Raw To Preview Gain", "RAW 到预览增益", "RAW からプレビューへのゲイン"),
+    (0xc7b5, "DefaultUserCrop", "Default User Crop", "默认用户裁剪", "デフォルトユーザークロップ"),
+    (0xc7e9, "DepthFormat", "Depth Format", "深度格式", "深度形式"),
+    (0xc7ea, "DepthNear", "Depth Near", "最近深度", "最近深度"),
+    (0xc7eb, "DepthFar", "Depth Far", "最远深度", "最遠深度"),
+    (0xc7ec, "DepthUnits", "Depth Units", "深度单位", "深度の単位"),
+    (0xc7ed, "DepthMeasureType", "Depth Measure Type", "深度测量方式", "深度の測定方式"),
+    (0xc7ee, "EnhanceParams", "Enhance Params", "增强参数", "強化パラメーター"),
+    (0xcd2d, "ProfileGainTableMap", "Profile Gain Table Map", "增益表映射", "ゲインテーブルマップ"),
+    (0xcd2e, "SemanticName", "Semantic Name", "语义名称", "セマンティック名"),
+    (0xcd30, "SemanticInstanceID", "Semantic Instance ID", "语义实例 ID", "セマンティックインスタンス ID"),
+    (0xcd31, "CalibrationIlluminant3", "Calibration Illuminant 3", "校准光源 3", "キャリブレーション光源 3"),
+    (0xcd32, "CameraCalibration3", "Camera Calibration 3", "相机校准 3", "カメラキャリブレーション 3"),
+    (0xcd33, "ColorMatrix3", "Color Matrix 3", "色彩矩阵 3", "カラーマトリクス 3"),
+    (0xcd34, "ForwardMatrix3", "Forward Matrix 3", "正向矩阵 3", "フォワードマトリクス 3"),
+    (0xcd35, "IlluminantData1", "Illuminant Data 1", "光源数据 1", "光源データ 1"),
+    (0xcd36, "IlluminantData2", "Illuminant Data 2", "光源数据 2", "光源データ 2"),
+    (0xcd37, "IlluminantData3", "Illuminant Data 3", "光源数据 3", "光源データ 3"),
+    (0xcd38, "MaskSubArea", "Mask Sub Area", "蒙版子区域", "マスクサブ領域"),
+    (0xcd39, "ProfileHueSatMapData3", "Profile Hue Sat Map Data 3", "色相饱和度映射 3", "色相彩度マップ 3"),
+    (0xcd3a, "ReductionMatrix3", "Reduction Matrix 3", "降维矩阵 3", "次元削減マトリクス 3"),
+    (0xcd3f, "RGBTables", "RGB Tables", "RGB 表", "RGB テーブル"),
+    (0xcd40, "ProfileGainTableMap2", "Profile Gain Table Map 2", "增益表映射 2", "ゲインテーブルマップ 2"),
+    (0xcd43, "ColumnInterleaveFactor", "Column Interleave Factor", "列交错系数", "列インターリーブ係数"),
+    (0xcd44, "ImageSequenceInfo", "Image Sequence Info", "图像序列信息", "画像シーケンス情報"),
+    (0xcd46, "ImageStats", "Image Stats", "图像统计", "画像統計"),
+    (0xcd47, "ProfileDynamicRange", "Profile Dynamic Range", "配置文件动态范围", "プロファイルのダイナミックレンジ"),
+    (0xcd48, "ProfileGroupName", "Profile Group Name", "配置文件组名称", "プロファイルグループ名"),
+    (0xcd49, "JXLDistance", "JXL Distance", "JPEG XL 距离", "JPEG XL 距離"),
+    (0xcd4a, "JXLEffort", "JXL Effort", "JPEG XL 压缩力度", "JPEG XL エフォート"),
+    (0xcd4b, "JXLDecodeSpeed", "JXL Decode Speed", "JPEG XL 解码速度", "JPEG XL デコード速度"),
+];
+
+/// CinemaDNG 中的标签
+const CINEMA_DNG_TAGS: &[ExtraTag] = &[
+    (0xc763, "TimeCodes", "Time Codes", "时间码", "タイムコード"),
+    (0xc764, "FrameRate", "Frame Rate", "帧率", "フレームレート"),
+    (0xc772, "TStop", "T-Stop", "T 值", "T 値"),
+    (0xc789, "ReelName", "Reel Name", "卷名", "リール名"),
+    (0xc7a1, "CameraLabel", "Camera Label", "相机标签", "カメララベル"),
+];
+
+/// 常见软件写入的私有标签
+const PRIVATE_TAGS: &[ExtraTag] = &[
+    (0x4746, "Rating", "Rating", "评级", "レーティング"),
+    (0x4749, "RatingPercent", "Rating Percent", "评级百分比", "レーティング（パーセント）"),
+    (0x8649, "PhotoshopSettings", "Photoshop Settings", "Photoshop 设置", "Photoshop 設定"),
+    (0x935c, "ImageSourceData", "Image Source Data", "图层数据", "レイヤーデータ"),
+    (0x9c9b, "XPTitle", "XP Title", "标题 (Windows)", "タイトル (Windows)"),
+    (0x9c9c, "XPComment", "XP Comment", "备注 (Windows)", "コメント (Windows)"),
+    (0x9c9d, "XPAuthor", "XP Author", "作者 (Windows)", "作成者 (Windows)"),
+    (0x9c9e, "XPKeywords", "XP Keywords", "标记 (Windows)", "タグ (Windows)"),
+    (0x9c9f, "XPSubject", "XP Subject", "主题 (Windows)", "件名 (Windows)"),
+    (0xc4a5, "PrintIM", "Print Image Matching", "打印图像匹配", "プリントイメージマッチング"),
+    (0xea1c, "Padding", "Padding", "填充", "パディング"),
+    (0xea1d, "OffsetSchema", "Offset Schema", "偏移模式", "オフセットスキーマ"),
+];
+
+/// 各规范的标签表：(所在 IFD, 规范, 标签表)
+///
+/// IFD0 与 ExifIFD 共用 TIFF 的编号空间，TIFF/EP 等标签在两处都可能出现，统一记为 GENERIC
+const EXTRA_TAG_TABLES: &[(ExifTagGroup, TagSpec, &[ExtraTag])] = &[
+    (ExifTagGroup::GENERIC, TagSpec::Tiff, TIFF_TAGS),
+    (ExifTagGroup::GENERIC, TagSpec::TiffEp, TIFF_EP_TAGS),
+    (ExifTagGroup::GENERIC, TagSpec::Exif, EXIF_TAGS),
+    (ExifTagGroup::GENERIC, TagSpec::Dng, DNG_TAGS),
+    (ExifTagGroup::GENERIC, TagSpec::CinemaDng, CINEMA_DNG_TAGS),
+    (ExifTagGroup::GENERIC, TagSpec::Private, PRIVATE_TAGS),
+    (ExifTagGroup::INTEROP, TagSpec::Exif, INTEROP_TAGS),
+];
+
+/// 在各规范的标签表中查找 little_exif 不认识的标签
+fn find_extra_tag(tag: &ExifTag) -> Option<(TagSpec, &'static ExtraTag)> {
+    if !tag.is_unknown() {
+        return None;
+    }
+    let group = match tag.get_group() {
+        ExifTagGroup::EXIF => ExifTagGroup::GENERIC,
+        group => group,
+    };
+    EXTRA_TAG_TABLES
+        .iter()
+        .filter(|(table_group, ..)| *table_group == group)
+        .find_map(|(_, spec, table)| table.iter().find(|(id, ..)| *id == tag.as_u16()).map(|entry| (*spec, entry)))
+}
+
+/// 查找 little_exif 不认识的 TIFF / EXIF / DNG 标签的名称
+///
+/// # 返回值
+/// - (规范名称, 当前语言下的名称)，不在已知标签表中时返回 None
+pub fn extra_tag_name(tag: &ExifTag) -> Option<(&'static str, &'static str)> {
+    find_extra_tag(tag).map(|(_, &(_, name, en, zh, ja))| (name, tr(en, zh, ja)))
+}
+
+/// 格式化 little_exif 不认识的标签的值
+fn format_unknown_tag_value(tag: &ExifTag) -> Option<String> {
+    let text = match tag {
+        // DNG 版本号写成 1.4.0.0
+        ExifTag::UnknownINT8U(v, 0xc612 | 0xc613, _) => v.iter().map(|b| b.to_string()).collect::<Vec<_>>().join("."),
+        // DNG 的校准光源与 EXIF LightSource 取值相同
+        ExifTag::UnknownINT16U(v, 0xc65a | 0xc65b | 0xcd31, _) if !v.is_empty() => light_source_name(v[0]),
+        // Windows 的 XP 标签固定为 UCS-2 小端字符串
+        ExifTag::UnknownINT8U(v, 0x9c9b..=0x9c9f, _) => decode_utf16(v, &Endian::Little),
+        // TIFF/EP 的 CFA 图案与 EXIF 相同，只是没有尺寸前缀
+        ExifTag::UnknownINT8U(v, 0x828e, _) | ExifTag::UnknownUNDEF(v, 0x828e, _) => {
+            v.iter().map(|&c| cfa_color_name(c)).collect::<Vec<_>>().join(",")
+        }
+        ExifTag::UnknownSTRING(s, _, _) => s.trim_matches('\0').trim().to_string(),
+        ExifTag::UnknownINT8U(v, _, _) if v.len() > 16 => byte_count(v.len()),
+        ExifTag::UnknownINT8U(v, _, _) => list(v),
+        // 较短且可打印的二进制值多为版本号之类的文本
+        ExifTag::UnknownUNDEF(v, _, _) if !v.is_empty() && v.len() <= 16 && v.iter().all(|b| b.is_ascii_graphic() || *b == b' ') => {
+            String::from_utf8_lossy(v).trim().to_string()
+        }
+        ExifTag::UnknownUNDEF(v, _, _) => byte_count(v.len()),
+        ExifTag::UnknownINT16U(v, _, _) => list(v),
+        ExifTag::UnknownINT32U(v, _, _) => list(v),
+        ExifTag::UnknownINT8S(v, _, _) => list(v),
+        ExifTag::UnknownINT16S(v, _, _) => list(v),
+        ExifTag::UnknownINT32S(v, _, _) => list(v),
+        ExifTag::UnknownFLOAT(v, _, _) => list(v),
+        ExifTag::UnknownDOUBLE(v, _, _) => list(v),
+        ExifTag::UnknownRATIONAL64U(v, _, _) => {
+            list(&v.iter().map(|r| ratio(r.nominator as f64, r.denominator as f64)).collect::<Vec<_>>())
+        }
+        ExifTag::UnknownRATIONAL64S(v, _, _) => {
+            list(&v.iter().map(|r| ratio(r.nominator as f64, r.denominator as f64)).collect::<Vec<_>>())
+        }
+        _ => return None,
+    };
+    Some(if text.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { text })
+}
+
+/// 按原始值显示标签，用于值为空或个数不足等无法按含义解释的情况
+fn format_raw_value(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+    match value {
+        Value::String(s) if !s.trim().is_empty() => s.trim().to_string(),
+        Value::Array(items) if !items.is_empty() => list(
+            &items
+                .iter()
+                .map(|item| match item.as_array().map(Vec::as_slice) {
+                    // 有理数为 [分子, 分母]
+                    Some([n, d]) => ratio(n.as_f64().unwrap_or_default(), d.as_f64().unwrap_or_default()),
+                    _ => item.to_string(),
+                })
+                .collect::<Vec<_>>(),
+        ),
+        _ => tr("(empty)", "空值", "（空）").to_string(),
+    }
+}
+
+/// 以逗号连接多个值，值太多时只列出前 8 个和总数
+fn list<T: std::fmt::Display>(values: &[T]) -> String {
+    let shown = values.iter().take(if values.len() > 16 { 8 } else { 16 }).map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+    if values.len() > 16 {
+        format!("{}, … ({} {})", shown, values.len(), tr("values", "个值", "個の値"))
+    } else {
+        shown
+    }
+}
+
+/// 有理数的小数形式，最多保留 4 位小数
+fn ratio(n: f64, d: f64) -> String {
+    if d == 0.0 { tr("undefined", "未定义", "未定義").to_string() } else { format!("{}", (n / d * 10000.0).round() / 10000.0) }
+}
+
+/// LightSource 和 DNG CalibrationIlluminant 的取值
+fn light_source_name(value: u16) -> String {
+    match value {
+        0 => tr("Unknown", "未知", "不明").to_string(),
+        1 => tr("Daylight", "日光", "昼光").to_string(),
+        2 => tr("Fluorescent", "荧光灯", "蛍光灯").to_string(),
+        3 => tr("Tungsten (Incandescent)", "钨丝灯", "タングステン（白熱灯）").to_string(),
+        4 => tr("Flash", "闪光灯", "フラッシュ").to_string(),
+        9 => tr("Fine weather", "晴朗天气", "晴天").to_string(),
+        10 => tr("Cloudy", "阴天", "曇天").to_string(),
+        11 => tr("Shade", "阴影", "日陰").to_string(),
+        12 => tr("Daylight fluorescent", "日光色荧光灯", "昼光色蛍光灯").to_string(),
+        13 => tr("Day white fluorescent", "日光白色荧光灯", "昼白色蛍光灯").to_string(),
+        14 => tr("Cool white fluorescent", "冷白荧光灯", "白色蛍光灯").to_string(),
+        15 => tr("White fluorescent", "白荧光灯", "温白色蛍光灯").to_string(),
+        16 => tr("Warm white fluorescent", "暖白荧光灯", "電球色蛍光灯").to_string(),
+        17 => tr("Standard light A", "标准灯光A", "標準光 A").to_string(),
+        18 => tr("Standard light B", "标准灯光B", "標準光 B").to_string(),
+        19 => tr("Standard light C", "标准灯光C", "標準光 C").to_string(),
+        20 => "D55".to_string(),
+        21 => "D65".to_string(),
+        22 => "D75".to_string(),
+        23 => "D50".to_string(),
+        24 => tr("ISO studio tungsten", "ISO工作室钨灯", "ISO スタジオタングステン").to_string(),
+        255 => tr("Other", "其他", "その他").to_string(),
+        _ => format!("{}: {}", tr("Unknown light source", "未知光源", "不明な光源"), value)
+    }
+}
+
+/// 解码 UserComment 等带 8 字节字符集前缀的文本
+///
+/// 前缀为 "ASCII"、"UNICODE"（按文件字节序的 UCS-2）、"JIS" 或全 0（未定义），
+/// 没有可识别前缀时整体按 UTF-8 解码
+fn decode_comment(data: &[u8], endian: &Endian) -> String {
+    if data.len() < 8 {
+        return String::from_utf8_lossy(data).to_string();
+    }
+    let (prefix, text) = data.split_at(8);
+    match prefix {
+        b"UNICODE\0" => decode_utf16(text, endian),
+        b"ASCII\0\0\0" | b"JIS\0\0\0\0\0" | [0, 0, 0, 0, 0, 0, 0, 0] => String::from_utf8_lossy(text).to_string(),
+        _ => String::from_utf8_lossy(data).to_string(),
+    }
+}
+
+/// 解码 UCS-2 文本，去掉结尾的 NUL
+fn decode_utf16(data: &[u8], endian: &Endian) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| match endian {
+            Endian::Big => u16::from_be_bytes([c[0], c[1]]),
+            Endian::Little => u16::from_le_bytes([c[0], c[1]]),
+        })
+        .collect();
+    String::from_utf16_lossy(&units).trim_end_matches('\0').to_string()
+}
+
+/// CFA 图案中的颜色编号
+fn cfa_color_name(color: u8) -> &'static str {
+    match color {
+        0 => tr("Red", "红", "赤"),
+        1 => tr("Green", "绿", "緑"),
+        2 => tr("Blue", "蓝", "青"),
+        3 => tr("Cyan", "青", "シアン"),
+        4 => tr("Magenta", "品红", "マゼンタ"),
+        5 => tr("Yellow", "黄", "黄"),
+        6 => tr("White", "白", "白"),
+        _ => "?",
+    }
+}
+
+/// 格式化 EXIF CFAPattern：两个 u16 给出重复图案的列数和行数，之后每字节一个颜色
+///
+/// 有些相机按与文件相反的字节序写尺寸，按文件字节序对不上时换一种再试
+///
+/// # 返回值
+/// - 形如 "[Red,Green][Green,Blue]" 的逐行表示，数据长度与尺寸不符时返回 None
+fn format_cfa_pattern(data: &[u8], endian: &Endian) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let colors = &data[4..];
+    let dims = |endian: &Endian| match endian {
+        Endian::Big => (u16::from_be_bytes([data[0], data[1]]) as usize, u16::from_be_bytes([data[2], data[3]]) as usize),
+        Endian::Little => (u16::from_le_bytes([data[0], data[1]]) as usize, u16::from_le_bytes([data[2], data[3]]) as usize),
+    };
+    let swapped = match endian {
+        Endian::Big => Endian::Little,
+        Endian::Little => Endian::Big,
+    };
+    let (columns, rows) = [dims(endian), dims(&swapped)]
+        .into_iter()
+        .find(|&(columns, rows)| columns > 0 && columns * rows == colors.len())?;
+    Some(
+        colors
+            .chunks(columns)
+            .take(rows)
+            .map(|row| format!("[{}]", row.iter().map(|&c| cfa_color_name(c)).collect::<Vec<_>>().join(",")))
+            .collect(),
+    )
+}
+
+/// "N 字节数据"
+fn byte_count(len: usize) -> String {
+    format!("{} {}", len, tr("bytes of data", "字节数据", "バイトのデータ"))
+}
+
+/// 秒的单位
+fn seconds_unit() -> &'static str {
+    tr("s", "秒", "秒")
+}
+
+fn return_ori_val_16(vec: &[u16]) -> String {
+    if let Some(&value) = vec.first() {
+        format!("{}", value)
+    } else {
+        "None".to_string()
+    }
+}
+
+/// 获取标签值的字符串表示
+/// ### 参数
+/// - `tag`: EXIF 标签
+/// - `endian`: 字节序信息，用于解码 UCS-2 注释和 CFA 图案
+/// ### 返回值
+/// - 格式化后的标签值字符串
+pub fn get_tag_value_string(tag: &ExifTag, endian: &Endian) -> String {
+    // 处理字符串类型的标签
+    if let 
+        ExifTag::Make(s) | 
+        ExifTag::Model(s) | 
+        ExifTag::Software(s) |
+        ExifTag::Artist(s) | 
+        ExifTag::Copyright(s) | 
+        ExifTag::ImageDescription(s) |
+        ExifTag::CreateDate(s) | 
+        ExifTag::DateTimeOriginal(s) | 
+        ExifTag::ModifyDate(s) |
+        ExifTag::GPSLatitudeRef(s) | 
+        ExifTag::GPSLongitudeRef(s) |
+        ExifTag::GPSSatellites(s) |
+        ExifTag::GPSStatus(s) |
+        ExifTag::GPSMeasureMode(s) |
+        ExifTag::GPSSpeedRef(s) |
+        ExifTag::GPSTrackRef(s) |
+        ExifTag::GPSImgDirectionRef(s) |
+        ExifTag::GPSMapDatum(s) |
+        ExifTag::GPSDestLatitudeRef(s) |
+        ExifTag::GPSDestLongitudeRef(s) |
+        ExifTag::GPSDestBearingRef(s) |
+        ExifTag::GPSDestDistanceRef(s) |
+        ExifTag::GPSDateStamp(s) |
+        ExifTag::InteroperabilityIndex(s) |
+        ExifTag::OffsetTime(s) |
+        ExifTag::OffsetTimeOriginal(s) |
+        ExifTag::OffsetTimeDigitized(s) |
+        ExifTag::SpectralSensitivity(s) |
+        ExifTag::RelatedSoundFile(s) |
+        ExifTag::OwnerName(s) |
+        ExifTag::LensMake(s) |
+        ExifTag::LensModel(s) |
+        ExifTag::LensSerialNumber(s) |
+        ExifTag::SerialNumber(s) |
+        ExifTag::SubSecTime(s) |
+        ExifTag::SubSecTimeOriginal(s) |
+        ExifTag::SubSecTimeDigitized(s) |
+        ExifTag::ImageUniqueID(s) = tag {
+        let trimmed = s.trim_matches('\0').trim();
+        return if trimmed.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { trimmed.to_string() };
+    }
+
+    // MakerNote 的内容由各厂商的解码器解析，这里只给出厂商和大小
+    if let ExifTag::MakerNote(v) = tag {
+        return match crate::makernote::maker_note_vendor(v, None) {
+            Some(vendor) => format!("{}{}{} {}", vendor, tr(" format, ", " 格式，", " 形式、"), v.len(), tr("bytes", "字节", "バイト")),
+            None => byte_count(v.len()),
+        };
+    }
+
+    // 处理带 8 字节字符集前缀的注释类型标签
+    if let
+        ExifTag::UserComment(v) |
+        ExifTag::GPSProcessingMethod(v) |
+        ExifTag::GPSAreaInformation(v) = tag {
+        let text = decode_comment(v, endian);
+        let trimmed = text.trim_matches('\0').trim();
+        return if trimmed.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { trimmed.to_string() };
+    }
+
+    // 处理有固定取值的 UNDEFINED 类型标签
+    if let
+        ExifTag::ComponentsConfiguration(v) |
+        ExifTag::FileSource(v) |
+        ExifTag::SceneType(v) |
+        ExifTag::CFAPattern(v) = tag {
+        return match tag {
+            ExifTag::ComponentsConfiguration(_) => {
+                // 每个字节表示一个分量，0 表示不存在
+                let components: Vec<&str> = v.iter().filter(|&&c| c != 0).map(|&c| match c {
+                    1 => "Y",
+                    2 => "Cb",
+                    3 => "Cr",
+                    4 => "R",
+                    5 => "G",
+                    6 => "B",
+                    _ => "?",
+                }).collect();
+                if components.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { components.join(", ") }
+            }
+            ExifTag::FileSource(_) => match v.first() {
+                Some(1) => tr("Film Scanner", "胶片扫描仪", "フィルムスキャナー").to_string(),
+                Some(2) => tr("Reflection Print Scanner", "反射式扫描仪", "反射型プリントスキャナー").to_string(),
+                Some(3) => tr("Digital Camera", "数码相机", "デジタルカメラ").to_string(),
+                Some(value) => format!("{}: {}", tr("Unknown file source", "未知文件来源", "不明なファイルソース"), value),
+                None => tr("(empty)", "空值", "（空）").to_string(),
+            },
+            ExifTag::SceneType(_) => match v.first() {
+                Some(1) => tr("Directly photographed", "直接拍摄", "直接撮影された画像").to_string(),
+                Some(value) => format!("{}: {}", tr("Unknown scene type", "未知场景类型", "不明なシーンタイプ"), value),
+                None => tr("(empty)", "空值", "（空）").to_string(),
+            },
+            _ => format_cfa_pattern(v, endian).unwrap_or_else(|| byte_count(v.len())),
+        };
+    }
+
+    // 处理结构复杂的二进制标签，只给出大小
+    if let
+        ExifTag::OECF(v) |
+        ExifTag::DeviceSettingDescription(v) |
+        ExifTag::CompositeImageExposureTimes(v) = tag {
+        return byte_count(v.len());
+    }
+
+    // 处理版本号等文本形式的 UNDEFINED 类型标签
+    if let
+        ExifTag::InteroperabilityVersion(v) |
+        ExifTag::ExifVersion(v) |
+        ExifTag::FlashpixVersion(v) = tag {
+        if let Ok(s) = std::str::from_utf8(v) {
+            let trimmed = s.trim_matches('\0').trim();
+            return if trimmed.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { trimmed.to_string() };
+        } else {
+            return format!("{}: {:x?}", tr("Binary data", "字节数据", "バイナリデータ"), &v[..12.min(v.len())]);
+        }
+    }
+
+    // 处理整数部分
+    if let
+        ExifTag::GPSVersionID(vec) |
+        ExifTag::GPSAltitudeRef(vec) = tag {
+        if let Some(&value) = vec.first() {
+            return match tag {
+                ExifTag::GPSVersionID(_) => {
+                    // GPS版本ID通常以4个字节表示，如[2, 2, 0, 0]
+                    if vec.len() >= 4 {
+                        format!("{}.{}.{}.{}", vec[0], vec[1], vec[2], vec[3])
+                    } else {
+                        format!("{}: {:?}", tr("GPS version", "GPS版本", "GPS バージョン"), vec)
+                    }
+                }
+                ExifTag::GPSAltitudeRef(_) => {
+                    // GPS海拔参考：0=高于海平面，1=低于海平面
+                    match value {
+                        0 => tr("Above Sea Level", "高于海平面", "海面上").to_string(),
+                        1 => tr("Below Sea Level", "低于海平面", "海面下").to_string(),
+                        _ => format!("{}: {}", tr("Unknown altitude reference", "未知海拔参考", "不明な高度基準"), value)
+                    }
+                }
+                _ => format!("{}", value)
+            };
+        } else {
+            return tr("(empty)", "空值", "（空）").to_string();
+        }
+    }
+
+    if let
+        ExifTag::Orientation(vec) |
+        ExifTag::ResolutionUnit(vec) |
+        ExifTag::SamplesPerPixel(vec) |
+        ExifTag::PlanarConfiguration(vec) |
+        ExifTag::Compression(vec) |
+        ExifTag::PhotometricInterpretation(vec) |
+        ExifTag::CellWidth(vec) |
+        ExifTag::CellHeight(vec) |
+        ExifTag::BitsPerSample(vec) |
+        ExifTag::TransferFunction(vec) |
+        ExifTag::YCbCrSubSampling(vec) |
+        ExifTag::YCbCrPositioning(vec) |
+        ExifTag::ExposureProgram(vec) |
+        ExifTag::SensitivityType(vec) |
+        ExifTag::MeteringMode(vec) |
+        ExifTag::LightSource(vec) |
+        ExifTag::Flash(vec) |
+        ExifTag::ColorSpace(vec) |
+        ExifTag::FocalPlaneResolutionUnit(vec) |
+        ExifTag::SensingMethod(vec) |
+        ExifTag::CustomRendered(vec) |
+        ExifTag::ExposureMode(vec) |
+        ExifTag::WhiteBalance(vec) |
+        ExifTag::FocalLengthIn35mmFormat(vec) |
+        ExifTag::SceneCaptureType(vec) |
+        ExifTag::GainControl(vec) |
+        ExifTag::Contrast(vec) |
+        ExifTag::Saturation(vec) |
+        ExifTag::Sharpness(vec) |
+        ExifTag::SubjectDistanceRange(vec) |
+        ExifTag::CompositeImage(vec) |
+        ExifTag::CompositeImageCount(vec) |
+        ExifTag::SubjectArea(vec) |
+        ExifTag::SubjectLocation(vec) |
+        ExifTag::SpatialFrequencyResponse(vec) |
+        ExifTag::ColorMap(vec) |
+        ExifTag::GPSDifferential(vec) = tag {
+        if let Some(&value) = vec.first() {
+            return match tag {
+                ExifTag::WhiteBalance(_) => {
+                    // 白平衡
+                    match value {
+                        0 => tr("Auto", "自动", "オート").to_string(),
+                        1 => tr("Manual", "手动", "マニュアル").to_string(),
+                        _ => format!("{}: {}", tr("Unknown white balance", "未知白平衡", "不明なホワイトバランス"), value)
+                    }
+                }
+                ExifTag::CompositeImageCount(_) => {
+                    // 合成图像数量：[源图像总数, 实际用于合成的图像数]
+                    if vec.len() >= 2 {
+                        match current_locale() {
+                            Locale::En => format!("{} source images, {} used", vec[0], vec[1]),
+                            Locale::ZhCn => format!("共 {} 张源图像，使用 {} 张", vec[0], vec[1]),
+                            Locale::Ja => format!("元画像 {} 枚中 {} 枚を使用", vec[0], vec[1]),
+                        }
+                    } else {
+                        format!("{:?}", vec)
+                    }
+                }
+                ExifTag::Orientation(_) => {
+                    // 方向
+                    match value {
+                        1 => tr("Normal", "正常", "標準").to_string(),
+                        2 => tr("Mirror horizontal", "水平翻转", "左右反転").to_string(),
+                        3 => tr("Rotate 180", "旋转180°", "180° 回転").to_string(),
+                        4 => tr("Mirror vertical", "垂直翻转", "上下反転").to_string(),
+                        5 => tr("Mirror horizontal and rotate 270 CW", "水平翻转并顺时针旋转270°", "左右反転して反時計回りに 90° 回転").to_string(),
+                        6 => tr("Rotate 90 CW", "顺时针旋转90°", "時計回りに 90° 回転").to_string(),
+                        7 => tr("Mirror horizontal and rotate 90 CW", "水平翻转并顺时针旋转90°", "左右反転して時計回りに 90° 回転").to_string(),
+                        8 => tr("Rotate 270 CW", "顺时针旋转270°", "反時計回りに 90° 回転").to_string(),
+                        _ => format!("{}: {}", tr("Unknown orientation", "未知方向", "不明な方向"), value)
+                    }
+                }
+                ExifTag::ResolutionUnit(_) => {
+                    // 分辨率单位
+                        match value {
+                            1 => tr("None", "无单位", "単位なし"),
+                            2 => tr("inches (DPI)", "英寸（DPI）", "インチ (DPI)"),
+                            3 => tr("cm (DPC)", "厘米（DPC）", "センチメートル (DPC)"),
+                            _ => tr("Unknown unit", "未知单位", "不明な単位"),
+                        }.to_string()
+                }
+                ExifTag::SamplesPerPixel(vec) => {
+                    // 每个像素的样本数
+                    return_ori_val_16(vec)
+                }
+                ExifTag::PlanarConfiguration(vec) => {
+                    // 平面配置
+                    if let Some(&value) = vec.first() {
+                        return match value {
+                            1 => tr("Chunky", "连续", "チャンキー").to_string(),
+                            2 => tr("Planar", "分离", "プレーナー").to_string(),
+                            _ => format!("{}: {}", tr("Unknown planar configuration", "未知平面配置", "不明な画像データの並び"), value)
+                        };
+                    } else {
+                        return tr("(empty)", "空值", "（空）").to_string();
+                    }
+                }
+                ExifTag::Compression(vec) => {
+                    // 压缩
+                    if let Some(&value) = vec.first() {
+                        return match value {
+                            1 => tr("Uncompressed", "无压缩", "非圧縮").to_string(),
+                            2 => "CCITT 1D".to_string(),
+                            3 => "T4/Group 3 Fax".to_string(),
+                            4 => "T6/Group 4 Fax".to_string(),
+                            5 => "LZW".to_string(),
+                            6 => tr("JPEG (old-style)", "JPEG (旧式)", "JPEG（旧方式）").to_string(),
+                            7 => "JPEG".to_string(),
+                            8 => "Deflate".to_string(),
+                            9 => "JBIG B&W".to_string(),
+                            10 => "JBIG Color".to_string(),
+                            32773 => "PackBits".to_string(),
+                            _ => format!("{}: {}", tr("Unknown compression", "未知压缩方式", "不明な圧縮方式"), value)
+                        };
+                    } else {
+                        return tr("(empty)", "空值", "（空）").to_string();
+                    }
+                }
+                ExifTag::PhotometricInterpretation(vec) => {
+                    // 光度解释
+                    if let Some(&value) = vec.first() {
+                        return match value {
+                            0 => tr("WhiteIsZero", "白底黑图", "白が 0").to_string(),
+                            1 => tr("BlackIsZero", "黑底白图", "黒が 0").to_string(),
+                            2 => "RGB".to_string(),
+                            3 => tr("RGB Palette", "调色板", "パレット").to_string(),
+                            4 => tr("Transparency Mask", "透明蒙版", "透過マスク").to_string(),
+                            5 => "CMYK".to_string(),
+                            6 => "YCbCr".to_string(),
+                            8 => "CIELab".to_string(),
+                            _ => format!("{}: {}", tr("Unknown photometric interpretation", "未知光度解释", "不明な画素構成"), value)
+                        };
+                    } else {
+                        return tr("(empty)", "空值", "（空）").to_string();
+                    }
+                }
+                ExifTag::CellWidth(vec) => {
+                    // 单元格宽度
+                    if let Some(&value) = vec.first() {
+                        return format!("{}", value);
+                    } else {
+                        return tr("(empty)", "空值", "（空）").to_string();
+                    }
+                }
+                ExifTag::CellHeight(vec) => {
+                    // 单元格高度
+                    return_ori_val_16(vec)
+                }
+                ExifTag::BitsPerSample(vec) => {
+                    // 每个样本的位数，每个分量一个值
+                    list(vec)
+                }
+                ExifTag::TransferFunction(vec) | ExifTag::ColorMap(vec) | ExifTag::SpatialFrequencyResponse(vec) => {
+                    // 查找表
+                    list(vec)
+                }
+                ExifTag::YCbCrSubSampling(vec) => {
+                    // YCbCr 子采样，[水平, 垂直] 两个因子
+                    match vec.as_slice() {
+                        [1, 1] => "YCbCr4:4:4 (1 1)".to_string(),
+                        [1, 2] => "YCbCr4:4:0 (1 2)".to_string(),
+                        [2, 1] => "YCbCr4:2:2 (2 1)".to_string(),
+                        [2, 2] => "YCbCr4:2:0 (2 2)".to_string(),
+                        [4, 1] => "YCbCr4:1:1 (4 1)".to_string(),
+                        [4, 2] => "YCbCr4:1:0 (4 2)".to_string(),
+                        _ => list(vec),
+                    }
+                }
+                ExifTag::YCbCrPositioning(_) => {
+                    // YCbCr 位置
+                    match value {
+                        1 => tr("Centered", "居中", "中心").to_string(),
+                        2 => tr("Co-sited", "共址", "コサイト").to_string(),
+                        _ => format!("{}: {}", tr("Unknown YCbCr positioning", "未知 YCbCr 定位", "不明な YCbCr の位置"), value)
+                    }
+                }
+                ExifTag::SubjectArea(vec) => {
+                    // 主体区域：2 个值为点，3 个值为圆，4 个值为矩形
+                    match vec.as_slice() {
+                        [x, y] => format!("({}, {})", x, y),
+                        [x, y, d] => format!("({}, {}) {} {}", x, y, tr("diameter", "直径", "直径"), d),
+                        [x, y, w, h] => format!("({}, {}) {}×{}", x, y, w, h),
+                        _ => list(vec),
+                    }
+                }
+                ExifTag::SubjectLocation(vec) => {
+                    // 主体位置
+                    match vec.as_slice() {
+                        [x, y] => format!("({}, {})", x, y),
+                        _ => list(vec),
+                    }
+                }
+                ExifTag::ExposureProgram(_) => {
+                    // 曝光程序
+                    match value {
+                        0 => tr("Not Defined", "未定义", "未定義").to_string(),
+                        1 => tr("Manual", "手动", "マニュアル").to_string(),
+                        2 => tr("Program AE", "标准程序", "プログラム AE").to_string(),
+                        3 => tr("Aperture-priority AE", "光圈优先", "絞り優先").to_string(),
+                        4 => tr("Shutter speed priority AE", "快门优先", "シャッター優先").to_string(),
+                        5 => tr("Creative (Slow speed)", "创意程序", "クリエイティブプログラム").to_string(),
+                        6 => tr("Action (High speed)", "动作程序", "アクションプログラム").to_string(),
+                        7 => tr("Portrait", "肖像模式", "ポートレート").to_string(),
+                        8 => tr("Landscape", "风景模式", "風景").to_string(),
+                        _ => format!("{}: {}", tr("Unknown exposure program", "未知曝光程序", "不明な露出プログラム"), value)
+                    }
+                }
+                ExifTag::SensitivityType(_) => {
+                    // 感光度类型
+                    match value {
+                        0 => tr("Unknown", "未知", "不明").to_string(),
+                        1 => tr("Standard Output Sensitivity", "标准输出感光度", "標準出力感度").to_string(),
+                        2 => tr("Recommended Exposure Index", "推荐曝光指数", "推奨露光指数").to_string(),
+                        3 => tr("ISO Speed", "ISO感光度", "ISO スピード").to_string(),
+                        4 => tr("Standard Output Sensitivity and Recommended Exposure Index", "标准输出感光度和推荐曝光指数", "標準出力感度と推奨露光指数").to_string(),
+                        5 => tr("Standard Output Sensitivity and ISO Speed", "标准输出感光度和ISO感光度", "標準出力感度と ISO スピード").to_string(),
+                        6 => tr("Recommended Exposure Index and ISO Speed", "推荐曝光指数和ISO感光度", "推奨露光指数と ISO スピード").to_string(),
+                        7 => tr("Standard Output Sensitivity, Recommended Exposure Index and ISO Speed", "标准输出感光度、推荐曝光指数和ISO感光度", "標準出力感度、推奨露光指数と ISO スピード").to_string(),
+                        _ => format!("{}: {}", tr("Unknown sensitivity type", "未知感光度类型", "不明な感度種別"), value)
+                    }
+                }
+                ExifTag::MeteringMode(_) => {
+                    // 测光模式
+                    match value {
+                        0 => tr("Unknown", "未知", "不明").to_string(),
+                        1 => tr("Average", "平均测光", "平均").to_string(),
+                        2 => tr("Center-weighted average", "中央重点平均测光", "中央重点").to_string(),
+                        3 => tr("Spot", "点测光", "スポット").to_string(),
+                        4 => tr("Multi-spot", "多点测光", "マルチスポット").to_string(),
+                        5 => tr("Multi-segment", "模式", "分割測光").to_string(),
+                        6 => tr("Partial", "局部测光", "部分測光").to_string(),
+                        255 => tr("Other", "其他", "その他").to_string(),
+                        _ => format!("{}: {}", tr("Unknown metering mode", "未知测光模式", "不明な測光方式"), value)
+                    }
+                }
+                ExifTag::LightSource(_) => light_source_name(value),
+                ExifTag::Flash(_) => {
+                    // 闪光灯
+                    match value {
+                        0x00 => tr("No Flash", "未闪光", "発光せず").to_string(),
+                        0x01 => tr("Fired", "闪光", "発光").to_string(),
+                        0x05 => tr("Fired, Return not detected", "闪光但未检测到反射光", "発光、反射光未検出").to_string(),
+                        0x07 => tr("Fired, Return detected", "闪光且检测到反射光", "発光、反射光検出").to_string(),
+                        0x09 => tr("On, Fired", "强制闪光", "強制発光").to_string(),
+                        0x0D => tr("On, Return not detected", "强制闪光但未检测到反射光", "強制発光、反射光未検出").to_string(),
+                        0x0F => tr("On, Return detected", "强制闪光且检测到反射光", "強制発光、反射光検出").to_string(),
+                        0x10 => tr("Off, Did not fire", "未闪光，强制闪光模式", "発光禁止").to_string(),
+                        0x18 => tr("Auto, Did not fire", "自动，未闪光", "オート、発光せず").to_string(),
+                        0x19 => tr("Auto, Fired", "自动，闪光", "オート、発光").to_string(),
+                        0x1D => tr("Auto, Fired, Return not detected", "自动，闪光但未检测到反射光", "オート、発光、反射光未検出").to_string(),
+                        0x1F => tr("Auto, Fired, Return detected", "自动，闪光且检测到反射光", "オート、発光、反射光検出").to_string(),
+                        0x20 => tr("No flash function", "无闪光功能", "フラッシュ機能なし").to_string(),
+                        0x41 => tr("Fired, Red-eye reduction", "闪光，防红眼模式", "発光、赤目軽減").to_string(),
+                        0x45 => tr("Fired, Red-eye reduction, Return not detected", "闪光，防红眼模式，未检测到反射光", "発光、赤目軽減、反射光未検出").to_string(),
+                        0x47 => tr("Fired, Red-eye reduction, Return detected", "闪光，防红眼模式，检测到反射光", "発光、赤目軽減、反射光検出").to_string(),
+                        0x49 => tr("On, Red-eye reduction", "强制闪光，防红眼模式", "強制発光、赤目軽減").to_string(),
+                        0x4D => tr("On, Red-eye reduction, Return not detected", "强制闪光，防红眼模式，未检测到反射光", "強制発光、赤目軽減、反射光未検出").to_string(),
+                        0x4F => tr("On, Red-eye reduction, Return detected", "强制闪光，防红眼模式，检测到反射光", "強制発光、赤目軽減、反射光検出").to_string(),
+                        0x59 => tr("Auto, Fired, Red-eye reduction", "自动，闪光，防红眼模式", "オート、発光、赤目軽減").to_string(),
+                        0x5D => tr("Auto, Fired, Red-eye reduction, Return not detected", "自动，闪光，防红眼模式，未检测到反射光", "オート、発光、赤目軽減、反射光未検出").to_string(),
+                        0x5F => tr("Auto, Fired, Red-eye reduction, Return detected", "自动，闪光，防红眼模式，检测到反射光", "オート、発光、赤目軽減、反射光検出").to_string(),
+                        _ => format!("{}: 0x{:04X}", tr("Unknown flash mode", "未知闪光模式", "不明なフラッシュモード"), value)
+                    }
+                }
+                ExifTag::ColorSpace(_) => {
+                    // 色彩空间
+                    match value {
+                        1 => "sRGB".to_string(),
+                        2 => "Adobe RGB".to_string(),
+                        65535 => tr("Uncalibrated", "未校准", "未校正").to_string(),
+                        _ => format!("{}: {}", tr("Unknown color space", "未知色彩空间", "不明な色空間"), value)
+                    }
+                }
+                ExifTag::FocalPlaneResolutionUnit(_) => {
+                    // 分辨率单位
+                    match value {
+                        1 => tr("None", "无单位", "単位なし").to_string(),
+                        2 => tr("inches", "英寸", "インチ").to_string(),
+                        3 => tr("cm", "厘米", "センチメートル").to_string(),
+                        4 => tr("mm", "毫米", "ミリメートル").to_string(),
+                        5 => tr("µm", "微米", "マイクロメートル").to_string(),
+                        _ => format!("{}: {}", tr("Unknown resolution unit", "未知分辨率单位", "不明な解像度単位"), value)
+                    }
+                }
+                ExifTag::SensingMethod(_) => {
+                    // 感光元件类型
+                    match value {
+                        1 => tr("Not defined", "未定义", "未定義").to_string(),
+                        2 => tr("One-chip color area", "单芯片彩色区域传感器", "単板カラーエリアセンサー").to_string(),
+                        3 => tr("Two-chip color area", "双芯片彩色区域传感器", "2 板カラーエリアセンサー").to_string(),
+                        4 => tr("Three-chip color area", "三芯片彩色区域传感器", "3 板カラーエリアセンサー").to_string(),
+                        5 => tr("Color sequential area", "彩色顺序区域传感器", "色順次カラーエリアセンサー").to_string(),
+                        7 => tr("Trilinear", "三线传感器", "3 線リニアセンサー").to_string(),
+                        8 => tr("Color sequential linear", "彩色顺序线性传感器", "色順次カラーリニアセンサー").to_string(),
+                        _ => format!("{}: {}", tr("Unknown sensing method", "未知感应方法", "不明なセンサー方式"), value)
+                    }
+                }
+                ExifTag::CustomRendered(_) => {
+                    // 自定义图像处理
+                    match value {
+                        0 => tr("Normal", "正常处理", "通常処理").to_string(),
+                        1 => tr("Custom", "自定义处理", "特殊処理").to_string(),
+                        _ => format!("{}: {}", tr("Unknown custom rendering", "未知图像处理", "不明な画像処理"), value)
+                    }
+                }
+                ExifTag::ExposureMode(_) => {
+                    // 曝光模式
+                    match value {
+                        0 => tr("Auto", "自动曝光", "オート").to_string(),
+                        1 => tr("Manual", "手动曝光", "マニュアル").to_string(),
+                        2 => tr("Auto bracket", "自动包围曝光", "オートブラケット").to_string(),
+                        _ => format!("{}: {}", tr("Unknown exposure mode", "未知曝光模式", "不明な露出モード"), value)
+                    }
+                }
+                ExifTag::FocalLengthIn35mmFormat(_) => {
+                    // 0 表示未知
+                    if value == 0 { tr("Unknown", "未知", "不明").to_string() } else { format!("{} mm", value) }
+                }
+                ExifTag::SceneCaptureType(_) => {
+                    // 场景类型
+                    match value {
+                        0 => tr("Standard", "标准", "標準").to_string(),
+                        1 => tr("Landscape", "风景", "風景").to_string(),
+                        2 => tr("Portrait", "肖像", "ポートレート").to_string(),
+                        3 => tr("Night", "夜景", "夜景").to_string(),
+                        _ => format!("{}: {}", tr("Unknown scene type", "未知场景类型", "不明なシーンタイプ"), value)
+                    }
+                }
+                ExifTag::GainControl(_) => {
+                    // 增益控制
+                    match value {
+                        0 => tr("None", "无", "なし").to_string(),
+                        1 => tr("Low gain up", "低增益", "低ゲインアップ").to_string(),
+                        2 => tr("High gain up", "高增益", "高ゲインアップ").to_string(),
+                        _ => format!("{}: {}", tr("Unknown gain control", "未知增益控制", "不明なゲイン制御"), value)
+                    }
+                }
+                ExifTag::Contrast(_) => {
+                    // 对比度
+                    match value {
+                        0 => tr("Normal", "正常", "標準").to_string(),
+                        1 => tr("Soft", "柔和", "ソフト").to_string(),
+                        2 => tr("Hard", "强烈", "ハード").to_string(),
+                        _ => format!("{}: {}", tr("Unknown contrast", "未知对比度", "不明なコントラスト"), value)
+                    }
+                }
+                ExifTag::Saturation(_) => {
+                    // 饱和度
+                    match value {
+                        0 => tr("Normal", "正常", "標準").to_string(),
+                        1 => tr("Low", "低饱和度", "低彩度").to_string(),
+                        2 => tr("High", "高饱和度", "高彩度").to_string(),
+                        _ => format!("{}: {}", tr("Unknown saturation", "未知饱和度", "不明な彩度"), value)
+                    }
+                }
+                ExifTag::Sharpness(_) => {
+                    // 锐度
+                    match value {
+                        0 => tr("Normal", "正常", "標準").to_string(),
+                        1 => tr("Soft", "柔和", "ソフト").to_string(),
+                        2 => tr("Hard", "强烈", "ハード").to_string(),
+                        _ => format!("{}: {}", tr("Unknown sharpness", "未知锐度", "不明なシャープネス"), value)
+                    }
+                }
+                ExifTag::SubjectDistanceRange(_) => {
+                    // 主体距离范围
+                    match value {
+                        0 => tr("Unknown", "未知", "不明").to_string(),
+                        1 => tr("Macro", "宏观", "マクロ").to_string(),
+                        2 => tr("Close", "近距离", "近景").to_string(),
+                        3 => tr("Distant", "远距离", "遠景").to_string(),
+                        _ => format!("{}: {}", tr("Unknown subject distance range", "未知主体距离范围", "不明な被写体距離レンジ"), value)
+                    }
+                }
+                ExifTag::CompositeImage(_) => {
+                    // 合成图像
+                    match value {
+                        0 => tr("Unknown", "未知", "不明").to_string(),
+                        1 => tr("Not a Composite Image", "非合成图像", "合成画像ではない").to_string(),
+                        2 => tr("General Composite Image", "合成图像", "合成画像").to_string(),
+                        3 => tr("Composite Image Captured While Shooting", "拍摄时合成的图像", "撮影時に合成された画像").to_string(),
+                        _ => format!("{}: {}", tr("Unknown composite image type", "未知合成图像类型", "不明な合成画像種別"), value)
+                    }
+                }
+                ExifTag::GPSDifferential(_) => {
+                    // GPS 差分
+                    match value {
+                        0 => tr("No Correction", "无差分", "ディファレンシャル補正なし").to_string(),
+                        1 => tr("Differential Corrected", "差分修正", "ディファレンシャル補正あり").to_string(),
+                        _ => format!("{}: {}", tr("Unknown differential", "未知差分状态", "不明なディファレンシャル補正"), value)
+                    }
+                }
+                _ => format!("{}", value)
+            };
+        } else {
+            return tr("(empty)", "空值", "（空）").to_string();
+        }
+    }
+
+    if let
+        ExifTag::ImageWidth(vec) |
+        ExifTag::ImageHeight(vec) |
+        ExifTag::RowsPerStrip(vec) |
+        ExifTag::ExifImageWidth(vec) |
+        ExifTag::ExifImageHeight(vec) |
+        ExifTag::StandardOutputSensitivity(vec) |
+        ExifTag::RecommendedExposureIndex(vec) |
+        ExifTag::ISOSpeed(vec) |
+        ExifTag::ISOSpeedLatitudeyyy(vec) |
+        ExifTag::ISOSpeedLatitudezzz(vec) |
+        ExifTag::StripByteCounts(vec) |
+        ExifTag::ThumbnailLength(vec) |
+        ExifTag::ExifOffset(vec) |
+        ExifTag::GPSInfo(vec) |
+        ExifTag::InteropOffset(vec) = tag {
+        if let Some(&value) = vec.first() {
+            return match tag {
+                ExifTag::ImageWidth(_) | ExifTag::ImageHeight(_) |
+                ExifTag::ExifImageWidth(_) | ExifTag::ExifImageHeight(_) => format!("{} {}", value, tr("px", "像素", "ピクセル")),
+                ExifTag::StripByteCounts(_) => list(vec),
+                ExifTag::ThumbnailLength(_) => format!("{} {}", value, tr("bytes", "字节", "バイト")),
+                ExifTag::RowsPerStrip(vec) => {
+                    // 每行像素数
+                    if let Some(&value) = vec.first() {
+                        return format!("{} {}", value, tr("rows/strip", "行/带", "行/ストリップ"));
+                    } else {
+                        return tr("(empty)", "空值", "（空）").to_string();
+                    }
+                }
+
+
+                _ => format!("{}", value)
+            };
+        } else {
+            return tr("(empty)", "空值", "（空）").to_string();
+        }
+    }
+
+    // 处理偏移类标签，值为相对 TIFF 头的字节偏移
+    if let ExifTag::StripOffsets(vec, _) | ExifTag::ThumbnailOffset(vec, _) = tag {
+        return if vec.is_empty() { tr("(empty)", "空值", "（空）").to_string() } else { list(vec) };
+    }
+
+    // 处理有理数类型的标签（光圈、焦距、曝光时间等）
+    if let ExifTag::LensInfo(vec) |
+        ExifTag::FNumber(vec) | 
+        ExifTag::FocalLength(vec) | 
+        ExifTag::ExposureTime(vec) |
+        ExifTag::XResolution(vec) | 
+        ExifTag::YResolution(vec) | 
+        ExifTag::GPSLatitude(vec) |
+        ExifTag::GPSLongitude(vec) | 
+        ExifTag::GPSAltitude(vec) |
+        ExifTag::ApertureValue(vec) |
+        ExifTag::MaxApertureValue(vec) |
+        ExifTag::SubjectDistance(vec) |
+        ExifTag::CompressedBitsPerPixel(vec) |
+        ExifTag::FocalPlaneXResolution(vec) |
+        ExifTag::FocalPlaneYResolution(vec) |
+        ExifTag::Humidity(vec) |
+        ExifTag::Pressure(vec) |
+        ExifTag::Acceleration(vec) |
+        ExifTag::FlashEnergy(vec) |
+        ExifTag::ExposureIndex(vec) |
+        ExifTag::DigitalZoomRatio(vec) |
+        ExifTag::Gamma(vec) |
+        ExifTag::WhitePoint(vec) |
+        ExifTag::PrimaryChromaticities(vec) |
+        ExifTag::YCbCrCoefficients(vec) |
+        ExifTag::ReferenceBlackWhite(vec) |
+        ExifTag::GPSDOP(vec) |
+        ExifTag::GPSSpeed(vec) |
+        ExifTag::GPSTrack(vec) |
+        ExifTag::GPSImgDirection(vec) |
+        ExifTag::GPSDestBearing(vec) |
+        ExifTag::GPSDestDistance(vec) |
+        ExifTag::GPSHPositioningError(vec) |
+        ExifTag::GPSDestLatitude(vec) |
+        ExifTag::GPSDestLongitude(vec) = tag {
+        if let Some(rational) = vec.first() {
+            let value = (rational.nominator as f64) / (rational.denominator as f64);
+            return match tag {
+                ExifTag::LensInfo(_) => {
+                    // 处理镜头信息标签
+                    if vec.len() >= 4 {
+                        let min_focal = (vec[0].nominator as f64) / (vec[0].denominator as f64);
+                        let max_focal = (vec[1].nominator as f64) / (vec[1].denominator as f64);
+                        let min_aperture = (vec[2].nominator as f64) / (vec[2].denominator as f64);
+                        let max_aperture = (vec[3].nominator as f64) / (vec[3].denominator as f64);
+                        format!("{:.0}-{:.0}mm f/{:.1}-{:.1}", 
+                            min_focal, max_focal, min_aperture, max_aperture)
+                    } else {
+                        format!("{:.4}", value)
+                    }
+                }
+                ExifTag::FNumber(_) => format!("f/{:.1}", value),
+                ExifTag::FocalLength(_) => format!("{:.1} mm", value),
+                ExifTag::ExposureTime(_) => {
+                    if (0.0..1.0).contains(&value) && value > 0.0 {
+                        format!("1/{:.0} {}", (1.0f64 / value).round(), seconds_unit())
+                    } else {
+                        format!("{:.1} {}", value, seconds_unit())
+                    }
+                }
+                
+                ExifTag::XResolution(_) | ExifTag::YResolution(_) => format!("{:.0} DPI", value),
+                ExifTag::GPSLatitude(_) | ExifTag::GPSLongitude(_) |
+                ExifTag::GPSDestLatitude(_) | ExifTag::GPSDestLongitude(_) => {
+                    if vec.len() >= 3 {
+                        let degrees = (vec[0].nominator as f64) / (vec[0].denominator as f64);
+                        let minutes = (vec[1].nominator as f64) / (vec[1].denominator as f64);
+                        let seconds = (vec[2].nominator as f64) / (vec[2].denominator as f64);
+                        format!("{:.0}°{:.0}'{:.2}''", degrees, minutes, seconds)
+                    } else {
+                        format!("{:.6}", value)
+                    }
+                }
+                ExifTag::GPSAltitude(_) => format!("{:.1} {}", value, tr("m", "米", "m")),
+                // APEX 光圈值 Av = 2·log2(N)
+                ExifTag::ApertureValue(_) | ExifTag::MaxApertureValue(_) => format!("f/{:.1}", 2f64.powf(value / 2.0)),
+                ExifTag::SubjectDistance(_) => {
+                    // 分子为 0xFFFFFFFF 表示无穷远，为 0 表示未知
+                    match rational.nominator {
+                        0xFFFF_FFFF => tr("Infinity", "无穷远", "無限遠").to_string(),
+                        0 => tr("Unknown", "未知", "不明").to_string(),
+                        _ => format!("{} {}", ratio(rational.nominator as f64, rational.denominator as f64), tr("m", "米", "m")),
+                    }
+                }
+                ExifTag::DigitalZoomRatio(_) if rational.nominator == 0 => tr("Not used", "未使用", "未使用").to_string(),
+                ExifTag::DigitalZoomRatio(_) => format!("{}×", ratio(rational.nominator as f64, rational.denominator as f64)),
+                ExifTag::Humidity(_) => format!("{} %", ratio(rational.nominator as f64, rational.denominator as f64)),
+                ExifTag::Pressure(_) => format!("{} hPa", ratio(rational.nominator as f64, rational.denominator as f64)),
+                ExifTag::Acceleration(_) => format!("{} mGal", ratio(rational.nominator as f64, rational.denominator as f64)),
+                ExifTag::FlashEnergy(_) => format!("{} BCPS", ratio(rational.nominator as f64, rational.denominator as f64)),
+                ExifTag::GPSHPositioningError(_) => format!("{} {}", ratio(rational.nominator as f64, rational.denominator as f64), tr("m", "米", "m")),
+                ExifTag::GPSTrack(_) | ExifTag::GPSImgDirection(_) | ExifTag::GPSDestBearing(_) => {
+                    format!("{}°", ratio(rational.nominator as f64, rational.denominator as f64))
+                }
+                // 色度坐标、系数等多值标签
+                ExifTag::WhitePoint(_) | ExifTag::PrimaryChromaticities(_) |
+                ExifTag::YCbCrCoefficients(_) | ExifTag::ReferenceBlackWhite(_) => {
+                    vec.iter().map(|r| ratio(r.nominator as f64, r.denominator as f64)).collect::<Vec<_>>().join(", ")
+                }
+                // 速度、距离的单位由对应的 Ref 标签给出
+                _ => ratio(rational.nominator as f64, rational.denominator as f64),
+            };
+        }
+    }
+    
+    // 处理带符号有理数类型的标签（APEX 快门、亮度、环境信息等）
+    if let
+        ExifTag::ShutterSpeedValue(vec) |
+        ExifTag::BrightnessValue(vec) |
+        ExifTag::ExposureCompensation(vec) |
+        ExifTag::AmbientTemperature(vec) |
+        ExifTag::WaterDepth(vec) |
+        ExifTag::CameraElevationAngle(vec) = tag {
+        if let Some(rational) = vec.first() {
+            let value = (rational.nominator as f64) / (rational.denominator as f64);
+            let text = ratio(rational.nominator as f64, rational.denominator as f64);
+            return match tag {
+                // APEX 快门速度 Tv = -log2(t)
+                ExifTag::ShutterSpeedValue(_) => {
+                    let t = 2f64.powf(-value);
+                    if t > 0.0 && t < 1.0 {
+                        format!("1/{:.0} {}", (1.0 / t).round(), seconds_unit())
+                    } else {
+                        format!("{:.1} {}", t, seconds_unit())
+                    }
+                }
+                ExifTag::BrightnessValue(_) => format!("{} EV", text),
+                // 曝光补偿通常以 1/3 档为步长，保留一位小数
+                ExifTag::ExposureCompensation(_) if value == 0.0 => "0 EV".to_string(),
+                ExifTag::ExposureCompensation(_) => format!("{:+.1} EV", value),
+                ExifTag::AmbientTemperature(_) => format!("{} °C", text),
+                ExifTag::WaterDepth(_) => format!("{} {}", text, tr("m", "米", "m")),
+                _ => format!("{}°", text),
+            };
+        }
+    }
+
+    // 处理 ISO 感光度标签，部分相机会写入多个值
+    if let ExifTag::ISO(vec) = tag {
+        if !vec.is_empty() {
+            return list(vec);
+        }
+    }
+
+    // 处理 GPS 时间戳标签
+    if let ExifTag::GPSTimeStamp(vec) = tag {
+        if vec.len() >= 3 {
+            let h = (vec[0].nominator as f64) / (vec[0].denominator as f64);
+            let m = (vec[1].nominator as f64) / (vec[1].denominator as f64);
+            let s = (vec[2].nominator as f64) / (vec[2].denominator as f64);
+            return format!("{:02.0}:{:02.0}:{:.1}", h, m, s);
+        }
+    }
+
+    // 处理 DNG 等 little_exif 不认识的标签
+    if let Some(text) = format_unknown_tag_value(tag) {
+        return text;
+    }
+
+    // 其余情况（值为空或个数不足）按原始值显示
+    format_raw_value(&get_tag_raw_value(tag, endian))
+}
+
+/// 在标签列表中查找与给定标签同编号、同 IFD 的标签
+pub fn find_tag<'a>(tags: &'a [ExifTag], exif_tag: &ExifTag) -> Option<&'a ExifTag> {
+    tags.iter().find(|t| t.as_u16() == exif_tag.as_u16() && t.get_group() == exif_tag.get_group())
+}
+
+/// 获取特定标签的值
+/// 
+/// # 参数
+/// - `tags`: EXIF 标签列表
+/// - `exif_tag`: 要查找的标签类型
+/// - `endian`: 字节序信息
+/// 
+/// # 返回值
+/// - 标签值的字符串表示，如果未找到则返回当前语言下的 "未找到"
+pub fn get_tag_value(tags: &[ExifTag], exif_tag: &ExifTag, endian: &Endian) -> String {
+    find_tag(tags, exif_tag)
+        .map(|tag| get_tag_value_string(tag, endian))
+        .unwrap_or_else(|| tr("Not found", "未找到", "見つかりません").to_string())
+}
+
+/// 获取标签的英文规范名称（与 little_exif 的枚举变体名一致）
+///
+/// 未知标签没有固定名称，使用 `Tag0xXXXX` 形式
+pub fn get_canonical_tag_name(tag: &ExifTag) -> String {
+    if let Some((name, _)) = extra_tag_name(tag) {
+        return name.to_string();
+    }
+    if tag.is_unknown() {
+        return format!("Tag0x{:04X}", tag.as_u16());
+    }
+    let debug = format!("{:?}", tag);
+    debug.split('(').next().unwrap_or(&debug).to_string()
+}
+
+/// 获取标签所属 IFD 的名称
+pub fn get_ifd_name(group: ExifTagGroup) -> &'static str {
+    match group {
+        ExifTagGroup::GENERIC => "IFD0",
+        ExifTagGroup::EXIF => "ExifIFD",
+        ExifTagGroup::GPS => "GPS",
+        ExifTagGroup::INTEROP => "InteropIFD",
+    }
+}
+
+/// 按标签格式将原始字节解码为 JSON 值
+///
+/// ### 参数
+/// - `tag`: EXIF 标签
+/// - `endian`: 字节序信息
+/// ### 返回值
+/// - 字符串格式返回字符串，数值格式返回数组，有理数返回 `[分子, 分母]` 数组
+pub fn get_tag_raw_value(tag: &ExifTag, endian: &Endian) -> serde_json::Value {
+    use serde_json::Value;
+
+    // 偏移类标签的值不在 value_as_u8_vec 中
+    if let ExifTag::StripOffsets(offsets, _) | ExifTag::ThumbnailOffset(offsets, _) = tag {
+        return Value::from(offsets.clone());
+    }
+
+    let bytes = tag.value_as_u8_vec(endian);
+    let u16_at = |c: &[u8]| match endian {
+        Endian::Big => u16::from_be_bytes([c[0], c[1]]),
+        Endian::Little => u16::from_le_bytes([c[0], c[1]]),
+    };
+    let u32_at = |c: &[u8]| match endian {
+        Endian::Big => u32::from_be_bytes([c[0], c[1], c[2], c[3]]),
+        Endian::Little => u32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+    };
+    let u64_at = |c: &[u8]| match endian {
+        Endian::Big => u64::from_be_bytes(c.try_into().unwrap_or([0; 8])),
+        Endian::Little => u64::from_le_bytes(c.try_into().unwrap_or([0; 8])),
+    };
+
+    match tag.format() {
+        ExifTagFormat::STRING => {
+            Value::from(String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string())
+        }
+        ExifTagFormat::INT8U | ExifTagFormat::UNDEF => Value::from(bytes),
+        ExifTagFormat::INT8S => Value::from(bytes.iter().map(|&b| b as i8).collect::<Vec<_>>()),
+        ExifTagFormat::INT16U => Value::from(bytes.chunks_exact(2).map(u16_at).collect::<Vec<_>>()),
+        ExifTagFormat::INT16S => Value::from(bytes.chunks_exact(2).map(|c| u16_at(c) as i16).collect::<Vec<_>>()),
+        ExifTagFormat::INT32U => Value::from(bytes.chunks_exact(4).map(u32_at).collect::<Vec<_>>()),
+        ExifTagFormat::INT32S => Value::from(bytes.chunks_exact(4).map(|c| u32_at(c) as i32).collect::<Vec<_>>()),
+        ExifTagFormat::RATIONAL64U => Value::from(
+            bytes.chunks_exact(8)
+                .map(|c| Value::from(vec![u32_at(&c[..4]), u32_at(&c[4..])]))
+                .collect::<Vec<_>>(),
+        ),
+        ExifTagFormat::RATIONAL64S => Value::from(
+            bytes.chunks_exact(8)
+                .map(|c| Value::from(vec![u32_at(&c[..4]) as i32, u32_at(&c[4..]) as i32]))
+                .collect::<Vec<_>>(),
+        ),
+        ExifTagFormat::FLOAT => Value::from(
+            bytes.chunks_exact(4).map(|c| f32::from_bits(u32_at(c)) as f64).collect::<Vec<_>>(),
+        ),
+        ExifTagFormat::DOUBLE => Value::from(
+            bytes.chunks_exact(8).map(|c| f64::from_bits(u64_at(c))).collect::<Vec<_>>(),
+        ),
+    }
+}
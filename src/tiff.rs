@@ -0,0 +1,548 @@
+use std::collections::HashSet;
+
+use little_exif::{
+    endian::Endian,
+    exif_tag::ExifTag,
+    exif_tag_format::ExifTagFormat,
+    ifd::ExifTagGroup,
+    rational::{iR64, uR64},
+    u8conversion::U8conversion,
+};
+
+use crate::{error::Error, locale::tr};
+
+/// 标准 TIFF 的魔数
+pub const TIFF_MAGIC: u16 = 42;
+/// BigTIFF 的魔数
+pub const BIGTIFF_MAGIC: u16 = 43;
+
+/// 指向 SubIFD 的标签
+const TAG_SUB_IFDS: u16 = 0x014a;
+/// 指向 Exif IFD 的标签
+const TAG_EXIF_IFD: u16 = 0x8769;
+/// 指向 GPS IFD 的标签
+const TAG_GPS_IFD: u16 = 0x8825;
+/// 指向 Interop IFD 的标签
+const TAG_INTEROP_IFD: u16 = 0xa005;
+
+/// IFD 链的最大长度，防止损坏文件中的环形链表
+const MAX_IFDS: usize = 64;
+
+/// IFD 中的一个条目
+#[derive(Debug, Clone)]
+pub struct TiffEntry {
+    /// 解码后的标签
+    pub tag: ExifTag,
+    /// 文件中记录的数据类型编号（1 = BYTE … 12 = DOUBLE，16 = LONG8 …）
+    pub type_code: u16,
+    /// 元素个数
+    pub count: u64,
+    /// 数据在 TIFF 数据中的偏移（值直接存放在条目中时为条目值字段的位置）
+    pub value_offset: usize,
+}
+
+/// 一个 IFD
+#[derive(Debug, Clone)]
+pub struct TiffIfd {
+    /// IFD 名称："IFD0"、"IFD1"、"SubIFD0"、"ExifIFD"、"GPS"、"InteropIFD" 等
+    pub name: String,
+    /// IFD 在 TIFF 数据中的偏移
+    pub offset: usize,
+    /// 条目列表（不含指向其他 IFD 的指针标签）
+    pub entries: Vec<TiffEntry>,
+}
+
+impl TiffIfd {
+    /// 按标签编号查找条目
+    pub fn find(&self, tag_id: u16) -> Option<&TiffEntry> {
+        self.entries.iter().find(|e| e.tag.as_u16() == tag_id)
+    }
+}
+
+/// 解析后的 TIFF 结构
+#[derive(Debug, Clone)]
+pub struct TiffInfo {
+    /// 字节序
+    pub endian: Endian,
+    /// 头部的魔数（42 = TIFF，43 = BigTIFF，RAW 格式可能使用其他值）
+    pub magic: u16,
+    /// 是否为 BigTIFF
+    pub big_tiff: bool,
+    /// 按遍历顺序列出的全部 IFD
+    pub ifds: Vec<TiffIfd>,
+}
+
+impl TiffInfo {
+    /// 按名称查找 IFD
+    pub fn ifd(&self, name: &str) -> Option<&TiffIfd> {
+        self.ifds.iter().find(|ifd| ifd.name == name)
+    }
+
+    /// 主图像的标签：IFD0、ExifIFD、GPS 和 InteropIFD 中的全部标签
+    pub fn primary_tags(&self) -> Vec<ExifTag> {
+        self.ifds
+            .iter()
+            .filter(|ifd| matches!(ifd.name.as_str(), "IFD0" | "ExifIFD" | "GPS" | "InteropIFD"))
+            .flat_map(|ifd| ifd.entries.iter().map(|e| e.tag.clone()))
+            .collect()
+    }
+
+    /// IFD0 中 XMP 标签（0x02BC）的内容
+    pub fn xmp_packet(&self) -> Option<Vec<u8>> {
+        match &self.ifd("IFD0")?.find(0x02bc)?.tag {
+            ExifTag::UnknownINT8U(bytes, _, _) | ExifTag::UnknownUNDEF(bytes, _, _) => Some(bytes.clone()),
+            _ => None,
+        }
+    }
+
+    /// DNG 版本号（如 "1.4.0.0"），不是 DNG 文件时返回 None
+    pub fn dng_version(&self) -> Option<String> {
+        let entry = self.ifd("IFD0")?.find(0xc612)?;
+        match &entry.tag {
+            ExifTag::UnknownINT8U(v, _, _) if !v.is_empty() => {
+                Some(v.iter().map(|b| b.to_string()).collect::<Vec<_>>().join("."))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 读取 TIFF 数据的辅助结构
+struct Reader<'a> {
+    data: &'a [u8],
+    endian: Endian,
+}
+
+impl Reader<'_> {
+    fn u16_at(&self, pos: usize) -> Option<u16> {
+        let b = self.data.get(pos..pos.checked_add(2)?)?;
+        Some(match self.endian {
+            Endian::Big => u16::from_be_bytes([b[0], b[1]]),
+            Endian::Little => u16::from_le_bytes([b[0], b[1]]),
+        })
+    }
+
+    fn u32_at(&self, pos: usize) -> Option<u32> {
+        let b = self.data.get(pos..pos.checked_add(4)?)?;
+        Some(match self.endian {
+            Endian::Big => u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            Endian::Little => u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        })
+    }
+
+    fn u64_at(&self, pos: usize) -> Option<u64> {
+        let b: [u8; 8] = self.data.get(pos..pos.checked_add(8)?)?.try_into().ok()?;
+        Some(match self.endian {
+            Endian::Big => u64::from_be_bytes(b),
+            Endian::Little => u64::from_le_bytes(b),
+        })
+    }
+}
+
+/// 数据类型编号对应的单个元素字节数
+fn type_size(type_code: u16) -> Option<usize> {
+    match type_code {
+        1 | 2 | 6 | 7 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 | 13 => Some(4),
+        5 | 10 | 12 | 16 | 17 | 18 => Some(8),
+        _ => None,
+    }
+}
+
+/// 判断数据的字节序
+///
+/// # 返回值
+/// - 以 "II" 或 "MM" 开头时返回对应的字节序，否则返回 None
+pub fn detect_endian(data: &[u8]) -> Option<Endian> {
+    match data.get(..2)? {
+        b"II" => Some(Endian::Little),
+        b"MM" => Some(Endian::Big),
+        _ => None,
+    }
+}
+
+/// 解析 TIFF（或 BigTIFF）结构，遍历 IFD0 开始的 IFD 链以及 SubIFD、Exif、GPS、Interop IFD
+///
+/// # 参数
+/// - `data`: 以 "II" / "MM" 字节序标记开头的 TIFF 数据
+/// - `allowed_magic`: 除 42 / 43 之外允许的魔数（如 ORF 的 0x4F52、RW2 的 0x0055）
+///
+/// # 返回值
+/// - 解析出的 IFD 列表；头部或 IFD0 损坏时返回 MalformedTiff 错误
+///
+/// 超出数据范围的条目和子 IFD 会被跳过，不影响其余标签
+pub fn parse_tiff_with_magic(data: &[u8], allowed_magic: &[u16]) -> Result<TiffInfo, Error> {
+    parse_tiff_inner(data, allowed_magic, "IFD0", ExifTagGroup::GENERIC)
+}
+
+/// 解析只包含单个 IFD 的 TIFF 结构，并把第一个 IFD 当作指定的 IFD
+///
+/// Canon CR3 的 CMT2（Exif）、CMT4（GPS）等盒子是独立的 TIFF，其第一个 IFD 并不是 IFD0
+///
+/// # 参数
+/// - `data`: 以 "II" / "MM" 字节序标记开头的 TIFF 数据
+/// - `name`: 第一个 IFD 的名称，如 "ExifIFD"
+/// - `group`: 第一个 IFD 中标签所属的分组
+pub fn parse_tiff_as(data: &[u8], name: &str, group: ExifTagGroup) -> Result<TiffInfo, Error> {
+    parse_tiff_inner(data, &[], name, group)
+}
+
+fn parse_tiff_inner(data: &[u8], allowed_magic: &[u16], first_name: &str, first_group: ExifTagGroup) -> Result<TiffInfo, Error> {
+    let endian = detect_endian(data).ok_or_else(|| Error::MalformedTiff(tr("missing II/MM byte order mark", "缺少 II/MM 字节序标记", "II/MM バイトオーダーマークがありません").to_string()))?;
+    let r = Reader { data, endian: endian.clone() };
+    let magic = r.u16_at(2).ok_or_else(|| Error::MalformedTiff(tr("incomplete TIFF header", "TIFF 头部不完整", "TIFF ヘッダーが不完全です").to_string()))?;
+
+    let big_tiff = magic == BIGTIFF_MAGIC;
+    let first = if big_tiff {
+        // BigTIFF：偏移字节数（必须为 8）+ 保留字 + 64 位偏移
+        if r.u16_at(4) != Some(8) {
+            return Err(Error::MalformedTiff(tr("BigTIFF offset size is not 8", "BigTIFF 偏移字节数不是 8", "BigTIFF のオフセットサイズが 8 ではありません").to_string()));
+        }
+        r.u64_at(8).ok_or_else(|| Error::MalformedTiff(tr("incomplete TIFF header", "TIFF 头部不完整", "TIFF ヘッダーが不完全です").to_string()))?
+    } else if magic == TIFF_MAGIC || allowed_magic.contains(&magic) {
+        r.u32_at(4).ok_or_else(|| Error::MalformedTiff(tr("incomplete TIFF header", "TIFF 头部不完整", "TIFF ヘッダーが不完全です").to_string()))? as u64
+    } else {
+        return Err(Error::MalformedTiff(format!("{} 0x{:04X}", tr("unknown TIFF magic number", "未知的 TIFF 魔数", "不明な TIFF マジックナンバー"), magic)));
+    };
+
+    let mut walker = Walker { r, big_tiff, visited: HashSet::new(), ifds: Vec::new() };
+    let first = usize::try_from(first).map_err(|_| Error::MalformedTiff(tr("IFD0 offset out of range", "IFD0 偏移超出范围", "IFD0 のオフセットが範囲外です").to_string()))?;
+    if walker.read_ifd(first, first_name, first_group).is_none() {
+        return Err(Error::MalformedTiff(format!("{} {} {} {}", first_name, tr("offset", "偏移", "オフセット"), first, tr("is out of range", "超出数据范围", "が範囲外です"))));
+    }
+    if first_name != "IFD0" {
+        return Ok(TiffInfo { endian, magic, big_tiff, ifds: walker.ifds });
+    }
+
+    // IFD0 之后的 IFD 链：IFD1（缩略图）、IFD2 ……
+    let mut next = walker.next_ifd_offset(first);
+    let mut index = 1;
+    while let Some(offset) = next.filter(|&o| o != 0) {
+        if walker.ifds.len() >= MAX_IFDS || walker.read_ifd(offset, &format!("IFD{}", index), ExifTagGroup::GENERIC).is_none() {
+            break;
+        }
+        next = walker.next_ifd_offset(offset);
+        index += 1;
+    }
+
+    Ok(TiffInfo { endian, magic, big_tiff, ifds: walker.ifds })
+}
+
+/// 解析标准 TIFF 或 BigTIFF 结构
+///
+/// # 参数
+/// - `data`: 以 "II" / "MM" 字节序标记开头的 TIFF 数据
+pub fn parse_tiff(data: &[u8]) -> Result<TiffInfo, Error> {
+    parse_tiff_with_magic(data, &[])
+}
+
+/// 遍历 IFD 时的状态
+struct Walker<'a> {
+    r: Reader<'a>,
+    big_tiff: bool,
+    /// 已访问过的 IFD 偏移，防止循环引用
+    visited: HashSet<usize>,
+    ifds: Vec<TiffIfd>,
+}
+
+impl Walker<'_> {
+    /// 条目数字段、单个条目和偏移字段的大小
+    fn sizes(&self) -> (usize, usize, usize) {
+        if self.big_tiff { (8, 20, 8) } else { (2, 12, 4) }
+    }
+
+    fn read_offset(&self, pos: usize) -> Option<u64> {
+        if self.big_tiff { self.r.u64_at(pos) } else { self.r.u32_at(pos).map(u64::from) }
+    }
+
+    fn entry_count(&self, offset: usize) -> Option<usize> {
+        let n = if self.big_tiff { self.r.u64_at(offset)? } else { self.r.u16_at(offset)? as u64 };
+        usize::try_from(n).ok()
+    }
+
+    /// IFD 末尾的下一个 IFD 偏移
+    fn next_ifd_offset(&self, offset: usize) -> Option<usize> {
+        let (count_size, entry_size, _) = self.sizes();
+        let count = self.entry_count(offset)?;
+        let pos = offset.checked_add(count_size)?.checked_add(count.checked_mul(entry_size)?)?;
+        usize::try_from(self.read_offset(pos)?).ok()
+    }
+
+    /// 读取一个 IFD 及其指向的子 IFD
+    ///
+    /// # 返回值
+    /// - IFD 偏移无效或已访问过时返回 None
+    fn read_ifd(&mut self, offset: usize, name: &str, group: ExifTagGroup) -> Option<()> {
+        if !self.visited.insert(offset) {
+            return None;
+        }
+        let (count_size, entry_size, field_size) = self.sizes();
+        let count = self.entry_count(offset)?;
+        let entries_start = offset.checked_add(count_size)?;
+        if entries_start.checked_add(count.checked_mul(entry_size)?)? > self.r.data.len() {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        let mut children: Vec<(usize, String, ExifTagGroup)> = Vec::new();
+        for i in 0..count {
+            let pos = entries_start + i * entry_size;
+            let tag_id = self.r.u16_at(pos)?;
+            let type_code = self.r.u16_at(pos + 2)?;
+            let count = self.read_offset(pos + 4)?;
+            let field = pos + 4 + field_size;
+
+            let size = match type_size(type_code).and_then(|s| (count as usize).checked_mul(s)) {
+                Some(s) => s,
+                None => continue,
+            };
+            // 数据不超过偏移字段大小时直接存放在条目中
+            let value_offset = if size <= field_size {
+                field
+            } else {
+                match self.read_offset(field).and_then(|o| usize::try_from(o).ok()) {
+                    Some(o) => o,
+                    None => continue,
+                }
+            };
+            let raw = match value_offset.checked_add(size).and_then(|end| self.r.data.get(value_offset..end)) {
+                Some(raw) => raw,
+                None => continue,
+            };
+
+            // 指针标签只用于遍历子 IFD，不作为普通标签输出
+            match (tag_id, &group) {
+                (TAG_EXIF_IFD, ExifTagGroup::GENERIC) => {
+                    children.extend(self.pointer_values(raw, type_code).first().map(|&o| (o, "ExifIFD".to_string(), ExifTagGroup::EXIF)));
+                    continue;
+                }
+                (TAG_GPS_IFD, ExifTagGroup::GENERIC) => {
+                    children.extend(self.pointer_values(raw, type_code).first().map(|&o| (o, "GPS".to_string(), ExifTagGroup::GPS)));
+                    continue;
+                }
+                (TAG_INTEROP_IFD, ExifTagGroup::EXIF) => {
+                    children.extend(self.pointer_values(raw, type_code).first().map(|&o| (o, "InteropIFD".to_string(), ExifTagGroup::INTEROP)));
+                    continue;
+                }
+                (TAG_SUB_IFDS, ExifTagGroup::GENERIC) => {
+                    let base = self.ifds.iter().filter(|ifd| ifd.name.starts_with("SubIFD")).count();
+                    for (n, o) in self.pointer_values(raw, type_code).into_iter().enumerate() {
+                        children.push((o, format!("SubIFD{}", base + n), ExifTagGroup::GENERIC));
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(tag) = decode_entry(tag_id, type_code, raw, &self.r.endian, group) {
+                entries.push(TiffEntry { tag, type_code, count, value_offset });
+            }
+        }
+
+        self.ifds.push(TiffIfd { name: name.to_string(), offset, entries });
+        for (child_offset, child_name, child_group) in children {
+            if self.ifds.len() < MAX_IFDS {
+                self.read_ifd(child_offset, &child_name, child_group);
+            }
+        }
+        Some(())
+    }
+
+    /// 读取指针标签中的偏移列表
+    fn pointer_values(&self, raw: &[u8], type_code: u16) -> Vec<usize> {
+        let r = Reader { data: raw, endian: self.r.endian.clone() };
+        let size = type_size(type_code).unwrap_or(4);
+        (0..raw.len() / size)
+            .filter_map(|i| match size {
+                8 => r.u64_at(i * 8).and_then(|v| usize::try_from(v).ok()),
+                2 => r.u16_at(i * 2).map(usize::from),
+                _ => r.u32_at(i * 4).map(|v| v as usize),
+            })
+            .collect()
+    }
+}
+
+/// 将条目的原始数据解码为 ExifTag
+///
+/// 文件中的类型与 little_exif 对已知标签声明的类型不一致时：
+/// 无符号整数之间互相转换（如 ImageWidth 存为 SHORT），其余情况保留为未知标签
+fn decode_entry(tag_id: u16, type_code: u16, raw: &[u8], endian: &Endian, group: ExifTagGroup) -> Option<ExifTag> {
+    let raw = raw.to_vec();
+    let format = match type_code {
+        // BigTIFF 的 64 位整数：能放进 32 位时按 LONG / SLONG 处理
+        16..=18 => {
+            let r = Reader { data: &raw, endian: endian.clone() };
+            let values: Vec<u64> = (0..raw.len() / 8).filter_map(|i| r.u64_at(i * 8)).collect();
+            if values.iter().any(|&v| v > u32::MAX as u64) {
+                return None;
+            }
+            let bytes = values.iter().flat_map(|&v| (v as u32).to_u8_vec(endian)).collect();
+            let format = if type_code == 17 { ExifTagFormat::INT32S } else { ExifTagFormat::INT32U };
+            return decode_entry_with_format(tag_id, format, bytes, endian, group);
+        }
+        13 => ExifTagFormat::INT32U, // IFD 类型按 LONG 处理
+        _ => ExifTagFormat::from_u16(type_code)?,
+    };
+    decode_entry_with_format(tag_id, format, raw, endian, group)
+}
+
+fn decode_entry_with_format(
+    tag_id: u16,
+    format: ExifTagFormat,
+    raw: Vec<u8>,
+    endian: &Endian,
+    group: ExifTagGroup,
+) -> Option<ExifTag> {
+    let expected = ExifTag::from_u16(tag_id, &group).ok().map(|t| t.format());
+    match expected {
+        Some(expected) if expected != format => {
+            match (unsigned_values(&format, &raw, endian), encode_unsigned(&expected)) {
+                (Some(values), Some(encode)) => {
+                    let bytes = values.iter().flat_map(|&v| encode(v, endian)).collect();
+                    ExifTag::from_u16_with_data(tag_id, &expected, &bytes, endian, &group).ok()
+                }
+                _ => Some(unknown_tag(tag_id, &format, &raw, endian, group)),
+            }
+        }
+        _ => ExifTag::from_u16_with_data(tag_id, &format, &raw, endian, &group).ok(),
+    }
+}
+
+/// 按无符号整数格式读取全部值
+fn unsigned_values(format: &ExifTagFormat, raw: &[u8], endian: &Endian) -> Option<Vec<u32>> {
+    let r = Reader { data: raw, endian: endian.clone() };
+    match format {
+        ExifTagFormat::INT8U => Some(raw.iter().map(|&b| b as u32).collect()),
+        ExifTagFormat::INT16U => Some((0..raw.len() / 2).filter_map(|i| r.u16_at(i * 2).map(u32::from)).collect()),
+        ExifTagFormat::INT32U => Some((0..raw.len() / 4).filter_map(|i| r.u32_at(i * 4)).collect()),
+        _ => None,
+    }
+}
+
+/// 无符号整数格式的编码函数，值超出范围时截断
+fn encode_unsigned(format: &ExifTagFormat) -> Option<fn(u32, &Endian) -> Vec<u8>> {
+    match format {
+        ExifTagFormat::INT8U => Some(|v, _| vec![v as u8]),
+        ExifTagFormat::INT16U => Some(|v, e| (v as u16).to_u8_vec(e)),
+        ExifTagFormat::INT32U => Some(|v, e| v.to_u8_vec(e)),
+        _ => None,
+    }
+}
+
+/// 按文件中的实际类型构造未知标签
+fn unknown_tag(tag_id: u16, format: &ExifTagFormat, raw: &Vec<u8>, endian: &Endian, group: ExifTagGroup) -> ExifTag {
+    match format {
+        ExifTagFormat::INT8U => ExifTag::UnknownINT8U(raw.clone(), tag_id, group),
+        ExifTagFormat::STRING => ExifTag::UnknownSTRING(String::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::INT16U => ExifTag::UnknownINT16U(Vec::<u16>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::INT32U => ExifTag::UnknownINT32U(Vec::<u32>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::RATIONAL64U => ExifTag::UnknownRATIONAL64U(Vec::<uR64>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::INT8S => ExifTag::UnknownINT8S(raw.iter().map(|&b| b as i8).collect(), tag_id, group),
+        ExifTagFormat::UNDEF => ExifTag::UnknownUNDEF(raw.clone(), tag_id, group),
+        ExifTagFormat::INT16S => ExifTag::UnknownINT16S(Vec::<i16>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::INT32S => ExifTag::UnknownINT32S(Vec::<i32>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::RATIONAL64S => ExifTag::UnknownRATIONAL64S(Vec::<iR64>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::FLOAT => ExifTag::UnknownFLOAT(Vec::<f32>::from_u8_vec(raw, endian), tag_id, group),
+        ExifTagFormat::DOUBLE => ExifTag::UnknownDOUBLE(Vec::<f64>::from_u8_vec(raw, endian), tag_id, group),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 小端 TIFF 头部，IFD0 位于偏移 8
+    fn header() -> Vec<u8> {
+        b"II\x2a\0\x08\0\0\0".to_vec()
+    }
+
+    /// 追加一个 IFD：条目为 (标签, 类型, 个数, 值)，最后是下一个 IFD 的偏移
+    fn push_ifd(data: &mut Vec<u8>, entries: &[(u16, u16, u32, u32)], next: u32) {
+        data.extend((entries.len() as u16).to_le_bytes());
+        for &(tag, type_code, count, value) in entries {
+            data.extend(tag.to_le_bytes());
+            data.extend(type_code.to_le_bytes());
+            data.extend(count.to_le_bytes());
+            data.extend(value.to_le_bytes());
+        }
+        data.extend(next.to_le_bytes());
+    }
+
+    fn names(info: &TiffInfo) -> Vec<&str> {
+        info.ifds.iter().map(|ifd| ifd.name.as_str()).collect()
+    }
+
+    #[test]
+    fn ifd_chain_loops_terminate() {
+        // IFD0 指向自己
+        let mut data = header();
+        push_ifd(&mut data, &[], 8);
+        assert_eq!(names(&parse_tiff(&data).unwrap()), ["IFD0"]);
+
+        // IFD0 -> IFD1 -> IFD0
+        let mut data = header();
+        push_ifd(&mut data, &[], 14);
+        push_ifd(&mut data, &[], 8);
+        assert_eq!(names(&parse_tiff(&data).unwrap()), ["IFD0", "IFD1"]);
+    }
+
+    #[test]
+    fn ifd_chain_is_limited() {
+        // 100 个不同的空 IFD 串成链表，每个 6 字节
+        let mut data = header();
+        for i in 0..100u32 {
+            let next = if i == 99 { 0 } else { 8 + 6 * (i + 1) };
+            push_ifd(&mut data, &[], next);
+        }
+        let info = parse_tiff(&data).unwrap();
+        assert_eq!(info.ifds.len(), MAX_IFDS);
+        assert_eq!(info.ifds.last().unwrap().name, format!("IFD{}", MAX_IFDS - 1));
+    }
+
+    #[test]
+    fn sub_ifds_are_limited() {
+        // IFD0 的 SubIFDs 指向 100 个空 IFD
+        let mut data = header();
+        let ifd0_size = 2 + 12 + 4;
+        let array = 8 + ifd0_size;
+        let first_sub = array + 4 * 100;
+        push_ifd(&mut data, &[(TAG_SUB_IFDS, 4, 100, array)], 0);
+        for i in 0..100u32 {
+            data.extend((first_sub + 6 * i).to_le_bytes());
+        }
+        for _ in 0..100 {
+            push_ifd(&mut data, &[], 0);
+        }
+        let info = parse_tiff(&data).unwrap();
+        assert_eq!(info.ifds.len(), MAX_IFDS);
+        assert_eq!(info.ifds[1].name, "SubIFD0");
+        assert!(info.ifd("IFD0").unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn child_ifd_pointing_back_is_skipped() {
+        // ExifIFD 指向 IFD0，InteropIFD 指向 ExifIFD 自己
+        let mut data = header();
+        push_ifd(&mut data, &[(TAG_EXIF_IFD, 4, 1, 8), (0x0100, 3, 1, 640)], 0);
+        let exif = data.len() as u32;
+        data[8 + 2 + 8..8 + 2 + 12].copy_from_slice(&exif.to_le_bytes());
+        push_ifd(&mut data, &[(TAG_EXIF_IFD, 4, 1, 8), (TAG_INTEROP_IFD, 4, 1, exif)], 0);
+        let info = parse_tiff(&data).unwrap();
+        assert_eq!(names(&info), ["IFD0", "ExifIFD"]);
+        assert!(matches!(&info.ifd("IFD0").unwrap().entries[0].tag, ExifTag::ImageWidth(v) if v == &[640]));
+    }
+
+    #[test]
+    fn out_of_range_offsets() {
+        let mut data = header();
+        push_ifd(&mut data, &[], 0x7fff_ffff);
+        assert_eq!(names(&parse_tiff(&data).unwrap()), ["IFD0"]);
+
+        let mut data = b"II\x2a\0\xff\0\0\0".to_vec();
+        push_ifd(&mut data, &[], 0);
+        assert!(matches!(parse_tiff(&data), Err(Error::MalformedTiff(_))));
+        assert!(matches!(parse_tiff(b"II\x2b\0\x04\0\0\0"), Err(Error::MalformedTiff(_))));
+        assert!(matches!(parse_tiff(b"XX\x2a\0"), Err(Error::MalformedTiff(_))));
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    fs::File,
+    path::Path,
+    io::Read,
+};
+
+use crate::error::Error;
+
+/// VP8X 标志位：包含 ICC 配置文件
+pub const VP8X_FLAG_ICC: u8 = 0x20;
+/// VP8X 标志位：包含 Alpha 通道
+pub const VP8X_FLAG_ALPHA: u8 = 0x10;
+/// VP8X 标志位：包含 EXIF
+pub const VP8X_FLAG_EXIF: u8 = 0x08;
+/// VP8X 标志位：包含 XMP
+pub const VP8X_FLAG_XMP: u8 = 0x04;
+/// VP8X 标志位：动画
+pub const VP8X_FLAG_ANIMATION: u8 = 0x02;
+
+/// APP1 段中 EXIF 数据的前缀，部分软件会把它写进 EXIF 数据块
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// WebP 的一帧动画（ANMF 数据块）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WebpFrame {
+    pub x_offset: u32,
+    pub y_offset: u32,
+    pub width: u32,
+    pub height: u32,
+    /// 帧时长（毫秒）
+    pub duration: u32,
+    /// 帧结束后是否清为背景色
+    pub dispose_to_background: bool,
+    /// 是否与上一帧 Alpha 混合
+    pub blend: bool,
+}
+
+/// WebP 动画信息（ANIM / ANMF 数据块）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebpAnimation {
+    /// 背景色，按 ARGB 排列
+    pub background_color: u32,
+    /// 循环次数，0 表示无限循环
+    pub loop_count: u16,
+    /// 各帧信息
+    pub frames: Vec<WebpFrame>,
+}
+
+impl WebpAnimation {
+    /// 一次播放的总时长（毫秒）
+    pub fn total_duration(&self) -> u64 {
+        self.frames.iter().map(|f| f.duration as u64).sum()
+    }
+}
+
+/// WebP 容器信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebpInfo {
+    /// 图像编码方式："VP8"（有损）、"VP8L"（无损），扩展格式为 "VP8X"
+    pub encoding: String,
+    /// VP8X 标志位，简单格式为 None
+    pub flags: Option<u8>,
+    /// 画布宽度
+    pub width: u32,
+    /// 画布高度
+    pub height: u32,
+    /// ICCP 数据块中的 ICC 配置文件
+    pub icc_profile: Option<Vec<u8>>,
+    /// 动画信息
+    pub animation: Option<WebpAnimation>,
+}
+
+impl WebpInfo {
+    /// 是否设置了某个 VP8X 标志位
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags.is_some_and(|f| f & flag != 0)
+    }
+}
+
+/// WebP 文件中找到的元数据
+#[derive(Debug, Clone, Default)]
+pub struct WebpMetadataChunks {
+    /// EXIF 数据（TIFF 结构，已去掉 "Exif\0\0" 前缀）
+    pub exif: Option<Vec<u8>>,
+    /// XMP 数据包
+    pub xmp: Option<Vec<u8>>,
+    /// 容器信息
+    pub info: WebpInfo,
+}
+
+/// 读取小端 24 位整数
+fn le24(d: &[u8]) -> u32 {
+    d[0] as u32 | (d[1] as u32) << 8 | (d[2] as u32) << 16
+}
+
+/// 遍历 WebP 的 RIFF 数据块，提取 EXIF、XMP、ICC 配置文件和动画信息，只读取文件
+///
+/// # 参数
+/// - `path`: WebP 文件路径
+///
+/// # 返回值
+/// - 找到的元数据（EXIF / XMP 都可能为空）
+/// - 文件无法读取、签名错误或数据块截断时返回 Error
+pub fn extract_metadata_from_webp(path: &Path) -> Result<WebpMetadataChunks, Error> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    extract_metadata_from_webp_bytes(&buf)
+}
+
+/// 遍历内存中 WebP 文件的 RIFF 数据块，参见 [`extract_metadata_from_webp`]
+///
+/// # 参数
+/// - `buf`: 完整的 WebP 文件内容
+///
+/// # 返回值
+/// - 找到的元数据，签名错误或数据块截断时返回 Error
+pub fn extract_metadata_from_webp_bytes(buf: &[u8]) -> Result<WebpMetadataChunks, Error> {
+    // RIFF 头：'RIFF' + 文件长度（小端）+ 'WEBP'
+    if buf.len() < 12 || &buf[..4] != b"RIFF" || &buf[8..12] != b"WEBP" {
+        return Err(Error::invalid_signature("webp"));
+    }
+    let riff_size = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+    // RIFF 长度之后的数据不属于 WebP，忽略
+    let end = buf.len().min(riff_size.saturating_add(8));
+
+    let mut found = WebpMetadataChunks::default();
+    let mut pos = 12usize;
+    while pos < end {
+        let chunk_start = pos;
+        if pos + 8 > end {
+            return Err(Error::TruncatedChunk { chunk: String::new(), offset: chunk_start });
+        }
+        let fourcc = &buf[pos..pos + 4];
+        let len = u32::from_le_bytes([buf[pos + 4], buf[pos + 5], buf[pos + 6], buf[pos + 7]]) as usize;
+        let chunk_name = String::from_utf8_lossy(fourcc).trim_end().to_string();
+        pos += 8;
+        if pos + len > end {
+            return Err(Error::TruncatedChunk { chunk: chunk_name, offset: chunk_start });
+        }
+        let data = &buf[pos..pos + len];
+        // 数据块长度为奇数时后面有一个填充字节
+        pos += len + (len & 1);
+
+        match fourcc {
+            b"VP8X" if data.len() >= 10 => {
+                found.info.encoding = "VP8X".to_string();
+                found.info.flags = Some(data[0]);
+                found.info.width = le24(&data[4..7]) + 1;
+                found.info.height = le24(&data[7..10]) + 1;
+            }
+            b"VP8 " => read_vp8_size(&mut found.info, data),
+            b"VP8L" => read_vp8l_size(&mut found.info, data),
+            b"ICCP" if found.info.icc_profile.is_none() => found.info.icc_profile = Some(data.to_vec()),
+            b"EXIF" if found.exif.is_none() => {
+                let exif = data.strip_prefix(EXIF_HEADER).unwrap_or(data);
+                found.exif = Some(exif.to_vec());
+            }
+            b"XMP " if found.xmp.is_none() => found.xmp = Some(data.to_vec()),
+            b"ANIM" if data.len() >= 6 => {
+                let animation = found.info.animation.get_or_insert_with(WebpAnimation::default);
+                // 背景色按 B、G、R、A 顺序存储
+                animation.background_color = u32::from_be_bytes([data[3], data[2], data[1], data[0]]);
+                animation.loop_count = u16::from_le_bytes([data[4], data[5]]);
+            }
+            b"ANMF" if data.len() >= 16 => {
+                found.info.animation.get_or_insert_with(WebpAnimation::default).frames.push(WebpFrame {
+                    x_offset: le24(&data[0..3]) * 2,
+                    y_offset: le24(&data[3..6]) * 2,
+                    width: le24(&data[6..9]) + 1,
+                    height: le24(&data[9..12]) + 1,
+                    duration: le24(&data[12..15]),
+                    dispose_to_background: data[15] & 0x01 != 0,
+                    blend: data[15] & 0x02 == 0,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(found)
+}
+
+/// 从 VP8（有损）帧头读取尺寸，VP8X 已给出画布尺寸时只记录编码方式
+fn read_vp8_size(info: &mut WebpInfo, data: &[u8]) {
+    if info.flags.is_none() {
+        info.encoding = "VP8".to_string();
+        // 3 字节帧标记 + 起始码 9D 01 2A + 14 位宽度 + 14 位高度
+        if data.len() >= 10 && data[3..6] == [0x9d, 0x01, 0x2a] {
+            info.width = (u16::from_le_bytes([data[6], data[7]]) & 0x3fff) as u32;
+            info.height = (u16::from_le_bytes([data[8], data[9]]) & 0x3fff) as u32;
+        }
+    }
+}
+
+/// 从 VP8L（无损）头部读取尺寸
+fn read_vp8l_size(info: &mut WebpInfo, data: &[u8]) {
+    if info.flags.is_none() {
+        info.encoding = "VP8L".to_string();
+        // 签名 0x2F + 14 位 (宽度 - 1) + 14 位 (高度 - 1)
+        if data.len() >= 5 && data[0] == 0x2f {
+            let bits = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+            info.width = (bits & 0x3fff) + 1;
+            info.height = ((bits >> 14) & 0x3fff) + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造 RIFF 数据块，奇数长度时补一个填充字节
+    fn chunk(fourcc: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut out = fourcc.to_vec();
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            out.push(0);
+        }
+        out
+    }
+
+    /// 用 RIFF 头包装数据块
+    fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = chunks.concat();
+        let mut out = b"RIFF".to_vec();
+        out.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+        out.extend_from_slice(b"WEBP");
+        out.extend_from_slice(&body);
+        out
+    }
+
+    /// VP8X 数据块：标志位 + 保留字节 + 24 位 (宽度 - 1) 与 (高度 - 1)
+    fn vp8x(flags: u8, width: u32, height: u32) -> Vec<u8> {
+        let mut data = vec![flags, 0, 0, 0];
+        data.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+        data.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+        chunk(b"VP8X", &data)
+    }
+
+    #[test]
+    fn vp8x_flags_and_metadata() {
+        let flags = VP8X_FLAG_ICC | VP8X_FLAG_EXIF | VP8X_FLAG_XMP;
+        let buf = webp(&[
+            vp8x(flags, 4000, 3000),
+            chunk(b"ICCP", b"icc"),
+            // VP8X 已给出画布尺寸，VP8 帧头不再覆盖
+            chunk(b"VP8 ", &[0, 0, 0, 0x9d, 0x01, 0x2a, 10, 0, 10, 0]),
+            chunk(b"EXIF", b"MM\0\x2a"),
+            chunk(b"XMP ", b"<x/>"),
+        ]);
+        let found = extract_metadata_from_webp_bytes(&buf).unwrap();
+        assert_eq!(found.info.encoding, "VP8X");
+        assert_eq!((found.info.width, found.info.height), (4000, 3000));
+        assert!(found.info.has_flag(VP8X_FLAG_EXIF) && found.info.has_flag(VP8X_FLAG_XMP));
+        assert!(!found.info.has_flag(VP8X_FLAG_ALPHA) && !found.info.has_flag(VP8X_FLAG_ANIMATION));
+        assert_eq!(found.info.icc_profile.as_deref(), Some(&b"icc"[..]));
+        assert_eq!(found.exif.as_deref(), Some(&b"MM\0\x2a"[..]));
+        assert_eq!(found.xmp.as_deref(), Some(&b"<x/>"[..]));
+    }
+
+    #[test]
+    fn exif_header_is_stripped() {
+        let buf = webp(&[chunk(b"EXIF", b"Exif\0\0II\x2a\0")]);
+        let found = extract_metadata_from_webp_bytes(&buf).unwrap();
+        assert_eq!(found.exif.as_deref(), Some(&b"II\x2a\0"[..]));
+        assert_eq!(found.info.flags, None);
+    }
+
+    #[test]
+    fn odd_length_chunks_are_padded() {
+        // 3 字节的 XMP 之后有填充字节，下一个数据块从偶数偏移开始
+        let buf = webp(&[chunk(b"XMP ", b"abc"), chunk(b"EXIF", b"II*")]);
+        let found = extract_metadata_from_webp_bytes(&buf).unwrap();
+        assert_eq!(found.xmp.as_deref(), Some(&b"abc"[..]));
+        assert_eq!(found.exif.as_deref(), Some(&b"II*"[..]));
+    }
+
+    #[test]
+    fn simple_formats() {
+        let buf = webp(&[chunk(b"VP8 ", &[0, 0, 0, 0x9d, 0x01, 0x2a, 0x80, 0x02, 0xe0, 0x01])]);
+        let info = extract_metadata_from_webp_bytes(&buf).unwrap().info;
+        assert_eq!((info.encoding.as_str(), info.width, info.height), ("VP8", 640, 480));
+
+        // 14 位宽度 - 1 = 99，高度 - 1 = 49
+        let bits: u32 = 99 | 49 << 14;
+        let mut data = vec![0x2f];
+        data.extend_from_slice(&bits.to_le_bytes());
+        let info = extract_metadata_from_webp_bytes(&webp(&[chunk(b"VP8L", &data)])).unwrap().info;
+        assert_eq!((info.encoding.as_str(), info.width, info.height), ("VP8L", 100, 50));
+    }
+
+    #[test]
+    fn animation_frames() {
+        // 背景色 B G R A，循环 3 次
+        let anim = chunk(b"ANIM", &[0x10, 0x20, 0x30, 0xff, 3, 0]);
+        let frame = |x: u32, duration: u32, bits: u8| {
+            let mut data = Vec::new();
+            for v in [x / 2, 0, 99, 49, duration] {
+                data.extend_from_slice(&v.to_le_bytes()[..3]);
+            }
+            data.push(bits);
+            chunk(b"ANMF", &data)
+        };
+        let buf = webp(&[vp8x(VP8X_FLAG_ANIMATION, 200, 50), anim, frame(0, 100, 0), frame(100, 250, 0x03)]);
+        let found = extract_metadata_from_webp_bytes(&buf).unwrap();
+        assert!(found.info.has_flag(VP8X_FLAG_ANIMATION));
+        let animation = found.info.animation.unwrap();
+        assert_eq!(animation.background_color, 0xff302010);
+        assert_eq!(animation.loop_count, 3);
+        assert_eq!(animation.total_duration(), 350);
+        assert_eq!(
+            animation.frames[1],
+            WebpFrame {
+                x_offset: 100,
+                y_offset: 0,
+                width: 100,
+                height: 50,
+                duration: 250,
+                dispose_to_background: true,
+                blend: false,
+            }
+        );
+        assert!(animation.frames[0].blend && !animation.frames[0].dispose_to_background);
+    }
+
+    #[test]
+    fn truncated_chunks() {
+        // 数据块长度超出 RIFF 范围
+        let mut buf = webp(&[chunk(b"EXIF", b"II*\0")]);
+        buf[16] = 100;
+        assert!(matches!(
+            extract_metadata_from_webp_bytes(&buf),
+            Err(Error::TruncatedChunk { ref chunk, offset: 12 }) if chunk == "EXIF"
+        ));
+
+        // 数据块头不完整
+        let mut buf = webp(&[chunk(b"XMP ", b"ab")]);
+        buf.extend_from_slice(b"EXI");
+        let riff_size = (buf.len() - 8) as u32;
+        buf[4..8].copy_from_slice(&riff_size.to_le_bytes());
+        assert!(matches!(
+            extract_metadata_from_webp_bytes(&buf),
+            Err(Error::TruncatedChunk { offset: 22, .. })
+        ));
+
+        // RIFF 长度之后的数据被忽略
+        let mut buf = webp(&[chunk(b"XMP ", b"ab")]);
+        buf.extend_from_slice(b"junk");
+        assert!(extract_metadata_from_webp_bytes(&buf).is_ok());
+
+        assert!(extract_metadata_from_webp_bytes(b"RIFF\0\0\0\0WEBX").is_err());
+    }
+}
@@ -0,0 +1,887 @@
+use little_exif::{
+    exif_tag::ExifTag,
+    endian::Endian,
+    rational::{iR64, uR64},
+};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    io::Write,
+    convert::TryInto,
+};
+use quick_xml::{
+    Reader,
+    events::Event,
+};
+
+use crate::{error::Error, locale::tr};
+use crate::xmp_writer::build_xmp_packet;
+
+/// 将元数据中的标签写入标准 XMP 附属文件（`<文件名>.xmp`）
+/// 
+/// # 参数
+/// - `path`: 原始图片文件路径
+/// - `out_dir`: 输出目录，为 None 时写在图片旁边
+/// - `tags`: EXIF 标签列表
+/// - `endian`: 字节序信息
+/// 
+/// # 返回值
+/// - 成功时返回写入的文件路径
+pub fn write_xmp_from_metadata(path: &Path, out_dir: Option<&Path>, tags: &[ExifTag], endian: &Endian) -> std::io::Result<PathBuf> {
+    // 与 Lightroom / exiftool 一致，附属文件与图片同名、扩展名为 .xmp
+    let xmp_path = match out_dir {
+        Some(dir) => dir.join(path.with_extension("xmp").file_name().unwrap_or_default()),
+        None => path.with_extension("xmp"),
+    };
+    let mut f = File::create(&xmp_path)?;
+    f.write_all(build_xmp_packet(tags, endian).as_bytes())?;
+    Ok(xmp_path)
+}
+
+/// RDF 语法命名空间
+pub const NS_RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+/// XML 保留命名空间（xml:lang 等），不需要声明
+pub const NS_XML: &str = "http://www.w3.org/XML/1998/namespace";
+/// TIFF 属性命名空间
+pub const NS_TIFF: &str = "http://ns.adobe.com/tiff/1.0/";
+/// EXIF 属性命名空间
+pub const NS_EXIF: &str = "http://ns.adobe.com/exif/1.0/";
+/// EXIF 2.3 新增属性命名空间（CIPA DC-010）
+pub const NS_EXIF_EX: &str = "http://cipa.jp/exif/1.0/";
+/// EXIF 辅助属性命名空间（镜头、序列号等）
+pub const NS_AUX: &str = "http://ns.adobe.com/exif/1.0/aux/";
+/// XMP 基本属性命名空间
+pub const NS_XMP: &str = "http://ns.adobe.com/xap/1.0/";
+/// Dublin Core 命名空间
+pub const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+
+/// XMP 属性（带命名空间前缀的限定名、命名空间 URI 及其值）
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmpProperty {
+    /// 文件中出现的限定名（如 "tiff:Make"），前缀只用于显示
+    pub name: String,
+    /// 由 xmlns 声明解析出的命名空间 URI，前缀未声明时为空
+    pub namespace: String,
+    pub value: XmpValue,
+}
+
+impl XmpProperty {
+    /// 属性的本地名（不带前缀）
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    /// 判断属性是否为指定命名空间中的指定本地名
+    pub fn is(&self, namespace: &str, local: &str) -> bool {
+        self.namespace == namespace && self.local_name() == local
+    }
+}
+
+/// XMP 属性值（RDF 数据模型）
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmpValue {
+    /// 简单值（文本或 rdf:resource 指向的 URI）
+    Simple(String),
+    /// 有序数组 rdf:Seq
+    Seq(Vec<XmpValue>),
+    /// 无序数组 rdf:Bag
+    Bag(Vec<XmpValue>),
+    /// 备选数组 rdf:Alt，每一项可带 xml:lang 语言标记
+    Alt(Vec<(Option<String>, XmpValue)>),
+    /// 结构体（rdf:parseType="Resource"、嵌套 rdf:Description 或属性简写形式）
+    Struct(Vec<XmpProperty>),
+}
+
+impl XmpValue {
+    /// 取出代表性的文本值
+    ///
+    /// ### 返回值
+    /// - 简单值本身、数组的第一项、Alt 中 x-default 项（没有时取第一项）；结构体返回 None
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            XmpValue::Simple(s) => Some(s),
+            XmpValue::Seq(items) | XmpValue::Bag(items) => items.first()?.as_text(),
+            XmpValue::Alt(items) => items
+                .iter()
+                .find(|(lang, _)| lang.as_deref() == Some("x-default"))
+                .or_else(|| items.first())
+                .and_then(|(_, v)| v.as_text()),
+            XmpValue::Struct(_) => None,
+        }
+    }
+
+    /// 按命名空间 URI 和本地名查找结构体字段
+    pub fn field(&self, namespace: &str, local: &str) -> Option<&XmpValue> {
+        match self {
+            XmpValue::Struct(fields) => fields.iter().find(|f| f.is(namespace, local)).map(|f| &f.value),
+            _ => None,
+        }
+    }
+}
+
+/// 解析 XMP 数据包并映射为 EXIF 标签列表
+///
+/// ### 参数
+/// - `xmp`: XMP 数据包（XML 文本）
+///
+/// ### 返回值
+/// - 成功时返回 EXIF 标签列表，XML 损坏时返回 Error
+pub fn parse_xmp_to_exif_tags(xmp: &str) -> Result<Vec<ExifTag>, Error> {
+    Ok(xmp_properties_to_exif_tags(&parse_xmp_properties(xmp)?))
+}
+
+/// 将 XMP 属性列表映射为 EXIF 标签列表
+pub fn xmp_properties_to_exif_tags(properties: &[XmpProperty]) -> Vec<ExifTag> {
+    let mut out: Vec<ExifTag> = Vec::new(); // 专门用于存储 ExifTag 类型的元素
+    for prop in properties {
+        match &prop.value {
+            // exif:Flash 是结构体，需要先还原为 EXIF 的位域值
+            value @ XmpValue::Struct(_) if prop.is(NS_EXIF, "Flash") => {
+                if let Some(n) = flash_struct_to_value(value) {
+                    out.push(ExifTag::Flash(vec![n]));
+                }
+            }
+            value => {
+                if let Some(text) = value.as_text() {
+                    map_xmp_key_value_into_tag(&prop.namespace, prop.local_name(), text, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 将 exif:Flash 结构体还原为 EXIF Flash 位域
+fn flash_struct_to_value(value: &XmpValue) -> Option<u16> {
+    let field = |f: &str| value.field(NS_EXIF, f).and_then(|v| v.as_text()).map(str::trim);
+    let flag = |f: &str| field(f).map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false) as u16;
+    let num = |f: &str| field(f).and_then(|v| v.parse::<u16>().ok()).unwrap_or(0);
+    // 结构体中一个字段都没有时视为无效
+    field("Fired")?;
+    Some(flag("Fired") | (num("Return") & 0x03) << 1 | (num("Mode") & 0x03) << 3 | flag("Function") << 5 | flag("RedEyeMode") << 6)
+}
+
+/// XML 属性
+#[derive(Debug, Default)]
+struct XmlAttr {
+    /// 限定名（如 "rdf:about"）
+    name: String,
+    /// 命名空间 URI，无前缀的属性为空
+    namespace: String,
+    /// 已反转义的值
+    value: String,
+}
+
+/// 解析 XML 得到的元素节点
+#[derive(Debug, Default)]
+struct XmlElement {
+    /// 限定名（如 "exif:ISOSpeedRatings"）
+    name: String,
+    /// 命名空间 URI
+    namespace: String,
+    attrs: Vec<XmlAttr>,
+    children: Vec<XmlElement>,
+    /// 元素直接包含的文本（含 CDATA）
+    text: String,
+}
+
+impl XmlElement {
+    /// 判断元素是否为指定的 RDF 语法元素
+    fn is_rdf(&self, local: &str) -> bool {
+        self.namespace == NS_RDF && local_name(&self.name) == local
+    }
+
+    /// 按命名空间 URI 和本地名读取属性值
+    fn attr(&self, namespace: &str, local: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|a| a.namespace == namespace && local_name(&a.name) == local)
+            .map(|a| a.value.as_str())
+    }
+
+    /// 查找第一个指定的 RDF 子元素
+    fn rdf_child(&self, local: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.is_rdf(local))
+    }
+
+    /// 深度优先查找第一个指定的 RDF 元素（包括自身）
+    fn find_rdf(&self, local: &str) -> Option<&XmlElement> {
+        if self.is_rdf(local) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|c| c.find_rdf(local))
+    }
+}
+
+/// 在命名空间作用域栈中解析前缀（从内到外查找），空前缀对应默认命名空间
+fn resolve_prefix(scopes: &[Vec<(String, String)>], prefix: &str) -> String {
+    if prefix == "xml" {
+        return NS_XML.to_string();
+    }
+    scopes
+        .iter()
+        .rev()
+        .flat_map(|scope| scope.iter())
+        .find(|(p, _)| p == prefix)
+        .map(|(_, uri)| uri.clone())
+        .unwrap_or_default()
+}
+
+/// 限定名中的前缀部分，没有前缀时为空
+fn prefix_of(name: &str) -> &str {
+    name.split_once(':').map(|(p, _)| p).unwrap_or("")
+}
+
+/// 将 XML 文本解析为元素树，返回一个无名的根节点
+///
+/// 元素和属性的前缀按 xmlns 声明解析为命名空间 URI
+///
+/// ### 返回值
+/// - XML 结构损坏（标签不匹配、属性错误、元素未闭合）时返回 Error::MalformedXmp
+fn parse_xml_tree(xml: &str) -> Result<XmlElement, Error> {
+    let mut reader = Reader::from_str(xml); // 从 xmp 创建一个 Reader 实例
+    reader.trim_text(true); // 表示启用文本修剪功能，去除文本内容两端的空白字符
+
+    let mut buf = Vec::new();
+    // 栈底是根节点，栈顶是当前正在解析的元素
+    let mut stack: Vec<XmlElement> = vec![XmlElement::default()];
+    // 与 stack 对应的命名空间声明作用域（前缀, URI）
+    let mut scopes: Vec<Vec<(String, String)>> = vec![Vec::new()];
+
+    // 读取失败说明 XML 结构损坏，附带出错位置返回 Error::MalformedXmp
+    loop {
+        let ev = reader.read_event_into(&mut buf).map_err(|e| {
+            Error::MalformedXmp(format!("{} ({} {})", e, tr("position", "位置", "位置"), reader.buffer_position()))
+        })?;
+        match ev {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let mut raw_attrs: Vec<(String, String)> = Vec::new();
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|err| {
+                        Error::MalformedXmp(format!("{} ({} {})", err, tr("position", "位置", "位置"), reader.buffer_position()))
+                    })?;
+                    let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                    let val = attr.unescape_value().map_err(|err| {
+                        Error::MalformedXmp(format!("{} ({} {})", err, tr("position", "位置", "位置"), reader.buffer_position()))
+                    })?;
+                    raw_attrs.push((key, val.to_string()));
+                }
+
+                // 先收集本元素上的命名空间声明，它们对元素自身和属性同样生效
+                let decls: Vec<(String, String)> = raw_attrs
+                    .iter()
+                    .filter_map(|(k, v)| match k.strip_prefix("xmlns") {
+                        Some("") => Some((String::new(), v.clone())),
+                        Some(rest) => rest.strip_prefix(':').map(|p| (p.to_string(), v.clone())),
+                        None => None,
+                    })
+                    .collect();
+                scopes.push(decls);
+
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                let mut el = XmlElement {
+                    namespace: resolve_prefix(&scopes, prefix_of(&name)),
+                    name,
+                    ..Default::default()
+                };
+                for (key, value) in raw_attrs {
+                    // 无前缀的属性不属于任何命名空间
+                    let namespace = match prefix_of(&key) {
+                        "" => String::new(),
+                        p => resolve_prefix(&scopes, p),
+                    };
+                    el.attrs.push(XmlAttr { name: key, namespace, value });
+                }
+
+                if matches!(ev, Event::Start(_)) {
+                    stack.push(el);
+                } else {
+                    scopes.pop();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(el);
+                    }
+                }
+            }
+            // quick-xml 已经检查了结束标签与开始标签是否匹配
+            Event::End(_) if stack.len() > 1 => {
+                scopes.pop();
+                if let Some(el) = stack.pop() {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(el);
+                    }
+                }
+            }
+            Event::Text(e) => {
+                let text = e.unescape().map_err(|err| {
+                    Error::MalformedXmp(format!("{} ({} {})", err, tr("position", "位置", "位置"), reader.buffer_position()))
+                })?;
+                if let Some(el) = stack.last_mut() {
+                    el.text.push_str(&text);
+                }
+            }
+            Event::CData(e) => {
+                if let Some(el) = stack.last_mut() {
+                    el.text.push_str(&String::from_utf8_lossy(&e.into_inner()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if stack.len() != 1 {
+        let name = stack.last().map(|e| e.name.clone()).unwrap_or_default();
+        return Err(Error::MalformedXmp(format!("{} <{}>", tr("unclosed element", "元素未闭合", "閉じられていない要素"), name)));
+    }
+    Ok(stack.pop().unwrap_or_default())
+}
+
+/// 解析 XMP 数据包，得到完整的属性树
+///
+/// 支持 rdf:Description 上的属性简写、元素形式的属性、rdf:Seq / rdf:Bag / rdf:Alt（含 xml:lang）、
+/// rdf:parseType="Resource" 结构体、嵌套 rdf:Description 以及 rdf:value 限定符形式。
+/// RDF 语法按命名空间 URI 识别，与文件中使用的前缀无关
+///
+/// ### 参数
+/// - `xmp`: XMP 数据包（XML 文本），直接在内存中解析
+///
+/// ### 返回值
+/// - 成功时返回顶层 XMP 属性列表，XML 损坏返回 Error::MalformedXmp
+pub fn parse_xmp_properties(xmp: &str) -> Result<Vec<XmpProperty>, Error> {
+    let root = parse_xml_tree(xmp)?;
+    let mut out: Vec<XmpProperty> = Vec::new();
+    if let Some(rdf) = root.find_rdf("RDF") {
+        for desc in rdf.children.iter().filter(|c| c.is_rdf("Description")) {
+            out.extend(node_properties(desc));
+        }
+    }
+    Ok(out)
+}
+
+/// 判断属性是否为 XMP 属性（而不是命名空间声明或 RDF / XML 语法属性）
+fn is_property_attr(attr: &XmlAttr) -> bool {
+    let is_decl = attr.name == "xmlns" || attr.name.starts_with("xmlns:");
+    !(is_decl || attr.namespace == NS_RDF || attr.namespace == NS_XML)
+}
+
+/// 读取一个节点（rdf:Description 或结构体元素）上的全部属性：属性简写和子元素
+fn node_properties(node: &XmlElement) -> Vec<XmpProperty> {
+    let mut out: Vec<XmpProperty> = node
+        .attrs
+        .iter()
+        .filter(|a| is_property_attr(a))
+        .map(|a| XmpProperty {
+            name: a.name.clone(),
+            namespace: a.namespace.clone(),
+            value: XmpValue::Simple(a.value.clone()),
+        })
+        .collect();
+    for child in &node.children {
+        out.push(XmpProperty {
+            name: child.name.clone(),
+            namespace: child.namespace.clone(),
+            value: element_value(child),
+        });
+    }
+    out
+}
+
+/// 解析元素形式的属性值
+fn element_value(el: &XmlElement) -> XmpValue {
+    // <ns:prop rdf:resource="uri"/>
+    if let Some(uri) = el.attr(NS_RDF, "resource") {
+        return XmpValue::Simple(uri.to_string());
+    }
+    // <ns:prop rdf:parseType="Resource">...</ns:prop>
+    if el.attr(NS_RDF, "parseType") == Some("Resource") {
+        return XmpValue::Struct(node_properties(el));
+    }
+    // 带限定符的值：<ns:prop><rdf:value>v</rdf:value><q:qual>..</q:qual></ns:prop>
+    if let Some(value) = el.rdf_child("value") {
+        return element_value(value);
+    }
+    // 数组容器
+    for container in &el.children {
+        let items = container.children.iter().filter(|c| c.is_rdf("li"));
+        if container.is_rdf("Seq") {
+            return XmpValue::Seq(items.map(element_value).collect());
+        } else if container.is_rdf("Bag") {
+            return XmpValue::Bag(items.map(element_value).collect());
+        } else if container.is_rdf("Alt") {
+            return XmpValue::Alt(
+                items.map(|li| (li.attr(NS_XML, "lang").map(str::to_string), element_value(li))).collect(),
+            );
+        }
+    }
+    // 嵌套 rdf:Description 表示的结构体
+    if let Some(desc) = el.rdf_child("Description") {
+        return XmpValue::Struct(node_properties(desc));
+    }
+    // 子元素或属性简写形式的结构体
+    if !el.children.is_empty() || el.attrs.iter().any(is_property_attr) {
+        return XmpValue::Struct(node_properties(el));
+    }
+    XmpValue::Simple(el.text.clone())
+}
+
+/// 取限定名中的本地名部分
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// 将 XMP 属性映射为 EXIF 标签
+///
+/// 按（命名空间 URI, 本地名）匹配，前缀不参与判断：
+/// `crs:WhiteBalance` 之类的 Camera Raw 设置不会被当成拍摄时的 EXIF，
+/// 使用非常规前缀的文件也能正确映射
+///
+/// ### 参数
+/// - `namespace`: 属性的命名空间 URI
+/// - `key`: 属性本地名
+/// - `val`: XMP 值
+/// - `out`: 输出的 EXIF 标签列表
+pub fn map_xmp_key_value_into_tag(namespace: &str, key: &str, val: &str, out: &mut Vec<ExifTag>) {
+    let v = val.trim();
+    if v.is_empty() { return; }
+
+    // XMP 日期是 ISO 8601，转换为 EXIF 的 "YYYY:MM:DD HH:MM:SS"，亚秒和时区放入对应的标签
+    type DateTags = (fn(String) -> ExifTag, fn(String) -> ExifTag, fn(String) -> ExifTag);
+    let date_tags: Option<DateTags> = match (namespace, key) {
+        (NS_TIFF, "DateTime") | (NS_XMP, "ModifyDate") => {
+            Some((ExifTag::ModifyDate, ExifTag::SubSecTime, ExifTag::OffsetTime))
+        }
+        (NS_EXIF, "DateTimeOriginal") => {
+            Some((ExifTag::DateTimeOriginal, ExifTag::SubSecTimeOriginal, ExifTag::OffsetTimeOriginal))
+        }
+        (NS_EXIF, "DateTimeDigitized") | (NS_XMP, "CreateDate") => {
+            Some((ExifTag::CreateDate, ExifTag::SubSecTimeDigitized, ExifTag::OffsetTimeDigitized))
+        }
+        _ => None,
+    };
+    if let Some((date_tag, subsec_tag, offset_tag)) = date_tags {
+        match iso8601_to_exif_date(v) {
+            Some((date, subsec, offset)) => {
+                // 日期已由其他属性提供时，亚秒和时区也不再覆盖
+                if push_unique(out, date_tag(date)) {
+                    for tag in [subsec.map(subsec_tag), offset.map(offset_tag)].into_iter().flatten() {
+                        push_unique(out, tag);
+                    }
+                }
+            }
+            // 无法识别的日期原样保留
+            None => {
+                push_unique(out, date_tag(v.to_string()));
+            }
+        }
+        return;
+    }
+
+    let parse_u16 = || v.parse::<u16>().ok();
+    let rational = || parse_fraction_to_rational(v);
+    let signed_rational = || parse_fraction_to_signed_rational(v);
+
+    // 根据（命名空间, 本地名）映射到对应的 EXIF 标签类型
+    let tag = match (namespace, key) {
+        // TIFF
+        (NS_TIFF, "Make") => Some(ExifTag::Make(v.to_string())),
+        (NS_TIFF, "Model") => Some(ExifTag::Model(v.to_string())),
+        (NS_TIFF, "Software") | (NS_XMP, "CreatorTool") => Some(ExifTag::Software(v.to_string())),
+        (NS_TIFF, "Artist") | (NS_DC, "creator") => Some(ExifTag::Artist(v.to_string())),
+        (NS_TIFF, "Copyright") | (NS_DC, "rights") => Some(ExifTag::Copyright(v.to_string())),
+        (NS_TIFF, "ImageDescription") | (NS_DC, "description") => Some(ExifTag::ImageDescription(v.to_string())),
+        (NS_TIFF, "XResolution") => rational().map(|r| ExifTag::XResolution(vec![r])), // 分辨率相关
+        (NS_TIFF, "YResolution") => rational().map(|r| ExifTag::YResolution(vec![r])),
+        (NS_TIFF, "ResolutionUnit") => parse_u16().map(|n| ExifTag::ResolutionUnit(vec![n])), // 分辨率单位
+        (NS_TIFF, "Orientation") => parse_u16().map(|n| ExifTag::Orientation(vec![n])), // 方向
+        // ISO 相关
+        (NS_EXIF, "ISOSpeedRatings") | (NS_EXIF_EX, "PhotographicSensitivity") => v
+            .parse::<u16>()
+            .ok()
+            .or_else(|| extract_first_number(v).map(|n| n as u16))
+            .map(|n| ExifTag::ISO(vec![n])),
+        (NS_EXIF, "SensitivityType") | (NS_EXIF_EX, "SensitivityType") => {
+            parse_u16().map(|n| ExifTag::SensitivityType(vec![n]))
+        }
+        (NS_EXIF, "RecommendedExposureIndex") | (NS_EXIF_EX, "RecommendedExposureIndex") => {
+            v.parse::<u32>().ok().map(|n| ExifTag::RecommendedExposureIndex(vec![n]))
+        }
+        // 曝光相关
+        (NS_EXIF, "FNumber") => rational().map(|r| ExifTag::FNumber(vec![r])),
+        (NS_EXIF, "ApertureValue") => rational().map(|r| ExifTag::ApertureValue(vec![r])),
+        (NS_EXIF, "MaxApertureValue") => rational().map(|r| ExifTag::MaxApertureValue(vec![r])),
+        (NS_EXIF, "ExposureTime") => rational().map(|r| ExifTag::ExposureTime(vec![r])),
+        (NS_EXIF, "ShutterSpeedValue") => signed_rational().map(|r| ExifTag::ShutterSpeedValue(vec![r])),
+        (NS_EXIF, "ExposureBiasValue") => signed_rational().map(|r| ExifTag::ExposureCompensation(vec![r])),
+        (NS_EXIF, "ExposureProgram") => parse_u16().map(|n| ExifTag::ExposureProgram(vec![n])),
+        (NS_EXIF, "ExposureMode") => parse_u16().map(|n| ExifTag::ExposureMode(vec![n])),
+        (NS_EXIF, "MeteringMode") => parse_u16().map(|n| ExifTag::MeteringMode(vec![n])),
+        (NS_EXIF, "LightSource") => parse_u16().map(|n| ExifTag::LightSource(vec![n])),
+        (NS_EXIF, "Flash") => parse_u16().map(|n| ExifTag::Flash(vec![n])),
+        (NS_EXIF, "WhiteBalance") => parse_u16().map(|n| ExifTag::WhiteBalance(vec![n])),
+        // 焦距相关
+        (NS_EXIF, "FocalLength") => rational().map(|r| ExifTag::FocalLength(vec![r])),
+        (NS_EXIF, "FocalLengthIn35mmFilm") => parse_u16().map(|n| ExifTag::FocalLengthIn35mmFormat(vec![n])),
+        // 场景与画面
+        (NS_EXIF, "SceneCaptureType") => parse_u16().map(|n| ExifTag::SceneCaptureType(vec![n])),
+        (NS_EXIF, "CustomRendered") => parse_u16().map(|n| ExifTag::CustomRendered(vec![n])),
+        (NS_EXIF, "SensingMethod") => parse_u16().map(|n| ExifTag::SensingMethod(vec![n])),
+        (NS_EXIF, "ColorSpace") => parse_u16().map(|n| ExifTag::ColorSpace(vec![n])),
+        (NS_EXIF, "Contrast") => parse_u16().map(|n| ExifTag::Contrast(vec![n])),
+        (NS_EXIF, "Saturation") => parse_u16().map(|n| ExifTag::Saturation(vec![n])),
+        (NS_EXIF, "Sharpness") => parse_u16().map(|n| ExifTag::Sharpness(vec![n])),
+        (NS_EXIF, "PixelXDimension") => v.parse::<u32>().ok().map(|n| ExifTag::ExifImageWidth(vec![n])),
+        (NS_EXIF, "PixelYDimension") => v.parse::<u32>().ok().map(|n| ExifTag::ExifImageHeight(vec![n])),
+        (NS_EXIF, "ImageUniqueID") => Some(ExifTag::ImageUniqueID(v.to_string())),
+        // 机身与镜头
+        (NS_AUX, "SerialNumber") | (NS_EXIF_EX, "BodySerialNumber") => Some(ExifTag::SerialNumber(v.to_string())),
+        (NS_EXIF_EX, "CameraOwnerName") => Some(ExifTag::OwnerName(v.to_string())),
+        (NS_EXIF_EX, "LensMake") => Some(ExifTag::LensMake(v.to_string())),
+        (NS_AUX, "Lens") | (NS_EXIF_EX, "LensModel") => Some(ExifTag::LensModel(v.to_string())),
+        (NS_AUX, "LensSerialNumber") | (NS_EXIF_EX, "LensSerialNumber") => {
+            Some(ExifTag::LensSerialNumber(v.to_string()))
+        }
+        (NS_AUX, "LensInfo") => {
+            // 解析镜头信息，格式如 "700/10 2100/10 40/10 56/10"
+            let rationals: Vec<uR64> = v.split_whitespace().filter_map(parse_fraction_to_rational).collect();
+            if rationals.is_empty() { None } else { Some(ExifTag::LensInfo(rationals)) }
+        }
+        // 其他未映射的字段保留在 XmpProperty 列表中，不转换为 EXIF 标签
+        _ => None,
+    };
+
+    if let Some(tag) = tag {
+        push_unique(out, tag);
+    }
+}
+
+/// 添加 EXIF 标签，已存在同一标签时忽略
+///
+/// 同一个 EXIF 标签可能由多个 XMP 属性映射而来（如 tiff:Software 与 xmp:CreatorTool），只保留第一个
+///
+/// # 返回值
+/// - 是否添加了该标签
+fn push_unique(out: &mut Vec<ExifTag>, tag: ExifTag) -> bool {
+    if out.iter().any(|t| t.as_u16() == tag.as_u16() && t.get_group() == tag.get_group()) {
+        return false;
+    }
+    out.push(tag);
+    true
+}
+
+/// 将 XMP 日期（ISO 8601）拆分为 EXIF 日期时间、亚秒和时区偏移
+///
+/// 支持 "YYYY-MM-DD"、"YYYY-MM-DDThh:mm"、"YYYY-MM-DDThh:mm:ss" 和 "YYYY-MM-DDThh:mm:ss.s"，
+/// 可带 "Z" 或 "+hh:mm" 时区；缺少的时间部分补 0
+///
+/// # 参数
+/// - `s`: XMP 日期
+///
+/// # 返回值
+/// - ("YYYY:MM:DD HH:MM:SS", 亚秒, 时区)，不是完整日期时返回 None
+fn iso8601_to_exif_date(s: &str) -> Option<(String, Option<String>, Option<String>)> {
+    let digits = |t: &str, len: usize| t.len() == len && t.bytes().all(|b| b.is_ascii_digit());
+    let (date, rest) = (s.get(..10)?, &s[10..]);
+    let ymd: Vec<&str> = date.split('-').collect();
+    if ymd.len() != 3 || !digits(ymd[0], 4) || !digits(ymd[1], 2) || !digits(ymd[2], 2) {
+        return None;
+    }
+    if rest.is_empty() {
+        return Some((format!("{}:{}:{} 00:00:00", ymd[0], ymd[1], ymd[2]), None, None));
+    }
+
+    let rest = rest.strip_prefix('T')?;
+    // 时区：末尾的 "Z" 或 "+hh:mm" / "-hh:mm"
+    let (time, offset) = match rest.find(['Z', '+', '-']) {
+        Some(i) => (&rest[..i], Some(&rest[i..])),
+        None => (rest, None),
+    };
+    let offset = match offset {
+        None => None,
+        Some("Z") => Some("+00:00".to_string()),
+        Some(o) if o.len() == 6 && digits(&o[1..3], 2) && &o[3..4] == ":" && digits(&o[4..], 2) => Some(o.to_string()),
+        Some(_) => return None,
+    };
+    let (hms, subsec) = match time.split_once('.') {
+        Some((hms, sub)) if !sub.is_empty() && sub.bytes().all(|b| b.is_ascii_digit()) => (hms, Some(sub.to_string())),
+        Some(_) => return None,
+        None => (time, None),
+    };
+    let parts: Vec<&str> = hms.split(':').collect();
+    if !(2..=3).contains(&parts.len()) || !parts.iter().all(|p| digits(p, 2)) {
+        return None;
+    }
+    let seconds = parts.get(2).copied().unwrap_or("00");
+    let exif = format!("{}:{}:{} {}:{}:{}", ymd[0], ymd[1], ymd[2], parts[0], parts[1], seconds);
+    Some((exif, subsec, offset))
+}
+
+/// 从字符串中提取第一个数字
+/// 
+/// # 参数
+/// - `s`: 输入字符串
+/// 
+/// # 返回值
+/// - 提取的数字，如果提取失败则返回 None
+fn extract_first_number(s: &str) -> Option<f64> {
+    let mut num = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() || c == '.' || c == '-' {
+            num.push(c);
+        } else if !num.is_empty() {
+            break;
+        }
+    }
+    num.parse::<f64>().ok()
+}
+
+/// 将分数字符串解析为有理数
+/// ### 参数
+/// - `s`: 分数字符串（如 "1/125"）
+/// ### 返回值
+/// - 解析后的有理数，如果解析失败则返回 None
+pub fn parse_fraction_to_rational(s: &str) -> Option<uR64> {
+    let s = s.trim();
+
+    // 处理整数格式
+    if let Ok(n) = s.parse::<u32>() {
+        return Some(uR64 { nominator: n, denominator: 1 });
+    }
+    
+    // 处理分数格式（如 "1/125"）
+    if s.contains('/') { // 检查字符串中是否包含斜杠
+        let parts: Vec<&str> = s.split('/').collect(); // 按斜杠分割字符串，将分割结果收集到 Vec 中
+        if parts.len() >= 2 {
+            if let (Ok(a), Ok(b)) = (parts[0].trim().parse::<u32>(), parts[1].trim().parse::<u32>()) {
+                return Some(uR64 { nominator: a, denominator: b });
+            }
+        }
+    }
+    
+    // 处理小数格式
+    if let Some(n) = extract_first_number(s) {
+        // 使用 1000 作为分母构造近似有理数
+        let denom: u32 = 1000;
+        let nom_u64 = (n * denom as f64).round() as u64;
+        let nom: u32 = nom_u64.try_into().unwrap_or(u32::MAX);
+        return Some(uR64 { nominator: nom, denominator: denom });
+    }
+    
+    None
+}
+
+/// 将分数字符串解析为有符号有理数（如 APEX 快门速度 "-2321928/1000000"）
+/// ### 参数
+/// - `s`: 分数或小数字符串
+/// ### 返回值
+/// - 解析后的有理数，如果解析失败则返回 None
+pub fn parse_fraction_to_signed_rational(s: &str) -> Option<iR64> {
+    let s = s.trim();
+    if let Some((a, b)) = s.split_once('/') {
+        if let (Ok(a), Ok(b)) = (a.trim().parse::<i32>(), b.trim().parse::<i32>()) {
+            return Some(iR64 { nominator: a, denominator: b });
+        }
+    }
+    if let Ok(n) = s.parse::<i32>() {
+        return Some(iR64 { nominator: n, denominator: 1 });
+    }
+    // 小数使用 1000 作为分母构造近似有理数
+    let n = s.parse::<f64>().ok()?;
+    Some(iR64 { nominator: (n * 1000.0).round() as i32, denominator: 1000 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 用 rdf:RDF / rdf:Description 包装属性，生成完整的 XMP 数据包
+    fn packet(body: &str) -> String {
+        format!(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="{}"><rdf:Description rdf:about="" xmlns:exif="{}" xmlns:dc="{}" xmlns:tiff="{}" {}</rdf:Description></rdf:RDF></x:xmpmeta>"#,
+            NS_RDF, NS_EXIF, NS_DC, NS_TIFF, body
+        )
+    }
+
+    fn find<'a>(props: &'a [XmpProperty], namespace: &str, local: &str) -> &'a XmpValue {
+        &props.iter().find(|p| p.is(namespace, local)).unwrap_or_else(|| panic!("缺少 {}", local)).value
+    }
+
+    fn simple(s: &str) -> XmpValue {
+        XmpValue::Simple(s.to_string())
+    }
+
+    #[test]
+    fn attribute_form_properties() {
+        let props = parse_xmp_properties(&packet(r#"tiff:Make="Canon" exif:FNumber="28/10">"#)).unwrap();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].name, "tiff:Make");
+        assert_eq!(props[0].namespace, NS_TIFF);
+        assert_eq!(props[0].value, simple("Canon"));
+        assert_eq!(find(&props, NS_EXIF, "FNumber"), &simple("28/10"));
+        // rdf:about 是语法属性，不算 XMP 属性
+        assert!(props.iter().all(|p| p.namespace != NS_RDF));
+    }
+
+    #[test]
+    fn arrays() {
+        let props = parse_xmp_properties(&packet(concat!(
+            r#">"#,
+            r#"<exif:ISOSpeedRatings><rdf:Seq><rdf:li>100</rdf:li><rdf:li>200</rdf:li></rdf:Seq></exif:ISOSpeedRatings>"#,
+            r#"<dc:subject><rdf:Bag><rdf:li>a</rdf:li><rdf:li>b</rdf:li></rdf:Bag></dc:subject>"#,
+            r#"<dc:title><rdf:Alt><rdf:li xml:lang="en">Title</rdf:li><rdf:li xml:lang="x-default">Default</rdf:li></rdf:Alt></dc:title>"#,
+        )))
+        .unwrap();
+        assert_eq!(find(&props, NS_EXIF, "ISOSpeedRatings"), &XmpValue::Seq(vec![simple("100"), simple("200")]));
+        assert_eq!(find(&props, NS_DC, "subject"), &XmpValue::Bag(vec![simple("a"), simple("b")]));
+        let title = find(&props, NS_DC, "title");
+        assert_eq!(
+            title,
+            &XmpValue::Alt(vec![
+                (Some("en".to_string()), simple("Title")),
+                (Some("x-default".to_string()), simple("Default")),
+            ])
+        );
+        // Alt 取 x-default，Seq 取第一项
+        assert_eq!(title.as_text(), Some("Default"));
+        assert_eq!(find(&props, NS_EXIF, "ISOSpeedRatings").as_text(), Some("100"));
+    }
+
+    #[test]
+    fn struct_forms() {
+        let props = parse_xmp_properties(&packet(concat!(
+            r#">"#,
+            r#"<exif:Flash rdf:parseType="Resource"><exif:Fired>True</exif:Fired><exif:Mode>1</exif:Mode></exif:Flash>"#,
+            r#"<exif:OECF><rdf:Description exif:Columns="2"><exif:Rows>3</exif:Rows></rdf:Description></exif:OECF>"#,
+            r#"<exif:DeviceSettingDescription exif:Columns="4"/>"#,
+        )))
+        .unwrap();
+
+        let flash = find(&props, NS_EXIF, "Flash");
+        assert_eq!(flash.field(NS_EXIF, "Fired"), Some(&simple("True")));
+        assert_eq!(flash.field(NS_EXIF, "Mode"), Some(&simple("1")));
+        assert_eq!(flash.as_text(), None);
+
+        // 嵌套 rdf:Description：属性简写和子元素都是字段
+        let oecf = find(&props, NS_EXIF, "OECF");
+        assert_eq!(oecf.field(NS_EXIF, "Columns"), Some(&simple("2")));
+        assert_eq!(oecf.field(NS_EXIF, "Rows"), Some(&simple("3")));
+
+        // 空元素上的属性简写
+        let dsd = find(&props, NS_EXIF, "DeviceSettingDescription");
+        assert_eq!(dsd.field(NS_EXIF, "Columns"), Some(&simple("4")));
+    }
+
+    #[test]
+    fn element_value_forms() {
+        let root = parse_xml_tree(&format!(
+            concat!(
+                r#"<r xmlns:rdf="{}" xmlns:exif="{}" xmlns:q="urn:q">"#,
+                r#"<exif:A><rdf:value>v</rdf:value><q:qual>ignored</q:qual></exif:A>"#,
+                r#"<exif:B rdf:resource="http://example.com/"/>"#,
+                r#"<exif:C>plain &amp; text</exif:C>"#,
+                r#"<exif:D/>"#,
+                r#"</r>"#
+            ),
+            NS_RDF, NS_EXIF
+        ))
+        .unwrap();
+        let values: Vec<XmpValue> = root.children[0].children.iter().map(element_value).collect();
+        assert_eq!(
+            values,
+            vec![simple("v"), simple("http://example.com/"), simple("plain & text"), simple("")]
+        );
+    }
+
+    #[test]
+    fn rdf_syntax_is_matched_by_uri() {
+        // 非常规前缀：RDF 语法和属性都按命名空间 URI 识别
+        let xmp = format!(
+            r#"<r:RDF xmlns:r="{}"><r:Description xmlns:e="{}"><e:ISOSpeedRatings><r:Seq><r:li>400</r:li></r:Seq></e:ISOSpeedRatings></r:Description></r:RDF>"#,
+            NS_RDF, NS_EXIF
+        );
+        let props = parse_xmp_properties(&xmp).unwrap();
+        assert_eq!(props[0].name, "e:ISOSpeedRatings");
+        assert_eq!(find(&props, NS_EXIF, "ISOSpeedRatings"), &XmpValue::Seq(vec![simple("400")]));
+    }
+
+    fn tags(body: &str) -> Vec<ExifTag> {
+        parse_xmp_to_exif_tags(&packet(body)).unwrap()
+    }
+
+    #[test]
+    fn namespace_mapping_uses_uris() {
+        // 非常规前缀绑定到标准命名空间时照常映射
+        let xmp = format!(
+            r#"<rdf:RDF xmlns:rdf="{}"><rdf:Description xmlns:e="{}" xmlns:t="{}" e:FNumber="28/10" t:Make="Nikon"/></rdf:RDF>"#,
+            NS_RDF, NS_EXIF, NS_TIFF
+        );
+        let out = parse_xmp_to_exif_tags(&xmp).unwrap();
+        assert!(out.contains(&ExifTag::FNumber(vec![uR64 { nominator: 28, denominator: 10 }])));
+        assert!(out.contains(&ExifTag::Make("Nikon".to_string())));
+
+        // 常规前缀绑定到其他命名空间时不映射
+        let xmp = format!(
+            r#"<rdf:RDF xmlns:rdf="{}"><rdf:Description xmlns:exif="urn:other" exif:FNumber="28/10"/></rdf:RDF>"#,
+            NS_RDF
+        );
+        assert!(parse_xmp_to_exif_tags(&xmp).unwrap().is_empty());
+
+        // Camera Raw 的白平衡设置不是拍摄时的 EXIF WhiteBalance
+        let crs = r#"xmlns:crs="http://ns.adobe.com/camera-raw-settings/1.0/" crs:WhiteBalance="1""#;
+        assert!(tags(&format!("{}>", crs)).is_empty());
+        let out = tags(&format!(r#"{} exif:WhiteBalance="0">"#, crs));
+        assert_eq!(out, vec![ExifTag::WhiteBalance(vec![0])]);
+
+        // 未声明的前缀没有命名空间
+        let mut out = Vec::new();
+        map_xmp_key_value_into_tag("", "Make", "Canon", &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn first_mapped_property_wins() {
+        let out = tags(r#"xmlns:xmp="http://ns.adobe.com/xap/1.0/" tiff:Software="A" xmp:CreatorTool="B">"#);
+        assert_eq!(out, vec![ExifTag::Software("A".to_string())]);
+    }
+
+    #[test]
+    fn iso_dates_become_exif_dates() {
+        let out = tags(concat!(
+            r#"xmlns:xmp="http://ns.adobe.com/xap/1.0/" exif:DateTimeOriginal="2024-07-14T18:05:09.42-07:00" "#,
+            r#"xmp:CreateDate="2024-07-14T18:05Z" xmp:ModifyDate="2024-07-15">"#,
+        ));
+        let expected = [
+            ExifTag::DateTimeOriginal("2024:07:14 18:05:09".to_string()),
+            ExifTag::SubSecTimeOriginal("42".to_string()),
+            ExifTag::OffsetTimeOriginal("-07:00".to_string()),
+            ExifTag::CreateDate("2024:07:14 18:05:00".to_string()),
+            ExifTag::OffsetTimeDigitized("+00:00".to_string()),
+            ExifTag::ModifyDate("2024:07:15 00:00:00".to_string()),
+        ];
+        assert_eq!(out, expected);
+
+        // 转换后拍摄时间合成标签可用
+        let composite = crate::composite::compute_composite_tags(&out, &Endian::Little);
+        let time = composite.iter().find(|t| t.name == "SubSecDateTimeOriginal").map(|t| t.value.as_str());
+        assert_eq!(time, Some("2024-07-14T18:05:09.42-07:00"));
+
+        // 无法识别的日期原样保留
+        assert_eq!(tags(r#"exif:DateTimeOriginal="2024">"#), vec![ExifTag::DateTimeOriginal("2024".to_string())]);
+        for bad in ["2024-07-14 18:05:09", "2024-07-14T18", "2024-07-14T18:05:09+0700", "2024-7-14"] {
+            assert_eq!(iso8601_to_exif_date(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn malformed_xml() {
+        for xmp in [
+            packet(r#"><exif:FNumber>28/10</exif:Aperture>"#),
+            packet(r#"exif:FNumber="28/10>"#),
+            format!(r#"<rdf:RDF xmlns:rdf="{}"><rdf:Description>"#, NS_RDF),
+        ] {
+            assert!(matches!(parse_xmp_properties(&xmp), Err(Error::MalformedXmp(_))), "{}", xmp);
+        }
+        // 没有 rdf:RDF 的合法 XML 不是错误，只是没有属性
+        assert_eq!(parse_xmp_properties("<a/>").unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,613 @@
+use little_exif::{
+    endian::Endian,
+    exif_tag::ExifTag,
+    ifd::ExifTagGroup,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::{
+    tags::get_tag_raw_value,
+    xmp::{XmpProperty, XmpValue, NS_AUX, NS_DC, NS_EXIF, NS_EXIF_EX, NS_TIFF, NS_XMP},
+};
+
+/// XMP 中使用的命名空间（前缀, URI），按此顺序声明
+const NAMESPACES: &[(&str, &str)] = &[
+    ("tiff", NS_TIFF),
+    ("exif", NS_EXIF),
+    ("exifEX", NS_EXIF_EX),
+    ("aux", NS_AUX),
+    ("xmp", NS_XMP),
+    ("dc", NS_DC),
+];
+
+/// 由前缀构造属性
+fn property(prefix: &str, name: &str, value: XmpValue) -> XmpProperty {
+    let namespace = NAMESPACES.iter().find(|(p, _)| *p == prefix).map(|(_, uri)| *uri).unwrap_or_default();
+    XmpProperty { name: format!("{}:{}", prefix, name), namespace: namespace.to_string(), value }
+}
+
+/// XMP 属性的值类型
+#[derive(Debug, Clone, Copy)]
+enum XmpKind {
+    /// 普通文本
+    Text,
+    /// 字符编码前缀（ASCII/UNICODE/JIS）开头的 UNDEF 文本
+    EncodedText,
+    /// 单个整数
+    Integer,
+    /// 单个有理数，写成 "分子/分母"
+    Rational,
+    /// 整数数组，写成 rdf:Seq
+    IntegerSeq,
+    /// 有理数数组，写成 rdf:Seq
+    RationalSeq,
+    /// 文本数组，写成 rdf:Seq（如 dc:creator）
+    TextSeq,
+    /// 多语言文本，写成 rdf:Alt + xml:lang="x-default"
+    LangAlt,
+    /// 有理数数组，以空格分隔写成一个文本（aux:LensInfo）
+    RationalList,
+    /// 4 字节 ASCII 版本号（如 "0232"）
+    Version,
+    /// GPS 版本号，写成 "2.3.0.0"
+    GpsVersion,
+    /// EXIF 日期时间，转换为 ISO 8601，附带亚秒和时区标签
+    Date { subsec: u16, offset: u16 },
+    /// GPS 经纬度，写成 "DDD,MM.mmmmmmK"，附带参考方向标签
+    GpsCoord { reference: u16, default_ref: char },
+    /// GPS 时间戳与日期戳合并为 ISO 8601 UTC 时间，没有 GPSDateStamp 时不输出
+    GpsTimeStamp,
+    /// 闪光灯，写成 exif:Flash 结构
+    Flash,
+}
+
+use XmpKind::*;
+use ExifTagGroup::{EXIF, GENERIC, GPS};
+
+/// EXIF 标签到 XMP 属性的映射（IFD 组, 标签编号, 命名空间前缀, 属性名, 值类型）
+///
+/// 参照 CIPA DC-010 与 Adobe XMP Specification Part 2，同一个标签可以对应多个属性
+const XMP_MAPPING: &[(ExifTagGroup, u16, &str, &str, XmpKind)] = &[
+    // TIFF（IFD0）
+    (GENERIC, 0x0100, "tiff", "ImageWidth", Integer),
+    (GENERIC, 0x0101, "tiff", "ImageLength", Integer),
+    (GENERIC, 0x0102, "tiff", "BitsPerSample", IntegerSeq),
+    (GENERIC, 0x0103, "tiff", "Compression", Integer),
+    (GENERIC, 0x0106, "tiff", "PhotometricInterpretation", Integer),
+    (GENERIC, 0x010e, "dc", "description", LangAlt),
+    (GENERIC, 0x010f, "tiff", "Make", Text),
+    (GENERIC, 0x0110, "tiff", "Model", Text),
+    (GENERIC, 0x0112, "tiff", "Orientation", Integer),
+    (GENERIC, 0x0115, "tiff", "SamplesPerPixel", Integer),
+    (GENERIC, 0x011a, "tiff", "XResolution", Rational),
+    (GENERIC, 0x011b, "tiff", "YResolution", Rational),
+    (GENERIC, 0x011c, "tiff", "PlanarConfiguration", Integer),
+    (GENERIC, 0x0128, "tiff", "ResolutionUnit", Integer),
+    (GENERIC, 0x012d, "tiff", "TransferFunction", IntegerSeq),
+    (GENERIC, 0x0131, "xmp", "CreatorTool", Text),
+    (GENERIC, 0x0132, "xmp", "ModifyDate", Date { subsec: 0x9290, offset: 0x9010 }),
+    (GENERIC, 0x013b, "dc", "creator", TextSeq),
+    (GENERIC, 0x013e, "tiff", "WhitePoint", RationalSeq),
+    (GENERIC, 0x013f, "tiff", "PrimaryChromaticities", RationalSeq),
+    (GENERIC, 0x0211, "tiff", "YCbCrCoefficients", RationalSeq),
+    (GENERIC, 0x0212, "tiff", "YCbCrSubSampling", IntegerSeq),
+    (GENERIC, 0x0213, "tiff", "YCbCrPositioning", Integer),
+    (GENERIC, 0x0214, "tiff", "ReferenceBlackWhite", RationalSeq),
+    (GENERIC, 0x8298, "dc", "rights", LangAlt),
+    // EXIF
+    (EXIF, 0x829a, "exif", "ExposureTime", Rational),
+    (EXIF, 0x829d, "exif", "FNumber", Rational),
+    (EXIF, 0x8822, "exif", "ExposureProgram", Integer),
+    (EXIF, 0x8824, "exif", "SpectralSensitivity", Text),
+    (EXIF, 0x8827, "exif", "ISOSpeedRatings", IntegerSeq),
+    (EXIF, 0x8830, "exifEX", "SensitivityType", Integer),
+    (EXIF, 0x8831, "exifEX", "StandardOutputSensitivity", Integer),
+    (EXIF, 0x8832, "exifEX", "RecommendedExposureIndex", Integer),
+    (EXIF, 0x8833, "exifEX", "ISOSpeed", Integer),
+    (EXIF, 0x8834, "exifEX", "ISOSpeedLatitudeyyy", Integer),
+    (EXIF, 0x8835, "exifEX", "ISOSpeedLatitudezzz", Integer),
+    (EXIF, 0x9000, "exif", "ExifVersion", Version),
+    (EXIF, 0x9003, "exif", "DateTimeOriginal", Date { subsec: 0x9291, offset: 0x9011 }),
+    (EXIF, 0x9004, "exif", "DateTimeDigitized", Date { subsec: 0x9292, offset: 0x9012 }),
+    (EXIF, 0x9004, "xmp", "CreateDate", Date { subsec: 0x9292, offset: 0x9012 }),
+    (EXIF, 0x9101, "exif", "ComponentsConfiguration", IntegerSeq),
+    (EXIF, 0x9102, "exif", "CompressedBitsPerPixel", Rational),
+    (EXIF, 0x9201, "exif", "ShutterSpeedValue", Rational),
+    (EXIF, 0x9202, "exif", "ApertureValue", Rational),
+    (EXIF, 0x9203, "exif", "BrightnessValue", Rational),
+    (EXIF, 0x9204, "exif", "ExposureBiasValue", Rational),
+    (EXIF, 0x9205, "exif", "MaxApertureValue", Rational),
+    (EXIF, 0x9206, "exif", "SubjectDistance", Rational),
+    (EXIF, 0x9207, "exif", "MeteringMode", Integer),
+    (EXIF, 0x9208, "exif", "LightSource", Integer),
+    (EXIF, 0x9209, "exif", "Flash", Flash),
+    (EXIF, 0x920a, "exif", "FocalLength", Rational),
+    (EXIF, 0x9214, "exif", "SubjectArea", IntegerSeq),
+    (EXIF, 0x9286, "exif", "UserComment", LangAlt),
+    (EXIF, 0xa000, "exif", "FlashpixVersion", Version),
+    (EXIF, 0xa001, "exif", "ColorSpace", Integer),
+    (EXIF, 0xa002, "exif", "PixelXDimension", Integer),
+    (EXIF, 0xa003, "exif", "PixelYDimension", Integer),
+    (EXIF, 0xa004, "exif", "RelatedSoundFile", Text),
+    (EXIF, 0xa20b, "exif", "FlashEnergy", Rational),
+    (EXIF, 0xa20e, "exif", "FocalPlaneXResolution", Rational),
+    (EXIF, 0xa20f, "exif", "FocalPlaneYResolution", Rational),
+    (EXIF, 0xa210, "exif", "FocalPlaneResolutionUnit", Integer),
+    (EXIF, 0xa214, "exif", "SubjectLocation", IntegerSeq),
+    (EXIF, 0xa215, "exif", "ExposureIndex", Rational),
+    (EXIF, 0xa217, "exif", "SensingMethod", Integer),
+    (EXIF, 0xa300, "exif", "FileSource", Integer),
+    (EXIF, 0xa301, "exif", "SceneType", Integer),
+    (EXIF, 0xa401, "exif", "CustomRendered", Integer),
+    (EXIF, 0xa402, "exif", "ExposureMode", Integer),
+    (EXIF, 0xa403, "exif", "WhiteBalance", Integer),
+    (EXIF, 0xa404, "exif", "DigitalZoomRatio", Rational),
+    (EXIF, 0xa405, "exif", "FocalLengthIn35mmFilm", Integer),
+    (EXIF, 0xa406, "exif", "SceneCaptureType", Integer),
+    (EXIF, 0xa407, "exif", "GainControl", Integer),
+    (EXIF, 0xa408, "exif", "Contrast", Integer),
+    (EXIF, 0xa409, "exif", "Saturation", Integer),
+    (EXIF, 0xa40a, "exif", "Sharpness", Integer),
+    (EXIF, 0xa40c, "exif", "SubjectDistanceRange", Integer),
+    (EXIF, 0xa420, "exif", "ImageUniqueID", Text),
+    (EXIF, 0xa430, "exifEX", "CameraOwnerName", Text),
+    (EXIF, 0xa431, "exifEX", "BodySerialNumber", Text),
+    (EXIF, 0xa431, "aux", "SerialNumber", Text),
+    (EXIF, 0xa432, "exifEX", "LensSpecification", RationalSeq),
+    (EXIF, 0xa432, "aux", "LensInfo", RationalList),
+    (EXIF, 0xa433, "exifEX", "LensMake", Text),
+    (EXIF, 0xa434, "exifEX", "LensModel", Text),
+    (EXIF, 0xa434, "aux", "Lens", Text),
+    (EXIF, 0xa435, "exifEX", "LensSerialNumber", Text),
+    (EXIF, 0xa435, "aux", "LensSerialNumber", Text),
+    (EXIF, 0xa500, "exifEX", "Gamma", Rational),
+    // GPS
+    (GPS, 0x0000, "exif", "GPSVersionID", GpsVersion),
+    (GPS, 0x0002, "exif", "GPSLatitude", GpsCoord { reference: 0x0001, default_ref: 'N' }),
+    (GPS, 0x0004, "exif", "GPSLongitude", GpsCoord { reference: 0x0003, default_ref: 'E' }),
+    (GPS, 0x0005, "exif", "GPSAltitudeRef", Integer),
+    (GPS, 0x0006, "exif", "GPSAltitude", Rational),
+    (GPS, 0x0007, "exif", "GPSTimeStamp", GpsTimeStamp),
+    (GPS, 0x0008, "exif", "GPSSatellites", Text),
+    (GPS, 0x0009, "exif", "GPSStatus", Text),
+    (GPS, 0x000a, "exif", "GPSMeasureMode", Text),
+    (GPS, 0x000b, "exif", "GPSDOP", Rational),
+    (GPS, 0x000c, "exif", "GPSSpeedRef", Text),
+    (GPS, 0x000d, "exif", "GPSSpeed", Rational),
+    (GPS, 0x000e, "exif", "GPSTrackRef", Text),
+    (GPS, 0x000f, "exif", "GPSTrack", Rational),
+    (GPS, 0x0010, "exif", "GPSImgDirectionRef", Text),
+    (GPS, 0x0011, "exif", "GPSImgDirection", Rational),
+    (GPS, 0x0012, "exif", "GPSMapDatum", Text),
+    (GPS, 0x0014, "exif", "GPSDestLatitude", GpsCoord { reference: 0x0013, default_ref: 'N' }),
+    (GPS, 0x0016, "exif", "GPSDestLongitude", GpsCoord { reference: 0x0015, default_ref: 'E' }),
+    (GPS, 0x0017, "exif", "GPSDestBearingRef", Text),
+    (GPS, 0x0018, "exif", "GPSDestBearing", Rational),
+    (GPS, 0x0019, "exif", "GPSDestDistanceRef", Text),
+    (GPS, 0x001a, "exif", "GPSDestDistance", Rational),
+    (GPS, 0x001b, "exif", "GPSProcessingMethod", EncodedText),
+    (GPS, 0x001c, "exif", "GPSAreaInformation", EncodedText),
+    (GPS, 0x001e, "exif", "GPSDifferential", Integer),
+    (GPS, 0x001f, "exif", "GPSHPositioningError", Rational),
+];
+
+/// 将 EXIF 标签序列化为标准 XMP 数据包（x:xmpmeta / rdf:RDF）
+///
+/// # 参数
+/// - `tags`: EXIF 标签列表
+/// - `endian`: 字节序信息
+///
+/// # 返回值
+/// - 可被 Lightroom、darktable、exiftool 读取的 XMP 文本；没有对应 XMP 属性的标签会被忽略
+pub fn build_xmp_packet(tags: &[ExifTag], endian: &Endian) -> String {
+    let mut seen: HashSet<(&str, &str)> = HashSet::new();
+    let mut props: Vec<XmpProperty> = Vec::new();
+
+    // 按映射表顺序输出，保证结果与标签顺序无关
+    for &(group, id, prefix, name, kind) in XMP_MAPPING {
+        if seen.contains(&(prefix, name)) {
+            continue;
+        }
+        let Some(tag) = lookup(tags, group, id) else { continue };
+        if let Some(value) = convert_value(tag, kind, prefix, tags, endian) {
+            seen.insert((prefix, name));
+            props.push(property(prefix, name, value));
+        }
+    }
+    serialize_xmp(&props)
+}
+
+/// 将 XMP 属性树序列化为 x:xmpmeta 数据包
+///
+/// # 参数
+/// - `props`: 顶层属性，名称使用 [`NAMESPACES`] 中的前缀
+///
+/// # 返回值
+/// - XMP 文本，只声明实际用到的命名空间
+pub fn serialize_xmp(props: &[XmpProperty]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\" x:xmptk=\"exif-reader {}\">\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    out.push_str(" <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n");
+    out.push_str("  <rdf:Description rdf:about=\"\"");
+    let mut used = HashSet::new();
+    for prop in props {
+        collect_prefixes(prop, &mut used);
+    }
+    for (prefix, uri) in NAMESPACES {
+        if used.contains(*prefix) {
+            out.push_str(&format!("\n    xmlns:{}=\"{}\"", prefix, uri));
+        }
+    }
+    out.push_str(">\n");
+    for prop in props {
+        write_element(&mut out, &prop.name, None, &prop.value, 3);
+    }
+    out.push_str("  </rdf:Description>\n </rdf:RDF>\n</x:xmpmeta>\n");
+    out
+}
+
+/// 收集属性树中用到的命名空间前缀
+fn collect_prefixes<'a>(prop: &'a XmpProperty, used: &mut HashSet<&'a str>) {
+    if let Some((prefix, _)) = prop.name.split_once(':') {
+        used.insert(prefix);
+    }
+    if let XmpValue::Struct(fields) = &prop.value {
+        for field in fields {
+            collect_prefixes(field, used);
+        }
+    }
+}
+
+/// 以元素形式写出一个属性（或数组项）
+fn write_element(out: &mut String, name: &str, lang: Option<&str>, value: &XmpValue, depth: usize) {
+    let pad = " ".repeat(depth);
+    let lang_attr = lang.map(|l| format!(" xml:lang=\"{}\"", xml_escape(l))).unwrap_or_default();
+    let mut write_array = |container: &str, items: Vec<(Option<&str>, &XmpValue)>| {
+        out.push_str(&format!("{}<{}{}>\n{} <{}>\n", pad, name, lang_attr, pad, container));
+        for (item_lang, item) in items {
+            write_element(out, "rdf:li", item_lang, item, depth + 2);
+        }
+        out.push_str(&format!("{} </{}>\n{}</{}>\n", pad, container, pad, name));
+    };
+    match value {
+        XmpValue::Simple(s) => {
+            out.push_str(&format!("{}<{n}{}>{}</{n}>\n", pad, lang_attr, xml_escape(s), n = name));
+        }
+        XmpValue::Seq(items) => write_array("rdf:Seq", items.iter().map(|v| (None, v)).collect()),
+        XmpValue::Bag(items) => write_array("rdf:Bag", items.iter().map(|v| (None, v)).collect()),
+        XmpValue::Alt(items) => write_array("rdf:Alt", items.iter().map(|(l, v)| (l.as_deref(), v)).collect()),
+        XmpValue::Struct(fields) => {
+            out.push_str(&format!("{}<{}{} rdf:parseType=\"Resource\">\n", pad, name, lang_attr));
+            for field in fields {
+                write_element(out, &field.name, None, &field.value, depth + 1);
+            }
+            out.push_str(&format!("{}</{}>\n", pad, name));
+        }
+    }
+}
+
+/// 按 IFD 组和标签编号查找标签
+fn lookup(tags: &[ExifTag], group: ExifTagGroup, id: u16) -> Option<&ExifTag> {
+    tags.iter().find(|t| t.get_group() == group && t.as_u16() == id)
+}
+
+/// 将标签值转换为指定类型的 XMP 值，无法转换时返回 None
+fn convert_value(tag: &ExifTag, kind: XmpKind, prefix: &str, tags: &[ExifTag], endian: &Endian) -> Option<XmpValue> {
+    let raw = get_tag_raw_value(tag, endian);
+    let value = match kind {
+        Text => XmpValue::Simple(raw_text(&raw)?),
+        EncodedText => XmpValue::Simple(decode_encoded_text(&raw_bytes(&raw)?, endian)?),
+        Integer => XmpValue::Simple(raw_numbers(&raw).first()?.to_string()),
+        Rational => XmpValue::Simple(raw_rationals(&raw).first()?.clone()),
+        IntegerSeq => simple_seq(non_empty(raw_numbers(&raw).iter().map(|n| n.to_string()).collect())?),
+        RationalSeq => simple_seq(non_empty(raw_rationals(&raw))?),
+        TextSeq => simple_seq(vec![raw_text(&raw)?]),
+        LangAlt => {
+            let text = match &raw {
+                Value::String(_) => raw_text(&raw)?,
+                _ => decode_encoded_text(&raw_bytes(&raw)?, endian)?,
+            };
+            XmpValue::Alt(vec![(Some("x-default".to_string()), XmpValue::Simple(text))])
+        }
+        RationalList => XmpValue::Simple(non_empty(raw_rationals(&raw))?.join(" ")),
+        Version => {
+            let bytes = raw_bytes(&raw)?;
+            let s = String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string();
+            XmpValue::Simple(non_empty_str(s)?)
+        }
+        GpsVersion => {
+            let bytes = raw_bytes(&raw)?;
+            XmpValue::Simple(non_empty(bytes.iter().map(|b| b.to_string()).collect())?.join("."))
+        }
+        Date { subsec, offset } => {
+            let text = raw_text(&raw)?;
+            let subsec = lookup(tags, EXIF, subsec).and_then(|t| raw_text(&get_tag_raw_value(t, endian)));
+            let offset = lookup(tags, EXIF, offset).and_then(|t| raw_text(&get_tag_raw_value(t, endian)));
+            XmpValue::Simple(exif_date_to_iso8601(&text, subsec.as_deref(), offset.as_deref())?)
+        }
+        GpsCoord { reference, default_ref } => {
+            let parts = raw_rational_values(&raw);
+            if parts.is_empty() {
+                return None;
+            }
+            let reference = lookup(tags, GPS, reference)
+                .and_then(|t| raw_text(&get_tag_raw_value(t, endian)))
+                .and_then(|s| s.chars().next())
+                .unwrap_or(default_ref);
+            XmpValue::Simple(format_gps_coordinate(&parts, reference))
+        }
+        GpsTimeStamp => {
+            let parts = raw_rational_values(&raw);
+            if parts.len() < 3 {
+                return None;
+            }
+            // GPSTimeStamp 是 UTC 时间，只能与 GPSDateStamp 组合；DateTimeOriginal 是本地日期，没有日期戳时不输出
+            let date = lookup(tags, GPS, 0x001d).and_then(|t| raw_text(&get_tag_raw_value(t, endian)))?;
+            let date: String = date.chars().take(10).collect::<String>().replace(':', "-");
+            if date.len() != 10 {
+                return None;
+            }
+            let seconds = parts[2];
+            let secs = if seconds.fract() == 0.0 {
+                format!("{:02}", seconds as u32)
+            } else {
+                let s = format!("{:06.3}", seconds);
+                s.trim_end_matches('0').to_string()
+            };
+            XmpValue::Simple(format!("{}T{:02}:{:02}:{}Z", date, parts[0] as u32, parts[1] as u32, secs))
+        }
+        Flash => {
+            let v = *raw_numbers(&raw).first()?;
+            let bool_str = |b: bool| if b { "True" } else { "False" }.to_string();
+            let fields = [
+                ("Fired", bool_str(v & 0x01 != 0)),
+                ("Return", ((v >> 1) & 0x03).to_string()),
+                ("Mode", ((v >> 3) & 0x03).to_string()),
+                ("Function", bool_str(v & 0x20 != 0)),
+                ("RedEyeMode", bool_str(v & 0x40 != 0)),
+            ];
+            XmpValue::Struct(
+                fields
+                    .into_iter()
+                    .map(|(f, v)| property(prefix, f, XmpValue::Simple(v)))
+                    .collect(),
+            )
+        }
+    };
+    Some(value)
+}
+
+/// 由文本列表构造 rdf:Seq
+fn simple_seq(items: Vec<String>) -> XmpValue {
+    XmpValue::Seq(items.into_iter().map(XmpValue::Simple).collect())
+}
+
+fn non_empty(v: Vec<String>) -> Option<Vec<String>> {
+    if v.is_empty() { None } else { Some(v) }
+}
+
+fn non_empty_str(s: String) -> Option<String> {
+    if s.trim().is_empty() { None } else { Some(s) }
+}
+
+/// 原始值中的文本（STRING 或 UNDEF 字节）
+fn raw_text(raw: &Value) -> Option<String> {
+    let s = match raw {
+        Value::String(s) => s.clone(),
+        _ => String::from_utf8_lossy(&raw_bytes(raw)?).to_string(),
+    };
+    non_empty_str(s.trim_end_matches('\0').trim().to_string())
+}
+
+/// 原始值中的字节序列
+fn raw_bytes(raw: &Value) -> Option<Vec<u8>> {
+    match raw {
+        Value::Array(items) => Some(items.iter().filter_map(|v| v.as_u64().map(|n| n as u8)).collect()),
+        Value::String(s) => Some(s.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// 原始值中的整数序列
+fn raw_numbers(raw: &Value) -> Vec<i64> {
+    match raw {
+        Value::Array(items) => items.iter().filter_map(|v| v.as_i64()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 原始值中的有理数，写成 "分子/分母"
+fn raw_rationals(raw: &Value) -> Vec<String> {
+    match raw {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| match v.as_array()?.as_slice() {
+                [n, d] => Some(format!("{}/{}", n.as_i64()?, d.as_i64()?)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 原始值中的有理数，计算为浮点数（分母为 0 的项视为 0）
+fn raw_rational_values(raw: &Value) -> Vec<f64> {
+    match raw {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| match v.as_array()?.as_slice() {
+                [n, d] => {
+                    let (n, d) = (n.as_f64()?, d.as_f64()?);
+                    Some(if d == 0.0 { 0.0 } else { n / d })
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 解码带 8 字节字符编码前缀的文本（UserComment、GPSProcessingMethod 等）
+fn decode_encoded_text(bytes: &[u8], endian: &Endian) -> Option<String> {
+    let (header, body) = if bytes.len() >= 8 { bytes.split_at(8) } else { (&[][..], bytes) };
+    let text = if header == b"UNICODE\0" {
+        let units: Vec<u16> = body
+            .chunks_exact(2)
+            .map(|c| match endian {
+                Endian::Big => u16::from_be_bytes([c[0], c[1]]),
+                Endian::Little => u16::from_le_bytes([c[0], c[1]]),
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else if header == b"ASCII\0\0\0" || header == b"JIS\0\0\0\0\0" || header == [0u8; 8] {
+        String::from_utf8_lossy(body).to_string()
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    };
+    non_empty_str(text.trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string())
+}
+
+/// 将 EXIF 日期时间 "YYYY:MM:DD HH:MM:SS" 转换为 ISO 8601
+///
+/// # 参数
+/// - `date`: EXIF 日期时间（已是 ISO 8601 时原样返回）
+/// - `subsec`: 亚秒（SubSecTime*）
+/// - `offset`: 时区偏移（OffsetTime*，如 "+08:00"）
+///
+/// # 返回值
+/// - 如 "2024-05-01T12:30:45.12+08:00"；日期为空或格式错误时返回 None
+pub fn exif_date_to_iso8601(date: &str, subsec: Option<&str>, offset: Option<&str>) -> Option<String> {
+    let date = date.trim();
+    let b = date.as_bytes();
+    // 已经是 ISO 8601（XMP 中无法转换为 EXIF 格式的日期原样保留）
+    if b.len() >= 10 && b[4] == b'-' && b[7] == b'-' {
+        return Some(date.to_string());
+    }
+    if b.len() < 19 || b[4] != b':' || b[7] != b':' || b[13] != b':' || b[16] != b':' {
+        return None;
+    }
+    let digits_ok = [0..4, 5..7, 8..10, 11..13, 14..16, 17..19]
+        .into_iter()
+        .all(|r| b[r].iter().all(u8::is_ascii_digit));
+    if !digits_ok {
+        return None;
+    }
+    let mut out = format!("{}-{}-{}T{}", &date[0..4], &date[5..7], &date[8..10], &date[11..19]);
+    if let Some(sub) = subsec.map(str::trim).filter(|s| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit())) {
+        out.push('.');
+        out.push_str(sub);
+    }
+    if let Some(off) = offset.map(str::trim) {
+        let ob = off.as_bytes();
+        if ob.len() == 6 && (ob[0] == b'+' || ob[0] == b'-') && ob[3] == b':' {
+            out.push_str(off);
+        } else if off == "Z" {
+            out.push('Z');
+        }
+    }
+    Some(out)
+}
+
+/// 将度/分/秒格式化为 XMP GPS 坐标 "DDD,MM.mmmmmmK"
+fn format_gps_coordinate(parts: &[f64], reference: char) -> String {
+    let total = parts.iter().zip([1.0, 60.0, 3600.0]).map(|(v, div)| v / div).sum::<f64>();
+    let degrees = total.trunc();
+    let minutes = (total - degrees) * 60.0;
+    format!("{},{:.6}{}", degrees as u32, minutes, reference.to_ascii_uppercase())
+}
+
+/// 转义 XML 文本中的特殊字符
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xmp::{parse_xmp_properties, parse_xmp_to_exif_tags};
+    use little_exif::rational::{iR64, uR64};
+
+    fn r(nominator: u32, denominator: u32) -> uR64 {
+        uR64 { nominator, denominator }
+    }
+
+    /// 生成 XMP 并重新解析为属性列表
+    fn round_trip(tags: &[ExifTag]) -> Vec<XmpProperty> {
+        parse_xmp_properties(&build_xmp_packet(tags, &Endian::Little)).unwrap()
+    }
+
+    fn find<'a>(props: &'a [XmpProperty], namespace: &str, local: &str) -> Option<&'a XmpValue> {
+        props.iter().find(|p| p.is(namespace, local)).map(|p| &p.value)
+    }
+
+    fn simple(s: &str) -> XmpValue {
+        XmpValue::Simple(s.to_string())
+    }
+
+    #[test]
+    fn round_trip_through_parser() {
+        let tags = [
+            ExifTag::Make("Canon".to_string()),
+            ExifTag::ImageDescription("a & <b> \"c\"".to_string()),
+            ExifTag::FNumber(vec![r(28, 10)]),
+            ExifTag::ExposureCompensation(vec![iR64 { nominator: -2, denominator: 3 }]),
+            ExifTag::ISO(vec![100, 200]),
+            ExifTag::DateTimeOriginal("2024:07:14 18:05:09".to_string()),
+            ExifTag::SubSecTimeOriginal("42".to_string()),
+            ExifTag::OffsetTimeOriginal("-07:00".to_string()),
+            ExifTag::Flash(vec![0x19]),
+            ExifTag::GPSLatitudeRef("S".to_string()),
+            ExifTag::GPSLatitude(vec![r(35, 1), r(30, 1), r(36, 1)]),
+            ExifTag::GPSLongitude(vec![r(139, 1), r(45, 1), r(0, 1)]),
+        ];
+        let props = round_trip(&tags);
+
+        assert_eq!(find(&props, NS_TIFF, "Make"), Some(&simple("Canon")));
+        // XML 特殊字符转义后能原样读回
+        assert_eq!(find(&props, NS_DC, "description").and_then(XmpValue::as_text), Some("a & <b> \"c\""));
+        assert_eq!(find(&props, NS_EXIF, "FNumber"), Some(&simple("28/10")));
+        assert_eq!(find(&props, NS_EXIF, "ExposureBiasValue"), Some(&simple("-2/3")));
+        assert_eq!(find(&props, NS_EXIF, "ISOSpeedRatings"), Some(&XmpValue::Seq(vec![simple("100"), simple("200")])));
+        assert_eq!(find(&props, NS_EXIF, "DateTimeOriginal"), Some(&simple("2024-07-14T18:05:09.42-07:00")));
+        // 经纬度参考方向并入坐标，缺少时默认北纬、东经
+        assert_eq!(find(&props, NS_EXIF, "GPSLatitude"), Some(&simple("35,30.600000S")));
+        assert_eq!(find(&props, NS_EXIF, "GPSLongitude"), Some(&simple("139,45.000000E")));
+
+        let flash = find(&props, NS_EXIF, "Flash").unwrap();
+        for (field, expected) in [("Fired", "True"), ("Return", "0"), ("Mode", "3"), ("Function", "False"), ("RedEyeMode", "False")] {
+            assert_eq!(flash.field(NS_EXIF, field), Some(&simple(expected)), "{}", field);
+        }
+
+        // 再映射回 EXIF 标签
+        let back = parse_xmp_to_exif_tags(&build_xmp_packet(&tags, &Endian::Little)).unwrap();
+        for tag in &tags[..4] {
+            assert!(back.contains(tag), "{:?}", tag);
+        }
+        // 数组只保留第一项；日期拆回 EXIF 格式及亚秒、时区
+        assert!(back.contains(&ExifTag::ISO(vec![100])));
+        for tag in &tags[5..9] {
+            assert!(back.contains(tag), "{:?}", tag);
+        }
+    }
+
+    #[test]
+    fn gps_timestamp_needs_date_stamp() {
+        let time = ExifTag::GPSTimeStamp(vec![r(9, 1), r(5, 1), r(75, 10)]);
+        let local = ExifTag::DateTimeOriginal("2024:07:15 02:05:07".to_string());
+
+        // 本地拍摄日期不能作为 UTC 日期
+        let props = round_trip(&[time.clone(), local.clone()]);
+        assert_eq!(find(&props, NS_EXIF, "GPSTimeStamp"), None);
+
+        let date = ExifTag::GPSDateStamp("2024:07:14".to_string());
+        let props = round_trip(&[time, local, date]);
+        assert_eq!(find(&props, NS_EXIF, "GPSTimeStamp"), Some(&simple("2024-07-14T09:05:07.5Z")));
+    }
+}